@@ -36,6 +36,32 @@ const generatedNodes = [
   `WaveShaperNode`,
 ];
 
+// Non spec, effect nodes that can be bypassed, cf. js/lib/bypass.js
+const bypassableNodes = [
+  'BiquadFilterNode',
+  'ConvolverNode',
+  'DynamicsCompressorNode',
+  'WaveShaperNode',
+];
+
+// Non spec, params whose nominal range is bounded by the Nyquist frequency, the
+// range is reported at the rate of the context, cf. renderOversample
+const nyquistParams = {
+  BiquadFilterNode: ['frequency'],
+  OscillatorNode: ['frequency'],
+};
+
+// Non spec, enum attributes that throw on an unknown value instead of ignoring
+// it, with the documentation of their values emitted above the setter
+const strictEnumAttributes = {
+  BiquadFilterNode: {
+    type: [
+      'lowpass, highpass, bandpass, notch and allpass ignore the gain param,',
+      'lowshelf and highshelf ignore Q, peaking uses both',
+    ],
+  },
+};
+
 const __dirname = path.dirname(fileURLToPath(import.meta.url));
 
 // @todo - read idl from wpt directory
@@ -317,7 +343,7 @@ async function beautifyAndLint(pathname, code) {
 
   const codeTmpl = fs.readFileSync(input, 'utf8');
   const tmpl = compile(codeTmpl);
-  const code = tmpl({ node: nodeIdl, tree, ...utils });
+  const code = tmpl({ node: nodeIdl, tree, bypassableNodes, nyquistParams, strictEnumAttributes, ...utils });
 
   beautifyAndLint(output, generatedPrefix(code));
 });
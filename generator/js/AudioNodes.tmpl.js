@@ -5,7 +5,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -14,13 +16,98 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+${d.bypassableNodes.includes(d.name(d.node)) ? `
+const {
+  isBypassed,
+  setBypass,
+} = require('./lib/bypass.js');
+` : ``}
+${d.name(d.node) === 'DelayNode' ? `
+const {
+  kFeedbackGuard,
+} = require('./lib/symbols.js');
+const {
+  createFeedbackGuard,
+} = require('./lib/feedback.js');
+` : ``}
+${d.name(d.node) === 'AudioBufferSourceNode' ? `
+const {
+  kInsertOutputGate,
+  kStartTime,
+} = require('./lib/symbols.js');
+const {
+  kPauseFadeQuanta,
+  holdAutomation,
+  scheduleAutomationEvents,
+} = require('./lib/pause.js');
+// non spec, cf. pause
+const kRenderQuantumSize = 128;
+` : ``}
+${d.name(d.node) === 'ConvolverNode' ? `
+const {
+  createImpulseSwap,
+  swapImpulse,
+  resetImpulseSwap,
+  toRenderImpulse,
+} = require('./lib/convolver.js');
+` : ``}
+${d.name(d.node) === 'GainNode' ? `
+// non spec, lowest value reached by an exponential fade, i.e. -120dB
+const kFadeEpsilon = 1e-6;
+` : ``}
+${d.name(d.node) === 'AnalyserNode' ? `
+const {
+  kChannelAnalysers,
+} = require('./lib/symbols.js');
+const {
+  kWindowFunctions,
+  WindowedSpectrum,
+  createChannelAnalysers,
+  getChannelAnalyser,
+} = require('./lib/analyser.js');
+` : ``}
 
 const ${d.parent(d.node)} = require('./${d.parent(d.node)}.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class ${d.name(d.node)} extends ${d.parent(d.node)} {
+    ${d.name(d.node) === 'AnalyserNode' ? `
+    // non spec, cf. windowFunction
+    #windowedSpectrum = new WindowedSpectrum();
+    // Uint8Array given to the last native getByte* call, repeated calls with
+    // the same array skip its validation and the native side reuses its
+    // backing store. A detached or resized array has a different length,
+    // which invalidates the cache.
+    #byteArray = null;
+    #byteArrayLength = 0;
+    ` : ``}
+    ${d.name(d.node) === 'WaveShaperNode' ? `
+    // non spec, cf. curveLength
+    #curveLength = 0;
+    ` : ``}
+    ${d.name(d.node) === 'ConvolverNode' ? `
+    // non spec, cf. swapBuffer
+    #impulseSwap = null;
+    ` : ``}
+    ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+    // value of the loop attribute, cf. #syncLoop
+    #loop = false;
+    // non spec, cf. resampleBuffer
+    #resampleBuffer = false;
+    // non spec, cf. pause
+    #gate = null;
+    #paused = null;
+    // non spec, cf. getPlaybackPosition
+    #ended = false;
+    ` : ``}
+    ${d.name(d.node) === 'DelayNode' ? `
+    // non spec, cf. maxDelayTime
+    #maxDelayTime = null;
+    ` : ``}
     ${d.audioParams(d.node).map(param => {
       return `
     #${d.name(param)} = null`;
@@ -245,6 +332,72 @@ module.exports = (jsExport, nativeBinding) => {
           `;
         }
 
+        if (d.name(d.node) === 'DelayNode') {
+          // non spec, soft limit the output of the node, cf. lib/feedback.js
+          checkOptions += `
+      if (options && options.clampFeedback !== undefined) {
+        parsedOptions.clampFeedback = conversions['boolean'](options.clampFeedback);
+      } else {
+        parsedOptions.clampFeedback = false;
+      }
+          `;
+        }
+
+        if (d.name(d.node) === 'AnalyserNode') {
+          // non spec, analyse each input channel separately, cf. lib/analyser.js
+          checkOptions += `
+      if (options && options.channelMode !== undefined) {
+        if (!['downmix', 'perChannel'].includes(options.channelMode)) {
+          throw new TypeError(\`Failed to construct '${d.name(d.node)}': Failed to read the 'channelMode' property from ${optionsType}: The provided value '\${options.channelMode}' is not a valid channel mode, should be one of 'downmix', 'perChannel'\`);
+        }
+
+        parsedOptions.channelMode = options.channelMode;
+      } else {
+        parsedOptions.channelMode = 'downmix';
+      }
+          `;
+        }
+
+        if (d.name(d.node) === 'AudioBufferSourceNode') {
+          // non spec, give the native node a copy of the buffer at the rate
+          // the graph is rendered at, cf. resampleBuffer
+          checkOptions += `
+      if (options && options.resampleBuffer !== undefined) {
+        parsedOptions.resampleBuffer = conversions['boolean'](options.resampleBuffer);
+      } else {
+        parsedOptions.resampleBuffer = false;
+      }
+
+      if (
+        parsedOptions.resampleBuffer
+        && parsedOptions.buffer !== null
+        && parsedOptions.buffer.sampleRate !== context[kNapiObj].sampleRate
+      ) {
+        parsedOptions.buffer = parsedOptions.buffer.resample(context[kNapiObj].sampleRate);
+      }
+          `;
+        }
+
+        if (d.parent(d.node) === 'AudioScheduledSourceNode') {
+          checkOptions += `
+      // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+      if (options && options.trackActualStartTime !== undefined) {
+        parsedOptions.trackActualStartTime = conversions['boolean'](options.trackActualStartTime);
+      } else {
+        parsedOptions.trackActualStartTime = false;
+      }
+          `;
+        }
+
+        if (d.name(d.node) === 'ConvolverNode') {
+          // non spec, cf. OfflineAudioContext renderOversample
+          checkOptions += `
+      if (parsedOptions.buffer !== null) {
+        parsedOptions.buffer = toRenderImpulse(context, parsedOptions.buffer);
+      }
+          `;
+        }
+
         // audio node options
         if (d.parent(optionsIdl) === 'AudioNodeOptions') {
           // Real check is done on rust side, let's just convert values to proper IDL type
@@ -273,6 +426,12 @@ module.exports = (jsExport, nativeBinding) => {
         return checkOptions;
       }())}
 
+      ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+      // cf. #syncLoop
+      const loop = parsedOptions.loop;
+      parsedOptions.loop = loop && parsedOptions.buffer !== null && options.buffer.length > 0;
+      ` : ``}
+
       let napiObj;
 
       try {
@@ -315,8 +474,45 @@ module.exports = (jsExport, nativeBinding) => {
         return `
       this.#${d.name(param)} = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].${d.name(param)},
+        context,
+        ${(d.nyquistParams[d.name(d.node)] || []).includes(d.name(param)) ? `nyquist: true,` : ``}
       });`;
       }).join('')}
+
+      ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+      this.#loop = loop;
+      this.#resampleBuffer = parsedOptions.resampleBuffer;
+      ` : ``}
+
+      ${d.name(d.node) === 'WaveShaperNode' ? `
+      if (parsedOptions.curve !== null) {
+        this.#curveLength = parsedOptions.curve.length;
+      }
+      ` : ``}
+
+      ${d.name(d.node) === 'AnalyserNode' ? `
+      // non spec, split the input into one analyser per channel, cf. channelMode
+      Object.defineProperty(this, kChannelAnalysers, {
+        __proto__: null,
+        enumerable: false,
+        value: parsedOptions.channelMode === 'perChannel'
+          ? createChannelAnalysers(this, this.channelCount, jsExport)
+          : null,
+      });
+      ` : ``}
+
+      ${d.name(d.node) === 'DelayNode' ? `
+      // non spec, route the output of the node through a limiter, cf. clampFeedback
+      Object.defineProperty(this, kFeedbackGuard, {
+        __proto__: null,
+        enumerable: false,
+        value: parsedOptions.clampFeedback
+          ? createFeedbackGuard(this, jsExport.GainNode, jsExport.WaveShaperNode)
+          : null,
+      });
+
+      this.#maxDelayTime = parsedOptions.maxDelayTime;
+      ` : ``}
     }
 
 ${d.audioParams(d.node).map(param => {
@@ -331,6 +527,433 @@ ${d.audioParams(d.node).map(param => {
   `;
 }).join('')}
 
+${d.bypassableNodes.includes(d.name(d.node)) ? `
+    // non spec
+    get bypass() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return isBypassed(this);
+    }
+
+    // non spec
+    set bypass(value) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      value = conversions['boolean'](value);
+      // the dry / wet routing is created on first bypass, cf. lib/bypass.js
+      setBypass(this, value, jsExport.GainNode);
+    }
+` : ``}
+
+
+${d.name(d.node) === 'ConvolverNode' ? `
+    // non spec, length of the buffer without copying its data to JS
+    get bufferLength() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this[kAudioBuffer] !== null ? this[kAudioBuffer].length : 0;
+    }
+
+    // non spec, set the impulse response of the node with a crossfade of
+    // \`crossfadeSeconds\` (defaults to 0.05) between the old and the new
+    // impulse responses, to prevent clicks. A crossfade of 0 is equivalent to
+    // setting the \`buffer\` attribute. A swap requested while a crossfade is
+    // running restarts the crossfade from the current mix.
+    swapBuffer(buffer, options = {}) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(\`Failed to execute 'swapBuffer' on '${d.name(d.node)}': 1 argument required, but only \${arguments.length} present\`);
+      }
+
+      if (!(buffer instanceof jsExport.AudioBuffer)) {
+        throw new TypeError(\`Failed to execute 'swapBuffer' on '${d.name(d.node)}': Parameter 1 is not of type 'AudioBuffer'\`);
+      }
+
+      if (options === null || typeof options !== 'object') {
+        throw new TypeError(\`Failed to execute 'swapBuffer' on '${d.name(d.node)}': Parameter 2 is not of type 'object'\`);
+      }
+
+      let crossfadeSeconds = 0.05;
+
+      if (options.crossfadeSeconds !== undefined) {
+        crossfadeSeconds = conversions['double'](options.crossfadeSeconds, {
+          context: \`Failed to execute 'swapBuffer' on '${d.name(d.node)}': Failed to read the 'crossfadeSeconds' property from options: The provided value (\${options.crossfadeSeconds})\`,
+        });
+      }
+
+      if (crossfadeSeconds < 0) {
+        throw new RangeError(\`Failed to execute 'swapBuffer' on '${d.name(d.node)}': crossfadeSeconds (\${crossfadeSeconds}) should be positive\`);
+      }
+
+      if (crossfadeSeconds === 0) {
+        this.buffer = buffer;
+        return;
+      }
+
+      if (this.#impulseSwap === null) {
+        this.#impulseSwap = createImpulseSwap(this, nativeBinding, jsExport.GainNode, jsExport.ConstantSourceNode);
+      }
+
+      // cf. OfflineAudioContext renderOversample
+      const napiBuffer = toRenderImpulse(this.context, buffer[kNapiObj]);
+
+      try {
+        swapImpulse(this.#impulseSwap, napiBuffer, crossfadeSeconds);
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      this[kAudioBuffer] = buffer;
+    }
+` : ``}
+
+${d.name(d.node) === 'WaveShaperNode' ? `
+    // non spec, length of the curve without copying its data to JS
+    get curveLength() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this.#curveLength;
+    }
+` : ``}
+
+${d.name(d.node) === 'AudioBufferSourceNode' ? `
+    // non spec, loop boundaries in sample frames of the buffer, i.e. converted
+    // from and to \`loopStart\` and \`loopEnd\` using the sample rate of the
+    // buffer (or of the context if no buffer is set)
+    get loopStartFrame() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return Math.round(this.loopStart * this.#loopSampleRate());
+    }
+
+    // non spec
+    set loopStartFrame(value) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      value = conversions['unsigned long'](value, {
+        enforceRange: true,
+        context: \`Failed to set the 'loopStartFrame' property on '${d.name(d.node)}': Value\`,
+      });
+
+      this.loopStart = value / this.#loopSampleRate();
+    }
+
+    // non spec
+    get loopEndFrame() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return Math.round(this.loopEnd * this.#loopSampleRate());
+    }
+
+    // non spec
+    set loopEndFrame(value) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      value = conversions['unsigned long'](value, {
+        enforceRange: true,
+        context: \`Failed to set the 'loopEndFrame' property on '${d.name(d.node)}': Value\`,
+      });
+
+      this.loopEnd = value / this.#loopSampleRate();
+    }
+
+    // non spec, if true the buffer is resampled to the rate of the context
+    // (i.e. the oversampled rate, cf. OfflineAudioContext renderOversample)
+    // once when it is assigned, instead of being interpolated while rendering.
+    // The \`buffer\` attribute still returns the assigned buffer.
+    get resampleBuffer() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this.#resampleBuffer;
+    }
+
+    #loopSampleRate() {
+      return this[kAudioBuffer] !== null ? this[kAudioBuffer].sampleRate : this.context.sampleRate;
+    }
+
+    // Looping over an empty buffer crashes the renderer while the source should
+    // just render silence and end, hence looping is only enabled on the native
+    // node once a buffer with some frames is set
+    #syncLoop() {
+      const buffer = this[kAudioBuffer];
+
+      try {
+        this[kNapiObj].loop = this.#loop && buffer !== null && buffer.length > 0;
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+    }
+
+    // non spec, pauses the playback at \`currentTime\`: the output is faded out
+    // over a few milliseconds, then the playhead is held and the output is
+    // silenced until \`resume\` is called, which fades the output in and continues
+    // the playback from the same position. The \`ended\` event is not dispatched
+    // on pause, nor is the time spent paused counted in the \`duration\` given to
+    // \`start\`.
+    //
+    // The playhead is held by setting the playbackRate to 0 at the end of the
+    // fade out. The automation events of the playbackRate scheduled after that
+    // time are saved and scheduled again on resume, delayed by the time spent
+    // paused. The value of a k-rate param is computed before the events
+    // scheduled at the start of the render quantum are applied, hence pause and
+    // resume are effective one render quantum after \`currentTime\`.
+    pause() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (this.#paused !== null) {
+        return;
+      }
+
+      // the gate is only inserted once needed, cf. AudioNode::connect
+      if (this.#gate === null) {
+        this.#gate = new jsExport.GainNode(this.context);
+        this[kInsertOutputGate](this.#gate);
+      }
+
+      const quantum = this.#renderQuantumDuration();
+      const now = this.context.currentTime;
+      // the playhead is held once the output is faded out
+      const holdTime = now + kPauseFadeQuanta * quantum;
+      const events = holdAutomation(this.#playbackRate, now, holdTime, 0);
+
+      this.#fadeGate(0, now + quantum);
+      this.#paused = { holdTime, events };
+    }
+
+    // non spec, cf. pause
+    resume() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (this.#paused === null) {
+        return;
+      }
+
+      const now = this.context.currentTime;
+      const { holdTime, events } = this.#paused;
+      // if resumed during the fade out, the playhead is not held
+      const resumeTime = Math.max(now, holdTime);
+
+      // the events scheduled while paused are replaced by the saved ones
+      holdAutomation(this.#playbackRate, now, resumeTime, 0);
+      scheduleAutomationEvents(this.#playbackRate, events, resumeTime - holdTime);
+      this.#fadeGate(1, now + this.#renderQuantumDuration());
+      this.#paused = null;
+    }
+
+    // ramp the gain of the gate from its current value at \`time\`
+    #fadeGate(value, time) {
+      const gain = this.#gate.gain;
+      const duration = kPauseFadeQuanta * this.#renderQuantumDuration();
+
+      holdAutomation(gain, this.context.currentTime, time, gain.getValueAtTime(time));
+      gain.linearRampToValueAtTime(value, time + duration);
+    }
+
+    // the rate of the napi context differs from \`sampleRate\` if the graph is
+    // oversampled, cf. OfflineAudioContext renderOversample
+    #renderQuantumDuration() {
+      return kRenderQuantumSize / this.context[kNapiObj].sampleRate;
+    }
+
+    // non spec, current position of the playhead in the buffer in seconds,
+    // i.e. the offset given to \`start\` plus the time played scaled by the
+    // playbackRate, wrapped into the loop if any. Returns -1 before the start
+    // time is reached and once the source has ended. The position is updated at
+    // the end of each render quantum.
+    getPlaybackPosition() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (this.getActualStartTime() === null || this.#ended) {
+        return -1;
+      }
+
+      return this[kNapiObj].position();
+    }
+` : ``}
+
+${d.name(d.node) === 'GainNode' ? `
+    // non spec, ramps the gain from its current value to \`targetValue\` over
+    // \`duration\` seconds starting at \`currentTime\`, e.g. to declick the
+    // start or the end of a sound. An exponential fade can't reach or start
+    // from zero, so it goes through \`kFadeEpsilon\` instead.
+    fade(targetValue, duration, options = {}) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (arguments.length < 2) {
+        throw new TypeError(\`Failed to execute 'fade' on '${d.name(d.node)}': 2 arguments required, but only \${arguments.length} present\`);
+      }
+
+      targetValue = conversions['float'](targetValue, {
+        context: \`Failed to execute 'fade' on '${d.name(d.node)}': Parameter 1\`,
+      });
+
+      duration = conversions['double'](duration, {
+        context: \`Failed to execute 'fade' on '${d.name(d.node)}': Parameter 2\`,
+      });
+
+      if (duration < 0) {
+        throw new RangeError(\`Failed to execute 'fade' on '${d.name(d.node)}': Parameter 2 (\${duration}) must be greater than or equal to 0\`);
+      }
+
+      if (typeof options !== 'object' || options === null) {
+        throw new TypeError(\`Failed to execute 'fade' on '${d.name(d.node)}': Parameter 3 is not of type 'object'\`);
+      }
+
+      const curve = options.curve === undefined ? 'linear' : options.curve;
+
+      if (!['linear', 'exponential'].includes(curve)) {
+        throw new TypeError(\`Failed to execute 'fade' on '${d.name(d.node)}': The provided value '\${curve}' is not a valid fade curve, should be one of 'linear', 'exponential'\`);
+      }
+
+      const gain = this.gain;
+      const startTime = this.context.currentTime;
+      const endTime = startTime + duration;
+
+      // keep the value of an ongoing automation
+      gain.cancelAndHoldAtTime(startTime);
+
+      if (curve === 'linear') {
+        gain.linearRampToValueAtTime(targetValue, endTime);
+      } else {
+        if (gain.getValueAtTime(startTime) < kFadeEpsilon) {
+          gain.setValueAtTime(kFadeEpsilon, startTime);
+        }
+
+        gain.exponentialRampToValueAtTime(Math.max(targetValue, kFadeEpsilon), endTime);
+
+        if (targetValue < kFadeEpsilon) {
+          gain.setValueAtTime(targetValue, endTime);
+        }
+      }
+    }
+` : ``}
+
+${d.name(d.node) === 'DelayNode' ? `
+    // non spec
+    get clampFeedback() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this[kFeedbackGuard] !== null;
+    }
+
+    // non spec
+    get maxDelayTime() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this.#maxDelayTime;
+    }
+
+    // non spec, number of frames (per channel) reserved by the native delay
+    // line, i.e. \`maxDelayTime\` rounded up to a render quantum at the rate of
+    // the render graph, plus the render quantum being written
+    getAllocatedFrames() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this[kNapiObj].allocatedFrames();
+    }
+` : ``}
+
+${d.name(d.node) === 'AnalyserNode' ? `
+    // non spec
+    get windowFunction() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this.#windowedSpectrum.windowFunction;
+    }
+
+    // non spec
+    set windowFunction(value) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (!kWindowFunctions.includes(value)) {
+        throw new TypeError(\`Failed to set the 'windowFunction' property on '${d.name(d.node)}': Value '\${value}' is not a valid window function, should be one of \${kWindowFunctions.map(name => \`'\${name}'\`).join(', ')}\`);
+      }
+
+      this.#windowedSpectrum.windowFunction = value;
+    }
+
+    // non spec, 'perChannel' if each of the first \`channelCount\` input
+    // channels (as set at construction) can be analysed separately
+    get channelMode() {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      return this[kChannelAnalysers] !== null ? 'perChannel' : 'downmix';
+    }
+
+    // non spec, same as getFloatFrequencyData for a single input channel
+    getFloatFrequencyDataForChannel(channelNumber, array) {
+      if (!(this instanceof ${d.name(d.node)})) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
+      }
+
+      if (arguments.length < 2) {
+        throw new TypeError(\`Failed to execute 'getFloatFrequencyDataForChannel' on '${d.name(d.node)}': 2 arguments required, but only \${arguments.length} present\`);
+      }
+
+      channelNumber = conversions['unsigned long'](channelNumber, {
+        enforceRange: true,
+        context: \`Failed to execute 'getFloatFrequencyDataForChannel' on '${d.name(d.node)}': Parameter 1\`,
+      });
+
+      if (!(array instanceof Float32Array)) {
+        throw new TypeError(\`Failed to execute 'getFloatFrequencyDataForChannel' on '${d.name(d.node)}': Parameter 2 is not of type 'Float32Array'\`);
+      }
+
+      const routing = this[kChannelAnalysers];
+
+      if (routing === null) {
+        throw new DOMException(\`Failed to execute 'getFloatFrequencyDataForChannel' on '${d.name(d.node)}': The node must be created with 'channelMode' set to 'perChannel'\`, 'InvalidStateError');
+      }
+
+      if (channelNumber >= routing.analysers.length) {
+        throw new DOMException(\`Failed to execute 'getFloatFrequencyDataForChannel' on '${d.name(d.node)}': The channel number provided (\${channelNumber}) is outside the range [0, \${routing.analysers.length - 1}]\`, 'IndexSizeError');
+      }
+
+      getChannelAnalyser(routing, this, channelNumber).getFloatFrequencyData(array);
+    }
+` : ``}
+
 ${d.attributes(d.node).map(attr => {
   // ------------------------------------------------------
   // Getters / Setters
@@ -357,13 +980,15 @@ ${d.attributes(d.node).map(attr => {
       break;
     }
     default: {
+      const isLoop = d.name(d.node) === 'AudioBufferSourceNode' && d.name(attr) === 'loop';
+
       getter = `
     get ${d.name(attr)}() {
       if (!(this instanceof ${d.name(d.node)})) {
         throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
       }
 
-      return this[kNapiObj].${d.name(attr)};
+      return ${isLoop ? `this.#loop` : `this[kNapiObj].${d.name(attr)}`};
     }
       `;
       break;
@@ -392,13 +1017,18 @@ ${d.attributes(d.node).map(attr => {
       value = conversions['${type}'](value, {
         context: \`Failed to set the '${d.name(attr)}' property on '${d.name(d.node)}': Value\`
       });
-
+      ${d.name(d.node) === 'AudioBufferSourceNode' && d.name(attr) === 'loop' ? `
+      this.#loop = value;
+      this.#syncLoop();
+    }
+      ` : `
       try {
         this[kNapiObj].${d.name(attr)} = value;
       } catch (err) {
         throwSanitizedError(err);
       }
     }
+      `}
         `;
         break;
       }
@@ -441,17 +1071,28 @@ ${d.attributes(d.node).map(attr => {
         // (for example as an operation argument) results in an exception being thrown.
         const typeIdl = d.findInTree(type);
         const values = JSON.stringify(typeIdl.values.map(e => e.value));
+        const strictDoc = (d.strictEnumAttributes[d.name(d.node)] || {})[d.name(attr)];
 
-        setter = `
+        setter = `${strictDoc ? `
+    ${strictDoc.map(line => `// ${line}`).join(`
+    `)}` : ``}
     set ${d.name(attr)}(value) {
       if (!(this instanceof ${d.name(d.node)})) {
         throw new TypeError("Invalid Invocation: Value of 'this' must be of type '${d.name(d.node)}'");
       }
 
+      ${strictDoc ? `
+      // Non-spec: an unknown value throws instead of being ignored, as
+      // silently keeping the previous value makes mistakes hard to spot
+      if (!${values}.includes(value)) {
+        throw new TypeError(\`Failed to set the '${d.name(attr)}' property on '${d.name(d.node)}': The provided value '\${value}' is not a valid enum value of type ${type}\`);
+      }
+      ` : `
       if (!${values}.includes(value)) {
         console.warn(\`Failed to set the '${d.name(attr)}' property on '${d.name(d.node)}': Value '\${value}' is not a valid '${type}' enum value\`);
         return;
       }
+      `}
 
       try {
         this[kNapiObj].${d.name(attr)} = value;
@@ -483,6 +1124,9 @@ ${d.attributes(d.node).map(attr => {
       } catch (err) {
         throwSanitizedError(err);
       }
+      ${d.name(d.node) === 'WaveShaperNode' ? `
+      this.#curveLength = value.length;
+      ` : ``}
     }
         `;
         break;
@@ -504,13 +1148,37 @@ ${d.attributes(d.node).map(attr => {
         throw new TypeError("Failed to set the '${d.name(attr)}' property on '${d.name(d.node)}': Failed to convert value to '${type}'");
       }
 
+      ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+      let napiBuffer = value[kNapiObj];
+      const renderSampleRate = this.context[kNapiObj].sampleRate;
+
+      if (this.#resampleBuffer && napiBuffer.sampleRate !== renderSampleRate) {
+        napiBuffer = napiBuffer.resample(renderSampleRate);
+      }
+      ` : ``}
+      ${d.name(d.node) === 'ConvolverNode' ? `
+      // cf. OfflineAudioContext renderOversample
+      const napiBuffer = toRenderImpulse(this.context, value[kNapiObj]);
+      ` : ``}
+
       try {
-        this[kNapiObj].${d.name(attr)} = value[kNapiObj];
+        this[kNapiObj].${d.name(attr)} = ${['AudioBufferSourceNode', 'ConvolverNode'].includes(d.name(d.node))
+          ? `napiBuffer`
+          : `value[kNapiObj]`};
       } catch (err) {
         throwSanitizedError(err);
       }
 
       this[kAudioBuffer] = value;
+      ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+      this.#syncLoop();
+      ` : ``}
+      ${d.name(d.node) === 'ConvolverNode' ? `
+      // the buffer is given to the node, cf. swapBuffer
+      if (this.#impulseSwap !== null) {
+        resetImpulseSwap(this.#impulseSwap);
+      }
+      ` : ``}
     }
         `;
         break;
@@ -556,11 +1224,22 @@ ${d.methods(d.node, false).map(method => {
             `;
             break;
           }
-          case 'Float32Array':
-          case 'Uint8Array': {
+          case 'Float32Array': {
             argCheck += `
       if (!(${name} instanceof ${type})) {
         throw new TypeError(\`Failed to execute '${d.name(method)}' on '${d.name(d.node)}': Parameter ${index + 1} is not of type '${type}'\`);
+      }
+            `;
+            break;
+          }
+          case 'Uint8Array': {
+            // non spec, fast path for visualizers calling the AnalyserNode
+            // getByte* methods with the same array on each frame
+            argCheck += `
+      const cached = ${name} === this.#byteArray && ${name}.length === this.#byteArrayLength;
+
+      if (!cached && !(${name} instanceof ${type})) {
+        throw new TypeError(\`Failed to execute '${d.name(method)}' on '${d.name(d.node)}': Parameter ${index + 1} is not of type '${type}'\`);
       }
             `;
             break;
@@ -594,11 +1273,50 @@ ${d.methods(d.node, false).map(method => {
         return argCheck;
       }).join('')}
 
+      ${d.name(d.node) === 'AnalyserNode' && ['getFloatFrequencyData', 'getByteFrequencyData'].includes(d.name(method)) ? `
+      // non spec, the default window is computed natively
+      if (this.#windowedSpectrum.windowFunction !== 'blackman') {
+        this.#windowedSpectrum.${d.name(method)}(this[kNapiObj], ${args[0].name});
+        return;
+      }
+      ` : ``}
+
+      ${d.name(d.node) === 'AnalyserNode' && ['getByteFrequencyData', 'getByteTimeDomainData'].includes(d.name(method)) ? `
+      try {
+        if (cached) {
+          // the native side writes into the array it has resolved last
+          this[kNapiObj].${d.name(method)}();
+        } else {
+          this[kNapiObj].${d.name(method)}(${args[0].name});
+          this.#byteArray = ${args[0].name};
+          this.#byteArrayLength = ${args[0].name}.length;
+        }
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+      ` : d.parent(d.node) === 'AudioScheduledSourceNode' && d.name(method) === 'start' ? `
+      ${args[0].name} = clampToCurrentTime(this.context, ${args[0].name});
+
+      try {
+        this[kNapiObj].${d.name(method)}(${args.map(arg => arg.name).join(', ')});
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      // keep track of the started sources, cf. BaseAudioContext::panic
+      this.context[kScheduledSourceNodes].add(this);
+      // cf. AudioScheduledSourceNode::getActualStartTime
+      this[kStartTime] = ${args[0].name};
+      ${d.name(d.node) === 'AudioBufferSourceNode' ? `
+      this.addEventListener('ended', () => this.#ended = true, { once: true });
+      ` : ``}
+      ` : `
       try {
         return this[kNapiObj].${d.name(method)}(${args.map(arg => arg.name).join(', ')});
       } catch (err) {
         throwSanitizedError(err);
       }
+      `}
     }
 `}).join('')}
   }
@@ -617,6 +1335,14 @@ ${(function() {
       configurable: true,
       value: ${d.minRequiredArgs(d.constructor(d.node))}
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze([${d.audioParams(d.node).map(param => `'${d.name(param)}'`).join(', ')}]),
+    },
   });
 
   Object.defineProperties(${d.name(d.node)}.prototype, {
@@ -636,6 +1362,23 @@ ${(function() {
     ${d.methods(d.node, false).map(method => {
       return `${d.name(method)}: kEnumerableProperty,`;
     }).join('')}
+    ${d.bypassableNodes.includes(d.name(d.node)) ? `bypass: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'ConvolverNode' ? `bufferLength: kEnumerableProperty,
+    swapBuffer: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'WaveShaperNode' ? `curveLength: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'AudioBufferSourceNode' ? `resampleBuffer: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'AudioBufferSourceNode' ? `loopStartFrame: kEnumerableProperty,
+    loopEndFrame: kEnumerableProperty,
+    pause: kEnumerableProperty,
+    resume: kEnumerableProperty,
+    getPlaybackPosition: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'DelayNode' ? `clampFeedback: kEnumerableProperty,
+    maxDelayTime: kEnumerableProperty,
+    getAllocatedFrames: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'GainNode' ? `fade: kEnumerableProperty,` : ``}
+    ${d.name(d.node) === 'AnalyserNode' ? `windowFunction: kEnumerableProperty,
+    channelMode: kEnumerableProperty,
+    getFloatFrequencyDataForChannel: kEnumerableProperty,` : ``}
   });
   `;
 }())}
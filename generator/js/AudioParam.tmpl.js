@@ -3,10 +3,162 @@ const conversions = require("webidl-conversions");
 const { toSanitizedSequence } = require('./lib/cast.js');
 const { throwSanitizedError } = require('./lib/errors.js');
 
-const { kEnumerableProperty, kHiddenProperty } = require('./lib/utils.js');
+const { assertWithinRenderDuration, clampToCurrentTime, kEnumerableProperty, kHiddenProperty } = require('./lib/utils.js');
 const { kNapiObj } = require('./lib/symbols.js');
 
+// Arguments of the automation methods, in order, cf. exportTimeline and importTimeline
+const kAutomationMethods = {
+  setValueAtTime: ['value', 'startTime'],
+  linearRampToValueAtTime: ['value', 'endTime'],
+  exponentialRampToValueAtTime: ['value', 'endTime'],
+  setTargetAtTime: ['target', 'startTime', 'timeConstant'],
+  setValueCurveAtTime: ['values', 'startTime', 'duration'],
+  cancelAndHoldAtTime: ['cancelTime'],
+};
+
+// Description of a recorded automation event, cf. getScheduledEvents
+function toScheduledEvent(event) {
+  switch (event.type) {
+    case 'setValueAtTime':
+      return { type: 'setValue', time: event.startTime, value: event.value };
+    case 'linearRampToValueAtTime':
+      return { type: 'linearRamp', time: event.endTime, value: event.value };
+    case 'exponentialRampToValueAtTime':
+      return { type: 'expRamp', time: event.endTime, value: event.value };
+    case 'setTargetAtTime':
+      return {
+        type: 'setTarget',
+        time: event.startTime,
+        value: event.target,
+        timeConstant: event.timeConstant,
+      };
+    case 'setValueCurveAtTime':
+      return {
+        type: 'setValueCurve',
+        time: event.startTime,
+        // value reached at the end of the curve
+        value: event.values[event.values.length - 1],
+        values: event.values.slice(),
+        duration: event.duration,
+      };
+    case 'cancelAndHoldAtTime':
+      return { type: 'cancelAndHold', time: event.cancelTime };
+  }
+}
+
+// Value of the automation described by the sorted scheduled \`events\` at
+// \`time\`, cf. getValueAtTime. \`initialValue\` is the value of the param before
+// the first event. The end of a value curve truncated by a cancelAndHold is
+// given by its \`holdTime\`.
+function valueAtTime(events, time, initialValue) {
+  // value and time reached by the previous events, \`target\` is the ongoing
+  // setTarget automation if any
+  let endTime = 0;
+  let endValue = initialValue;
+  let target = null;
+
+  const currentValue = t => {
+    if (target === null) {
+      return endValue;
+    }
+
+    return target.value + (target.startValue - target.value) * Math.exp(-(t - target.time) / target.timeConstant);
+  };
+
+  for (const event of events) {
+    switch (event.type) {
+      case 'linearRamp':
+      case 'expRamp': {
+        if (time < event.time) {
+          const startValue = currentValue(endTime);
+          const ratio = (time - endTime) / (event.time - endTime);
+
+          if (event.type === 'linearRamp') {
+            return startValue + (event.value - startValue) * ratio;
+          }
+
+          // the ramp holds its start value until its end if values are
+          // null or of opposite signs
+          if (startValue * event.value <= 0) {
+            return startValue;
+          }
+
+          return startValue * Math.pow(event.value / startValue, ratio);
+        }
+
+        endValue = event.value;
+        break;
+      }
+      case 'setTarget': {
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        target = {
+          time: event.time,
+          startValue: currentValue(event.time),
+          value: event.value,
+          timeConstant: event.timeConstant,
+        };
+        endTime = event.time;
+        continue;
+      }
+      case 'setValueCurve': {
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        const curveEndTime = event.time + event.duration;
+        const holdTime = event.holdTime !== undefined ? event.holdTime : curveEndTime;
+        const values = event.values;
+        // position in the curve, the end of the curve is held
+        const position = Math.min(time, holdTime);
+
+        if (position < curveEndTime) {
+          const k = (values.length - 1) * (position - event.time) / event.duration;
+          const index = Math.floor(k);
+          const value = values[index] + (values[index + 1] - values[index]) * (k - index);
+
+          if (time < holdTime) {
+            return value;
+          }
+
+          endValue = value;
+        } else {
+          endValue = values[values.length - 1];
+        }
+
+        target = null;
+        endTime = holdTime;
+        continue;
+      }
+      default: {
+        // setValue and cancelAndHold
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        endValue = event.value;
+        break;
+      }
+    }
+
+    target = null;
+    endTime = event.time;
+  }
+
+  return currentValue(time);
+}
+
 class AudioParam {
+  #automationEvents = [];
+  // value of the param before the first automation event, cf. getValueAtTime
+  #initialValue = null;
+  // context of the param if known, cf. clampToCurrentTime
+  #context = null;
+  // non spec, the nominal range is bounded by the Nyquist frequency, cf. minValue
+  #nyquist = false;
+
   constructor(options) {
     // Make constructor "private"
     if (
@@ -21,6 +173,16 @@ class AudioParam {
       value: options[kNapiObj],
       ...kHiddenProperty,
     });
+
+    if (options.context !== undefined) {
+      this.#context = options.context;
+    }
+
+    if (options.nyquist === true) {
+      this.#nyquist = true;
+    }
+
+    this.#initialValue = this[kNapiObj].value;
   }
 
 ${d.attributes(d.node).map(attr => {
@@ -33,6 +195,13 @@ ${d.attributes(d.node).map(attr => {
       throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'AudioParam'");
     }
 
+    ${['minValue', 'maxValue'].includes(d.name(attr)) ? `
+    // non spec, the graph of an oversampled context is rendered at a higher
+    // rate, report the Nyquist frequency of the context, cf. renderOversample
+    if (this.#nyquist && this.#context !== null) {
+      return Math.sign(this[kNapiObj].${d.name(attr)}) * this.#context.sampleRate / 2;
+    }
+    ` : ``}
     return this[kNapiObj].${d.name(attr)};
   }
   `;
@@ -57,6 +226,13 @@ ${d.attributes(d.node).map(attr => {
     } catch (err) {
       throwSanitizedError(err);
     }
+${d.name(attr) === 'value' ? `
+    // the value setter acts as setValueAtTime at the current time, cf.
+    // getValueAtTime
+    this.#recordAutomationEvent('setValueAtTime', {
+      value,
+      startTime: this.#context !== null ? this.#context.currentTime : 0,
+    });` : ``}
   }
         `;
         break;
@@ -145,16 +321,243 @@ ${d.methods(d.node, false).map(method => {
       }
     }).join('')}
 
+    ${(function() {
+      // cancel times are kept as is, cancelling events in the past is meaningful
+      const timeArg = argumentNames.find(name => name === 'startTime' || name === 'endTime');
+
+      const endTime = d.name(method) === 'setValueCurveAtTime' ? `${timeArg} + duration` : timeArg;
+
+      return timeArg ? `
+    ${timeArg} = clampToCurrentTime(this.#context, ${timeArg});
+    assertWithinRenderDuration(this.#context, '${d.name(method)}', ${endTime});
+      ` : ``;
+    }())}
+
     try {
       this[kNapiObj].${d.name(method)}(${argumentNames.join(', ')});
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('${d.name(method)}', { ${argumentNames.join(', ')} });
+
     return this;
   }
     `
   }).join('')}
+
+  // non spec
+  exportTimeline() {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'AudioParam'");
+    }
+
+    return this.#automationEvents.map(event => {
+      const copy = { ...event };
+
+      if (Array.isArray(copy.values)) {
+        copy.values = copy.values.slice();
+      }
+
+      return copy;
+    });
+  }
+
+  // non spec, read-only list of the automation events of the timeline, sorted
+  // by time. Events scheduled after a \`cancelAndHoldAtTime\` are removed, as
+  // they are by the render thread.
+  getScheduledEvents() {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'AudioParam'");
+    }
+
+    let events = [];
+
+    this.#automationEvents.forEach(event => {
+      const scheduledEvent = toScheduledEvent(event);
+
+      if (scheduledEvent.type === 'cancelAndHold') {
+        events = events.filter(e => e.time < scheduledEvent.time);
+      }
+
+      events.push(scheduledEvent);
+    });
+
+    // stable sort, events at the same time keep their insertion order
+    return events.sort((a, b) => a.time - b.time);
+  }
+
+  // non spec, value of the param at the given time computed from the
+  // scheduled automation events, cf. getScheduledEvents. After a
+  // \`cancelAndHoldAtTime\`, the value at the cancel time is held as it is by
+  // the render thread. Setting the \`value\` attribute schedules a setValue
+  // event at the current time.
+  getValueAtTime(time) {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'AudioParam'");
+    }
+
+    if (arguments.length < 1) {
+      throw new TypeError(\`Failed to execute 'getValueAtTime' on 'AudioParam': 1 argument required, but only \${arguments.length} present\`);
+    }
+
+    time = conversions['double'](time, {
+      context: \`Failed to execute 'getValueAtTime' on 'AudioParam': Parameter 1\`,
+    });
+
+    return valueAtTime(this.#resolveAutomationEvents(), time, this.#initialValue);
+  }
+
+  // non spec
+  importTimeline(events) {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'AudioParam'");
+    }
+
+    if (arguments.length < 1) {
+      throw new TypeError(\`Failed to execute 'importTimeline' on 'AudioParam': 1 argument required, but only \${arguments.length} present\`);
+    }
+
+    if (events === null || typeof events !== 'object' || !(Symbol.iterator in events)) {
+      throw new TypeError(\`Failed to execute 'importTimeline' on 'AudioParam': Parameter 1 is not iterable\`);
+    }
+
+    events = Array.from(events);
+    // check the whole timeline before clearing the current one
+    events.forEach((event, index) => {
+      const argumentNames = event !== null && typeof event === 'object'
+        ? kAutomationMethods[event.type]
+        : undefined;
+
+      const isValid = argumentNames !== undefined && argumentNames.every(name => {
+        if (name === 'values') {
+          return event.values !== null
+            && typeof event.values === 'object'
+            && Symbol.iterator in event.values
+            && Array.from(event.values).every(value => typeof value === 'number');
+        } else {
+          return typeof event[name] === 'number';
+        }
+      });
+
+      if (!isValid) {
+        throw new TypeError(\`Failed to execute 'importTimeline' on 'AudioParam': Invalid automation event at index \${index}\`);
+      }
+    });
+
+    this.cancelScheduledValues(0);
+    this.#automationEvents = [];
+
+    events.forEach(event => {
+      const args = kAutomationMethods[event.type].map(name => event[name]);
+      this[event.type](...args);
+    });
+  }
+
+  #recordAutomationEvent(type, args) {
+    if (type === 'cancelScheduledValues') {
+      this.#automationEvents = this.#automationEvents.filter(event => {
+        const time = event.startTime !== undefined ? event.startTime
+          : event.endTime !== undefined ? event.endTime
+          : event.cancelTime;
+        return time < args.cancelTime;
+      });
+    } else {
+      const event = { type, ...args };
+
+      if (event.values !== undefined) {
+        event.values = Array.from(event.values);
+      }
+
+      this.#automationEvents.push(event);
+      this.#pruneAutomationEvents();
+    }
+  }
+
+  // drop the events that ended before the current time so that the timeline
+  // does not grow without bound, the value reached by the last of them is
+  // kept as a \`setValueAtTime\` event
+  #pruneAutomationEvents() {
+    const currentTime = this.#context !== null ? this.#context.currentTime : 0;
+    const events = this.#resolveAutomationEvents();
+    // index of the last ended event, a setTarget is ended only by the next one
+    let last = -1;
+    let holdTime = 0;
+
+    events.forEach((event, index) => {
+      const endTime = event.type === 'setValueCurve'
+        ? (event.holdTime !== undefined ? event.holdTime : event.time + event.duration)
+        : event.time;
+
+      if (event.type !== 'setTarget' && endTime <= currentTime) {
+        last = index;
+        holdTime = endTime;
+      }
+    });
+
+    if (last < 1) {
+      return;
+    }
+
+    const holdValue = valueAtTime(events, holdTime, this.#initialValue);
+
+    // recorded events still needed, including the ones cancelled by a
+    // pending \`cancelAndHoldAtTime\` which contribute to its held value
+    const kept = new Set();
+    const keep = event => {
+      kept.add(event.recordedEvent);
+      event.cancelledEvents.forEach(keep);
+    };
+
+    events.slice(last + 1).forEach(keep);
+
+    this.#initialValue = holdValue;
+    this.#automationEvents = [
+      { type: 'setValueAtTime', value: holdValue, startTime: holdTime },
+      ...this.#automationEvents.filter(event => kept.has(event)),
+    ];
+  }
+
+  // scheduled automation events sorted by time, with the value held by each
+  // \`cancelAndHold\` and the hold time of the value curves it truncates, cf.
+  // getValueAtTime
+  #resolveAutomationEvents() {
+    let events = [];
+
+    this.#automationEvents.forEach(event => {
+      const scheduledEvent = toScheduledEvent(event);
+      // recorded event and events it cancels, cf. #pruneAutomationEvents
+      scheduledEvent.recordedEvent = event;
+      scheduledEvent.cancelledEvents = [];
+
+      if (scheduledEvent.type === 'cancelAndHold') {
+        const cancelTime = scheduledEvent.time;
+        // value reached at the cancel time by the events cancelled
+        scheduledEvent.value = valueAtTime(events, cancelTime, this.#initialValue);
+        scheduledEvent.cancelledEvents = events.filter(e => e.time >= cancelTime);
+
+        events = events.filter(e => e.time < cancelTime);
+        // value curves are truncated at the cancel time
+        events.forEach(e => {
+          if (e.type === 'setValueCurve' && e.time + e.duration > cancelTime) {
+            e.holdTime = cancelTime;
+          }
+        });
+      }
+
+      // insert after the events at the same time to keep the list sorted
+      let index = events.length;
+
+      while (index > 0 && events[index - 1].time > scheduledEvent.time) {
+        index -= 1;
+      }
+
+      events.splice(index, 0, scheduledEvent);
+    });
+
+    return events;
+  }
 }
 
 Object.defineProperties(AudioParam, {
@@ -181,6 +584,10 @@ Object.defineProperties(AudioParam.prototype, {
   ${d.methods(d.node, false).map(method => {
     return `${d.name(method)}: kEnumerableProperty,`;
   }).join('')}
+  exportTimeline: kEnumerableProperty,
+  importTimeline: kEnumerableProperty,
+  getScheduledEvents: kEnumerableProperty,
+  getValueAtTime: kEnumerableProperty,
 });
 
 
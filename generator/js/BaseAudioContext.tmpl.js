@@ -1,20 +1,69 @@
+const conversions = require('webidl-conversions');
 const {
   isFunction,
+  isIterable,
   kEnumerableProperty,
   kHiddenProperty,
 } = require('./lib/utils.js');
 const {
   kNapiObj,
   kPrivateConstructor,
+  kScheduledSourceNodes,
+  kGraphNodes,
 } = require('./lib/symbols.js');
+const {
+  exportGraph,
+  importGraph,
+} = require('./lib/graph.js');
 
 const AudioWorklet = require('./AudioWorklet.js');
 
+// duration of the fade out applied by \`panic\`
+const kPanicDeclickDuration = 0.005;
+// values of the AudioContextState enum, cf. waitForState
+const kAudioContextStates = ['suspended', 'running', 'closed'];
+// ids of the decodings started with an \`AbortSignal\`
+let nextDecodingId = 0;
+
+// scale the buffer so that its peak reaches \`target\` dBFS, returns the applied
+// gain, cf. \`normalize\` option of \`decodeAudioData\`
+function normalizeAudioBuffer(audioBuffer, target) {
+  let peak = 0;
+
+  for (let channel = 0; channel < audioBuffer.numberOfChannels; channel++) {
+    for (const sample of audioBuffer.getChannelData(channel)) {
+      peak = Math.max(peak, Math.abs(sample));
+    }
+  }
+
+  if (peak === 0) {
+    return 1;
+  }
+
+  const gain = Math.pow(10, target / 20) / peak;
+
+  for (let channel = 0; channel < audioBuffer.numberOfChannels; channel++) {
+    // the channel data is a view on the buffer, scale it in place
+    const data = audioBuffer.getChannelData(channel);
+
+    for (let i = 0; i < data.length; i++) {
+      data[i] *= gain;
+    }
+  }
+
+  return gain;
+}
+
+// buffers given as \`into\` to \`decodeAudioData\` whose decoding is not settled
+const decodingTargets = new WeakSet();
+
 module.exports = (jsExport, _nativeBinding) => {
   class BaseAudioContext extends EventTarget {
     #audioWorklet = null;
     #destination = null;
     #listener = null;
+    // sources stopped by \`panic\` that have not ended yet
+    #panickedSources = new Set();
 
     constructor(options) {
       // Make constructor "private"
@@ -33,9 +82,23 @@ module.exports = (jsExport, _nativeBinding) => {
         ...kHiddenProperty,
       });
 
+      // started AudioScheduledSourceNode, removed when \`ended\` is dispatched
+      Object.defineProperty(this, kScheduledSourceNodes, {
+        value: new Set(),
+        ...kHiddenProperty,
+      });
+
+      // weak references to the nodes connected with \`connect\`, cf. exportGraph
+      Object.defineProperty(this, kGraphNodes, {
+        value: new Set(),
+        ...kHiddenProperty,
+      });
+
       this.#audioWorklet = new AudioWorklet({
         [kPrivateConstructor]: true,
         workletId: this[kNapiObj].workletId,
+        // the processors run at the rate of the graph, i.e. the global
+        // \`sampleRate\` is the oversampled rate, cf. renderOversample
         sampleRate: this[kNapiObj].sampleRate,
       });
 
@@ -68,6 +131,7 @@ module.exports = (jsExport, _nativeBinding) => {
       if (this.#listener === null) {
         this.#listener = new jsExport.AudioListener({
           [kNapiObj]: this[kNapiObj].listener,
+          context: this,
         });
       }
 
@@ -129,7 +193,27 @@ module.exports = (jsExport, _nativeBinding) => {
     // when decodeErrorCallback is present the program will crash in an
     // unexpected manner
     // cf. https://webaudio.github.io/web-audio-api/#dom-baseaudiocontext-decodeaudiodata
-    async decodeAudioData(arrayBuffer, decodeSuccessCallback = undefined, decodeErrorCallback = undefined) {
+    //
+    // Non-spec: \`options\` can be given as last argument, or in place of the
+    // callbacks, e.g. \`decodeAudioData(arrayBuffer, { trackIndex: 1 })\`
+    // - trackIndex: index of the track to decode in a multi-track container,
+    //   cf. \`probe\`, defaults to the default track of the container
+    // - signal: AbortSignal to cancel the decoding, which then rejects with an
+    //   AbortError and discards the partially decoded data
+    // - into: AudioBuffer in which the data is decoded, instead of a new
+    //   AudioBuffer, to reuse its memory when decoding many clips of the same
+    //   shape. Rejects with a NotSupportedError if the number of channels, the
+    //   length or the sample rate of the decoded data differ. The buffer cannot
+    //   be used until the decoding is settled.
+    // - metadata: if true, resolves with \`{ audioBuffer, metadata }\` where
+    //   \`metadata\` describes the decoded track as found in the file, i.e. its
+    //   \`index\`, \`codec\`, original \`sampleRate\`, \`numberOfChannels\`, \`bitDepth\`
+    //   and container \`duration\`, cf. \`probe\` (\`null\` if not known)
+    // - normalize: if true, the decoded buffer is scaled so that its peak
+    //   reaches \`normalizeTarget\` (in dBFS, defaults to -1), the applied gain
+    //   is given as \`normalizationGain\` in the metadata. Silent buffers are
+    //   left untouched.
+    async decodeAudioData(arrayBuffer, decodeSuccessCallback = undefined, decodeErrorCallback = undefined, options = {}) {
       if (!(this instanceof BaseAudioContext)) {
         throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
       }
@@ -142,23 +226,128 @@ module.exports = (jsExport, _nativeBinding) => {
         throw new TypeError('Failed to execute "decodeAudioData": parameter 1 is not of type "ArrayBuffer"');
       }
 
+      if (
+        decodeSuccessCallback !== null
+        && typeof decodeSuccessCallback === 'object'
+        && decodeErrorCallback === undefined
+      ) {
+        options = decodeSuccessCallback;
+        decodeSuccessCallback = undefined;
+      }
+
+      if (typeof options !== 'object' || options === null) {
+        throw new TypeError(\`Failed to execute 'decodeAudioData' on 'BaseAudioContext': Options must be an object\`);
+      }
+
+      let trackIndex;
+
+      if (options.trackIndex !== undefined) {
+        trackIndex = conversions['unsigned long'](options.trackIndex, {
+          enforceRange: true,
+          context: \`Failed to execute 'decodeAudioData' on 'BaseAudioContext': Failed to read the 'trackIndex' property from options: The provided value (\${options.trackIndex})\`,
+        });
+      }
+
+      let signal = null;
+      let decodingId;
+
+      if (options.signal !== undefined) {
+        if (!(options.signal instanceof AbortSignal)) {
+          throw new TypeError(\`Failed to execute 'decodeAudioData' on 'BaseAudioContext': Failed to read the 'signal' property from options: The provided value is not of type 'AbortSignal'\`);
+        }
+
+        signal = options.signal;
+        decodingId = nextDecodingId++;
+      }
+
+      let target = null;
+
+      if (options.into !== undefined) {
+        if (!(options.into instanceof jsExport.AudioBuffer)) {
+          throw new TypeError(\`Failed to execute 'decodeAudioData' on 'BaseAudioContext': Failed to read the 'into' property from options: The provided value is not of type 'AudioBuffer'\`);
+        }
+
+        target = options.into;
+      }
+
+      const withMetadata = conversions['boolean'](options.metadata);
+      const normalize = conversions['boolean'](options.normalize);
+      let normalizeTarget = -1;
+
+      if (options.normalizeTarget !== undefined) {
+        normalizeTarget = conversions['double'](options.normalizeTarget, {
+          context: \`Failed to execute 'decodeAudioData' on 'BaseAudioContext': Failed to read the 'normalizeTarget' property from options: The provided value (\${options.normalizeTarget})\`,
+        });
+      }
+      const abortDecoding = () => this[kNapiObj].abortDecoding(decodingId);
+
       try {
-        const nativeAudioBuffer = await this[kNapiObj].decodeAudioData(arrayBuffer);
-        const audioBuffer = new jsExport.AudioBuffer({ [kNapiObj]: nativeAudioBuffer });
+        if (signal !== null) {
+          if (signal.aborted) {
+            throw new Error('AbortError - the decoding was aborted');
+          }
+
+          signal.addEventListener('abort', abortDecoding, { once: true });
+        }
+
+        if (target !== null) {
+          if (decodingTargets.has(target)) {
+            throw new Error('InvalidStateError - the provided AudioBuffer is already being decoded into');
+          }
+
+          if (target.sampleRate !== this.sampleRate) {
+            throw new Error(\`NotSupportedError - the decoded data (at \${this.sampleRate}Hz) does not match the provided AudioBuffer (at \${target.sampleRate}Hz)\`);
+          }
+
+          decodingTargets.add(target);
+        }
+
+        let result;
+
+        try {
+          result = await this[kNapiObj].decodeAudioData(
+            arrayBuffer,
+            trackIndex,
+            decodingId,
+            withMetadata,
+            this.sampleRate,
+            target !== null ? target[kNapiObj] : null,
+          );
+        } finally {
+          if (target !== null) {
+            decodingTargets.delete(target);
+          }
+        }
+
+        const nativeAudioBuffer = withMetadata ? result.audioBuffer : result;
+        const audioBuffer = target !== null ? target : new jsExport.AudioBuffer({ [kNapiObj]: nativeAudioBuffer });
+        const normalizationGain = normalize ? normalizeAudioBuffer(audioBuffer, normalizeTarget) : 1;
+
+        const decoded = withMetadata
+          ? { audioBuffer, metadata: { ...result.metadata, normalizationGain } }
+          : audioBuffer;
 
         if (isFunction(decodeSuccessCallback)) {
-          decodeSuccessCallback(audioBuffer);
+          decodeSuccessCallback(decoded);
         } else {
-          return audioBuffer;
+          return decoded;
         }
       } catch (err) {
-        const error = new DOMException(\`Failed to execute 'decodeAudioData': \${err.message}\`, 'EncodingError');
+        // out of range \`trackIndex\`, aborted decoding or shape mismatch
+        const match = err.message.match(/^(IndexSizeError|AbortError|NotSupportedError|InvalidStateError) - /);
+        const name = match !== null ? match[1] : 'EncodingError';
+        const message = match !== null ? err.message.slice(match[0].length) : err.message;
+        const error = new DOMException(\`Failed to execute 'decodeAudioData': \${message}\`, name);
 
         if (isFunction(decodeErrorCallback)) {
           decodeErrorCallback(error);
         } else {
           throw error;
         }
+      } finally {
+        if (signal !== null) {
+          signal.removeEventListener('abort', abortDecoding);
+        }
       }
     }
 
@@ -210,6 +399,240 @@ module.exports = (jsExport, _nativeBinding) => {
       return new jsExport.PeriodicWave(this, options);
     }
 
+    // Non-spec: stop all started sources and fade out the output to avoid clicks
+    // The fade out gain is only inserted in front of the destination until all
+    // the stopped sources have ended, so that the context can be used again.
+    // The tail of the effects (e.g. a delay feedback loop) is thus heard again
+    // afterward. Does nothing if no source is playing, can be safely called
+    // several times.
+    panic() {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
+      }
+
+      const sources = this[kScheduledSourceNodes];
+
+      if (sources.size === 0) {
+        return;
+      }
+
+      const now = this.currentTime;
+      const stopTime = now + kPanicDeclickDuration;
+      const destination = this.#destination[kNapiObj];
+
+      destination.startPanic(now, stopTime);
+
+      for (let node of sources) {
+        try {
+          node.stop(stopTime);
+        } catch (err) {
+          // node may have been stopped in between, ignore
+        }
+
+        this.#panickedSources.add(node);
+        node.addEventListener('ended', () => {
+          this.#panickedSources.delete(node);
+
+          if (this.#panickedSources.size === 0) {
+            destination.endPanic();
+          }
+        }, { once: true });
+      }
+
+      sources.clear();
+    }
+
+    // Non-spec: musical scheduling helpers, \`beat\` is given relative to
+    // \`currentTime\`, e.g. \`timeAtBeat(1, 120)\` is \`currentTime + 0.5\`
+    timeAtBeat(beat, bpm) {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
+      }
+
+      if (arguments.length < 2) {
+        throw new TypeError(\`Failed to execute 'timeAtBeat' on 'BaseAudioContext': 2 arguments required, but only \${arguments.length} present\`);
+      }
+
+      beat = conversions['double'](beat, {
+        context: \`Failed to execute 'timeAtBeat' on 'BaseAudioContext': The provided beat value\`,
+      });
+
+      bpm = conversions['double'](bpm, {
+        context: \`Failed to execute 'timeAtBeat' on 'BaseAudioContext': The provided bpm value\`,
+      });
+
+      if (beat < 0) {
+        throw new RangeError(\`Failed to execute 'timeAtBeat' on 'BaseAudioContext': The provided beat value (\${beat}) is less than the minimum bound (0)\`);
+      }
+
+      if (bpm <= 0) {
+        throw new RangeError(\`Failed to execute 'timeAtBeat' on 'BaseAudioContext': The provided bpm value (\${bpm}) should be strictly positive\`);
+      }
+
+      return this.currentTime + beat * 60 / bpm;
+    }
+
+    // Non-spec: start the given source at \`timeAtBeat(beat, bpm)\`, returns
+    // the computed start time
+    scheduleAtBeat(node, beat, bpm) {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
+      }
+
+      if (arguments.length < 3) {
+        throw new TypeError(\`Failed to execute 'scheduleAtBeat' on 'BaseAudioContext': 3 arguments required, but only \${arguments.length} present\`);
+      }
+
+      if (!(node instanceof jsExport.AudioScheduledSourceNode)) {
+        throw new TypeError(\`Failed to execute 'scheduleAtBeat' on 'BaseAudioContext': parameter 1 is not of type 'AudioScheduledSourceNode'\`);
+      }
+
+      const when = this.timeAtBeat(beat, bpm);
+      node.start(when);
+
+      return when;
+    }
+
+    // Non-spec: combined response of a chain of filters, i.e. the product of
+    // the magnitude responses and the sum of the phase responses (wrapped to
+    // [-PI, PI]) of the given BiquadFilterNodes and IIRFilterNodes
+    getCombinedFrequencyResponse(filters, frequencyHz, magResponse, phaseResponse) {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BaseAudioContext\'');
+      }
+
+      if (arguments.length < 4) {
+        throw new TypeError(\`Failed to execute 'getCombinedFrequencyResponse' on 'BaseAudioContext': 4 arguments required, but only \${arguments.length} present\`);
+      }
+
+      if (!isIterable(filters)) {
+        throw new TypeError(\`Failed to execute 'getCombinedFrequencyResponse' on 'BaseAudioContext': Parameter 1 is not iterable\`);
+      }
+
+      filters = Array.from(filters);
+
+      filters.forEach((filter, index) => {
+        if (!(filter instanceof jsExport.BiquadFilterNode) && !(filter instanceof jsExport.IIRFilterNode)) {
+          throw new TypeError(\`Failed to execute 'getCombinedFrequencyResponse' on 'BaseAudioContext': Element at index \${index} is not of type 'BiquadFilterNode' or 'IIRFilterNode'\`);
+        }
+      });
+
+      [frequencyHz, magResponse, phaseResponse].forEach((array, index) => {
+        if (!(array instanceof Float32Array)) {
+          throw new TypeError(\`Failed to execute 'getCombinedFrequencyResponse' on 'BaseAudioContext': Parameter \${index + 2} is not of type 'Float32Array'\`);
+        }
+      });
+
+      if (magResponse.length !== frequencyHz.length || phaseResponse.length !== frequencyHz.length) {
+        throw new DOMException(\`Failed to execute 'getCombinedFrequencyResponse' on 'BaseAudioContext': The lengths of magResponse (\${magResponse.length}) and phaseResponse (\${phaseResponse.length}) should be equal to the length of frequencyHz (\${frequencyHz.length})\`, 'InvalidAccessError');
+      }
+
+      const mag = new Float32Array(frequencyHz.length);
+      const phase = new Float32Array(frequencyHz.length);
+
+      magResponse.fill(1);
+      phaseResponse.fill(0);
+
+      filters.forEach(filter => {
+        filter.getFrequencyResponse(frequencyHz, mag, phase);
+
+        for (let i = 0; i < frequencyHz.length; i++) {
+          magResponse[i] *= mag[i];
+          phaseResponse[i] = Math.atan2(Math.sin(phaseResponse[i] + phase[i]), Math.cos(phaseResponse[i] + phase[i]));
+        }
+      });
+    }
+
+    // Non-spec: JSON-serializable description of the graph, i.e. the nodes
+    // connected with \`connect\`, with their type, options and current param
+    // values, and their connections, cf. lib/graph.js
+    exportGraph() {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BaseAudioContext\'');
+      }
+
+      return exportGraph(this, jsExport);
+    }
+
+    // Non-spec: recreate in \`context\` the nodes and connections described by
+    // \`json\` (as returned by \`exportGraph\`, or its string representation).
+    // Returns the created nodes in the order of the description, \`null\` for
+    // the nodes flagged as not reconstructible.
+    static importGraph(context, json) {
+      if (arguments.length < 2) {
+        throw new TypeError(\`Failed to execute 'importGraph' on 'BaseAudioContext': 2 arguments required, but only \${arguments.length} present\`);
+      }
+
+      if (!(context instanceof BaseAudioContext)) {
+        throw new TypeError(\`Failed to execute 'importGraph' on 'BaseAudioContext': parameter 1 is not of type 'BaseAudioContext'\`);
+      }
+
+      if (typeof json === 'string') {
+        json = JSON.parse(json);
+      }
+
+      if (
+        json === null
+        || typeof json !== 'object'
+        || !Array.isArray(json.nodes)
+        || !Array.isArray(json.connections)
+      ) {
+        throw new TypeError(\`Failed to execute 'importGraph' on 'BaseAudioContext': parameter 2 is not a valid graph description\`);
+      }
+
+      return importGraph(context, json, jsExport);
+    }
+
+    // Non-spec: resolves once the context reaches \`state\`, immediately if it
+    // is already in this state. Rejects with an InvalidStateError if the
+    // context is closed before, unless \`state\` is 'closed'
+    waitForState(state) {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(\`Failed to execute 'waitForState' on 'BaseAudioContext': 1 argument required, but only \${arguments.length} present\`);
+      }
+
+      if (!kAudioContextStates.includes(state)) {
+        throw new TypeError(\`Failed to execute 'waitForState' on 'BaseAudioContext': The provided value '\${state}' is not a valid enum value of type AudioContextState\`);
+      }
+
+      const closedError = () => new DOMException(\`Failed to execute 'waitForState' on 'BaseAudioContext': Context has been closed before reaching state '\${state}'\`, 'InvalidStateError');
+
+      if (this.state === state) {
+        return Promise.resolve();
+      }
+
+      if (this.state === 'closed') {
+        return Promise.reject(closedError());
+      }
+
+      return new Promise((resolve, reject) => {
+        const onStateChange = () => {
+          if (this.state === state) {
+            this.removeEventListener('statechange', onStateChange);
+            resolve();
+          } else if (this.state === 'closed') {
+            this.removeEventListener('statechange', onStateChange);
+            reject(closedError());
+          }
+        };
+
+        this.addEventListener('statechange', onStateChange);
+      });
+    }
+
+    // Non-spec: factory of the NoiseSourceNode
+    createNoiseSource(options = {}) {
+      if (!(this instanceof BaseAudioContext)) {
+        throw new TypeError("Invalid Invocation: Value of 'this' must be of type 'BaseAudioContext'");
+      }
+
+      return new jsExport.NoiseSourceNode(this, options);
+    }
+
     // --------------------------------------------------------------------
     // Factory Methods (use the patched AudioNodes)
     // --------------------------------------------------------------------
@@ -272,6 +695,13 @@ module.exports = (jsExport, _nativeBinding) => {
     decodeAudioData: kEnumerableProperty,
     createBuffer: kEnumerableProperty,
     createPeriodicWave: kEnumerableProperty,
+    panic: kEnumerableProperty,
+    timeAtBeat: kEnumerableProperty,
+    scheduleAtBeat: kEnumerableProperty,
+    createNoiseSource: kEnumerableProperty,
+    getCombinedFrequencyResponse: kEnumerableProperty,
+    exportGraph: kEnumerableProperty,
+    waitForState: kEnumerableProperty,
   });
 
   return BaseAudioContext;
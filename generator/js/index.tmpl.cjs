@@ -31,6 +31,10 @@ jsExport.AudioRenderCapacity = require('./js/AudioRenderCapacity.js');
 jsExport.PeriodicWave = require('./js/PeriodicWave.js')(jsExport, nativeBinding);
 jsExport.AudioBuffer = require('./js/AudioBuffer.js')(jsExport, nativeBinding);
 
+// Non-spec nodes
+jsExport.ChannelMatrixNode = require('./js/ChannelMatrixNode.js')(jsExport, nativeBinding);
+jsExport.NoiseSourceNode = require('./js/NoiseSourceNode.js')(jsExport, nativeBinding);
+
 // --------------------------------------------------------------------------
 // Promisify MediaDevices API
 // --------------------------------------------------------------------------
@@ -52,5 +56,23 @@ jsExport.mediaDevices.getUserMedia = async function getUserMedia(options) {
   return Promise.resolve(stream);
 };
 
+// --------------------------------------------------------------------------
+// Non-spec decoding utils
+// --------------------------------------------------------------------------
+const probeNative = nativeBinding.probe;
+// List the tracks of a media container, the returned indices can be given
+// as \`trackIndex\` option to \`decodeAudioData\`
+jsExport.probe = async function probe(arrayBuffer) {
+  if (!(arrayBuffer instanceof ArrayBuffer)) {
+    throw new TypeError('Failed to execute "probe": parameter 1 is not of type "ArrayBuffer"');
+  }
+
+  try {
+    return await probeNative(arrayBuffer);
+  } catch (err) {
+    throw new DOMException(\`Failed to execute 'probe': \${err.message}\`, 'EncodingError');
+  }
+};
+
 module.exports = jsExport;
 
@@ -30,8 +30,13 @@ export const {
   // generated nodes
 ${d.nodes.map(n => `  ${d.name(n)},`).join('\n')}
 
+  // non-spec nodes
+  ChannelMatrixNode,
+  NoiseSourceNode,
+
   // helper methods
   mediaDevices,
+  probe,
 } = nativeModule;
 
 export default nativeModule;
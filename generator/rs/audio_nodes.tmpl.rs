@@ -3,7 +3,26 @@ use napi_derive::js_function;
 use web_audio_api::node::*;
 use crate::*;
 
-pub(crate) struct ${d.napiName(d.node)}(${d.name(d.node)});
+${(function() {
+    // non spec, state of the wrapper on top of the upstream node
+    let fields = [d.name(d.node)];
+
+    if (d.parent(d.node) === "AudioScheduledSourceNode") {
+        fields.push(`crate::utils::RenderStartTime`);
+    }
+
+    // backing store of the Uint8Array given to the last getByte* call
+    if (d.name(d.node) === "AnalyserNode") {
+        fields.push(`Option<(*mut u8, usize)>`);
+    }
+
+    // maxDelayTime the delay line has been allocated with
+    if (d.name(d.node) === "DelayNode") {
+        fields.push(`f64`);
+    }
+
+    return `pub(crate) struct ${d.napiName(d.node)}(${fields.join(', ')});`;
+}())}
 
 // for debug purpose
 // impl Drop for ${d.napiName(d.node)} {
@@ -31,6 +50,17 @@ impl ${d.napiName(d.node)} {
             if (d.parent(d.node) === "AudioScheduledSourceNode") {
                 methods.push(`Property::new("start")?.with_method(start)`);
                 methods.push(`Property::new("stop")?.with_method(stop)`);
+                methods.push(`Property::new("actualStartTime")?.with_method(actual_start_time)`);
+            }
+
+            // non spec, cf. AudioBufferSourceNode::getPlaybackPosition
+            if (d.name(d.node) === "AudioBufferSourceNode") {
+                methods.push(`Property::new("position")?.with_method(position)`);
+            }
+
+            // non spec, cf. DelayNode::getAllocatedFrames
+            if (d.name(d.node) === "DelayNode") {
+                methods.push(`Property::new("allocatedFrames")?.with_method(allocated_frames)`);
             }
 
             let interface = attributes.concat(methods);
@@ -312,8 +342,17 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
             .with_property_attributes(PropertyAttributes::Static),
     ])?;
 
+    ${d.parent(d.node) === "AudioScheduledSourceNode" ? `
+    // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+    let track_actual_start_time = js_options
+        .get::<&str, JsBoolean>("trackActualStartTime")?
+        .unwrap()
+        .get_value()?;
+    let start_time = crate::utils::RenderStartTime::new(track_actual_start_time);
+    ` : ``}
+
     // finalize instance creation
-    let napi_node = ${d.napiName(d.node)}(native_node);
+    let napi_node = ${d.napiName(d.node)}(native_node${d.parent(d.node) === "AudioScheduledSourceNode" ? `, start_time` : d.name(d.node) === "AnalyserNode" ? `, Default::default()` : d.name(d.node) === "DelayNode" ? `, max_delay_time` : ``});
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -372,6 +411,8 @@ fn start(ctx: CallContext) -> Result<JsUndefined> {
 
     let when = ctx.get::<JsNumber>(0)?.get_double()?;
     node.start_at(when);
+    // cf. AudioScheduledSourceNode::getActualStartTime
+    napi_node.1.record(napi_node.0.context(), when);
 
     ctx.env.get_undefined()
 }
@@ -403,6 +444,8 @@ fn start(ctx: CallContext) -> Result<JsUndefined> {
     };
 
     node.start_at_with_offset_and_duration(when, offset, duration);
+    // cf. AudioScheduledSourceNode::getActualStartTime
+    napi_node.1.record(napi_node.0.context(), when);
 
     ctx.env.get_undefined()
 }
@@ -421,14 +464,59 @@ fn stop(ctx: CallContext) -> Result<JsUndefined> {
 
     ctx.env.get_undefined()
 }
+
+// non spec, cf. AudioScheduledSourceNode::getActualStartTime
+#[js_function]
+fn actual_start_time(ctx: CallContext) -> Result<JsUnknown> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<${d.napiName(d.node)}>(&js_this)?;
+
+    match napi_node.1.get() {
+        Some(time) => ctx.env.create_double(time).map(|v| v.into_unknown()),
+        None => ctx.env.get_null().map(|v| v.into_unknown()),
+    }
+}
         `;
 
+        if (d.name(d.node) === "AudioBufferSourceNode") {
+            methods += `
+#[js_function(0)]
+fn position(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<${d.napiName(d.node)}>(&js_this)?;
+    let node = napi_node.unwrap();
+
+    let value = node.position();
+    ctx.env.create_double(value)
+}
+            `;
+        }
+
         return methods;
     } else {
         return ``;
     }
 }())}
 
+${d.name(d.node) === "DelayNode" ? `
+// non spec, number of frames (per channel) of the delay line, computed as
+// upstream does at the rate of the render graph, i.e. \`maxDelayTime\` rounded
+// up to a render quantum, plus the render quantum being written
+#[js_function(0)]
+fn allocated_frames(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<${d.napiName(d.node)}>(&js_this)?;
+
+    let render_quantum_size = 128.;
+    let sample_rate =
+        web_audio_api::context::BaseAudioContext::sample_rate(napi_node.0.context()) as f64;
+    let num_quanta = (napi_node.1 * sample_rate / render_quantum_size).ceil();
+    let frames = (num_quanta + 1.) * render_quantum_size;
+
+    ctx.env.create_uint32(frames as u32)
+}
+` : ``}
+
 ${d.attributes(d.node).length > 0 ? `
 // -------------------------------------------------
 // Getters / Setters
@@ -732,7 +820,11 @@ ${d.methods(d.node).map(method => {
 fn ${d.slug(method)}(ctx: CallContext) -> Result<JsUndefined> {
     let js_this = ctx.this_unchecked::<JsObject>();
     let napi_node = ctx.env.unwrap::<${d.napiName(d.node)}>(&js_this)?;
+    ${method.arguments.some(arg => d.memberType(arg) === "Uint8Array") ? `
+    let ${d.napiName(d.node)}(node, byte_array) = napi_node;
+    ` : `
     let node = napi_node.unwrap();
+    `}
 
     ${method.arguments.map((arg, index) => {
         const attrType = d.memberType(arg);
@@ -753,8 +845,27 @@ fn ${d.slug(method)}(ctx: CallContext) -> Result<JsUndefined> {
             }
             case "Uint8Array": {
                 return `
-    let mut ${d.slug(arg.name)}_js = ctx.get::<JsTypedArray>(${index})?.into_value()?;
-    let ${d.slug(arg.name)}: &mut [u8] = ${d.slug(arg.name)}_js.as_mut();
+    // non spec, the JS facade calls the method without argument if the array
+    // is the one given to the last call, cf. AnalyserNode::getByteFrequencyData
+    let mut ${d.slug(arg.name)}_js;
+    let ${d.slug(arg.name)}: &mut [u8] = if ctx.length == ${index} {
+        match *byte_array {
+            // SAFETY: the JS facade holds the array given to the last call, and
+            // checks that it is neither detached nor resized
+            Some((ptr, len)) => unsafe { std::slice::from_raw_parts_mut(ptr, len) },
+            None => &mut [],
+        }
+    } else {
+        ${d.slug(arg.name)}_js = ctx.get::<JsTypedArray>(${index})?.into_value()?;
+        // the data of a detached array is a null pointer
+        let ${d.slug(arg.name)}: &mut [u8] = if ${d.slug(arg.name)}_js.length == 0 {
+            &mut []
+        } else {
+            ${d.slug(arg.name)}_js.as_mut()
+        };
+        *byte_array = (!${d.slug(arg.name)}.is_empty()).then_some((${d.slug(arg.name)}.as_mut_ptr(), ${d.slug(arg.name)}.len()));
+        ${d.slug(arg.name)}
+    };
                 `;
                 break;
             }
@@ -34,6 +34,12 @@ ${d.nodes.map(n => { return `
 mod ${d.slug(n)};
 use crate::${d.slug(n)}::${d.napiName(n)};`}).join('')}
 
+// Non-spec audio nodes
+mod channel_matrix_node;
+use crate::channel_matrix_node::NapiChannelMatrixNode;
+mod noise_source_node;
+use crate::noise_source_node::NapiNoiseSourceNode;
+
 // AudioWorklet internals
 use crate::audio_worklet_node::{
     exit_audio_worklet_global_scope,
@@ -47,6 +53,10 @@ mod media_devices;
 use crate::media_devices::napi_enumerate_devices;
 use crate::media_devices::napi_get_user_media;
 
+// Non-spec decoding utils
+mod decoding;
+use crate::decoding::napi_probe;
+
 #[cfg(all(
     any(windows, unix),
     target_arch = "x86_64",
@@ -84,6 +94,15 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
     exports.set_named_property("${d.name(n)}", napi_class)?;
     `}).join('')}
 
+    // ----------------------------------------------------------------
+    // Non-spec audio nodes
+    // ----------------------------------------------------------------
+    let napi_class = NapiChannelMatrixNode::create_js_class(&env)?;
+    exports.set_named_property("ChannelMatrixNode", napi_class)?;
+
+    let napi_class = NapiNoiseSourceNode::create_js_class(&env)?;
+    exports.set_named_property("NoiseSourceNode", napi_class)?;
+
     // ----------------------------------------------------------------
     // AudioWorklet utils (internal)
     // ----------------------------------------------------------------
@@ -109,6 +128,21 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
     // expose media devices
     exports.set_named_property("mediaDevices", media_devices)?;
 
+    // ----------------------------------------------------------------
+    // Non-spec decoding utils
+    // ----------------------------------------------------------------
+    exports.create_named_method("probe", napi_probe)?;
+
+    // ----------------------------------------------------------------
+    // Non-spec rendering limits
+    // ----------------------------------------------------------------
+    // maximum number of channels supported by the renderer, e.g. for an
+    // AudioBuffer or a destination
+    exports.set_named_property(
+        "maxChannelCount",
+        env.create_uint32(web_audio_api::MAX_CHANNELS as u32)?,
+    )?;
+
     // ----------------------------------------------------------------
     // Store constructors for classes that need to be created from within Rust code
     // ----------------------------------------------------------------
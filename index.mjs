@@ -68,8 +68,16 @@ export const {
   StereoPannerNode,
   WaveShaperNode,
 
+  // non-spec nodes
+  ChannelMatrixNode,
+  NoiseSourceNode,
+  Sampler,
+
   // helper methods
   mediaDevices,
+  probe,
+  setOfflineRenderConcurrency,
+  closeAllContexts,
 } = nativeModule;
 
 export default nativeModule;
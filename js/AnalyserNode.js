@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,13 +34,32 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  kChannelAnalysers,
+} = require('./lib/symbols.js');
+const {
+  kWindowFunctions,
+  WindowedSpectrum,
+  createChannelAnalysers,
+  getChannelAnalyser,
+} = require('./lib/analyser.js');
 
 const AudioNode = require('./AudioNode.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class AnalyserNode extends AudioNode {
+    // non spec, cf. windowFunction
+    #windowedSpectrum = new WindowedSpectrum();
+    // Uint8Array given to the last native getByte* call, repeated calls with
+    // the same array skip its validation and the native side reuses its
+    // backing store. A detached or resized array has a different length,
+    // which invalidates the cache.
+    #byteArray = null;
+    #byteArrayLength = 0;
 
     constructor(context, options) {
 
@@ -90,6 +111,16 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.smoothingTimeConstant = 0.8;
       }
 
+      if (options && options.channelMode !== undefined) {
+        if (!['downmix', 'perChannel'].includes(options.channelMode)) {
+          throw new TypeError(`Failed to construct 'AnalyserNode': Failed to read the 'channelMode' property from AnalyserOptions: The provided value '${options.channelMode}' is not a valid channel mode, should be one of 'downmix', 'perChannel'`);
+        }
+
+        parsedOptions.channelMode = options.channelMode;
+      } else {
+        parsedOptions.channelMode = 'downmix';
+      }
+
       if (options && options.channelCount !== undefined) {
         parsedOptions.channelCount = conversions['unsigned long'](options.channelCount, {
           enforceRange: true,
@@ -121,6 +152,78 @@ module.exports = (jsExport, nativeBinding) => {
         [kNapiObj]: napiObj,
       });
 
+      // non spec, split the input into one analyser per channel, cf. channelMode
+      Object.defineProperty(this, kChannelAnalysers, {
+        __proto__: null,
+        enumerable: false,
+        value: parsedOptions.channelMode === 'perChannel'
+          ? createChannelAnalysers(this, this.channelCount, jsExport)
+          : null,
+      });
+    }
+
+    // non spec
+    get windowFunction() {
+      if (!(this instanceof AnalyserNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AnalyserNode\'');
+      }
+
+      return this.#windowedSpectrum.windowFunction;
+    }
+
+    // non spec
+    set windowFunction(value) {
+      if (!(this instanceof AnalyserNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AnalyserNode\'');
+      }
+
+      if (!kWindowFunctions.includes(value)) {
+        throw new TypeError(`Failed to set the 'windowFunction' property on 'AnalyserNode': Value '${value}' is not a valid window function, should be one of ${kWindowFunctions.map(name => `'${name}'`).join(', ')}`);
+      }
+
+      this.#windowedSpectrum.windowFunction = value;
+    }
+
+    // non spec, 'perChannel' if each of the first `channelCount` input
+    // channels (as set at construction) can be analysed separately
+    get channelMode() {
+      if (!(this instanceof AnalyserNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AnalyserNode\'');
+      }
+
+      return this[kChannelAnalysers] !== null ? 'perChannel' : 'downmix';
+    }
+
+    // non spec, same as getFloatFrequencyData for a single input channel
+    getFloatFrequencyDataForChannel(channelNumber, array) {
+      if (!(this instanceof AnalyserNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AnalyserNode\'');
+      }
+
+      if (arguments.length < 2) {
+        throw new TypeError(`Failed to execute 'getFloatFrequencyDataForChannel' on 'AnalyserNode': 2 arguments required, but only ${arguments.length} present`);
+      }
+
+      channelNumber = conversions['unsigned long'](channelNumber, {
+        enforceRange: true,
+        context: `Failed to execute 'getFloatFrequencyDataForChannel' on 'AnalyserNode': Parameter 1`,
+      });
+
+      if (!(array instanceof Float32Array)) {
+        throw new TypeError(`Failed to execute 'getFloatFrequencyDataForChannel' on 'AnalyserNode': Parameter 2 is not of type 'Float32Array'`);
+      }
+
+      const routing = this[kChannelAnalysers];
+
+      if (routing === null) {
+        throw new DOMException(`Failed to execute 'getFloatFrequencyDataForChannel' on 'AnalyserNode': The node must be created with 'channelMode' set to 'perChannel'`, 'InvalidStateError');
+      }
+
+      if (channelNumber >= routing.analysers.length) {
+        throw new DOMException(`Failed to execute 'getFloatFrequencyDataForChannel' on 'AnalyserNode': The channel number provided (${channelNumber}) is outside the range [0, ${routing.analysers.length - 1}]`, 'IndexSizeError');
+      }
+
+      getChannelAnalyser(routing, this, channelNumber).getFloatFrequencyData(array);
     }
 
     get fftSize() {
@@ -247,6 +350,12 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError(`Failed to execute 'getFloatFrequencyData' on 'AnalyserNode': Parameter 1 is not of type 'Float32Array'`);
       }
 
+      // non spec, the default window is computed natively
+      if (this.#windowedSpectrum.windowFunction !== 'blackman') {
+        this.#windowedSpectrum.getFloatFrequencyData(this[kNapiObj], array);
+        return;
+      }
+
       try {
         return this[kNapiObj].getFloatFrequencyData(array);
       } catch (err) {
@@ -263,12 +372,27 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError(`Failed to execute 'getByteFrequencyData' on 'AnalyserNode': 1 argument required, but only ${arguments.length} present`);
       }
 
-      if (!(array instanceof Uint8Array)) {
+      const cached = array === this.#byteArray && array.length === this.#byteArrayLength;
+
+      if (!cached && !(array instanceof Uint8Array)) {
         throw new TypeError(`Failed to execute 'getByteFrequencyData' on 'AnalyserNode': Parameter 1 is not of type 'Uint8Array'`);
       }
 
+      // non spec, the default window is computed natively
+      if (this.#windowedSpectrum.windowFunction !== 'blackman') {
+        this.#windowedSpectrum.getByteFrequencyData(this[kNapiObj], array);
+        return;
+      }
+
       try {
-        return this[kNapiObj].getByteFrequencyData(array);
+        if (cached) {
+          // the native side writes into the array it has resolved last
+          this[kNapiObj].getByteFrequencyData();
+        } else {
+          this[kNapiObj].getByteFrequencyData(array);
+          this.#byteArray = array;
+          this.#byteArrayLength = array.length;
+        }
       } catch (err) {
         throwSanitizedError(err);
       }
@@ -303,12 +427,21 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError(`Failed to execute 'getByteTimeDomainData' on 'AnalyserNode': 1 argument required, but only ${arguments.length} present`);
       }
 
-      if (!(array instanceof Uint8Array)) {
+      const cached = array === this.#byteArray && array.length === this.#byteArrayLength;
+
+      if (!cached && !(array instanceof Uint8Array)) {
         throw new TypeError(`Failed to execute 'getByteTimeDomainData' on 'AnalyserNode': Parameter 1 is not of type 'Uint8Array'`);
       }
 
       try {
-        return this[kNapiObj].getByteTimeDomainData(array);
+        if (cached) {
+          // the native side writes into the array it has resolved last
+          this[kNapiObj].getByteTimeDomainData();
+        } else {
+          this[kNapiObj].getByteTimeDomainData(array);
+          this.#byteArray = array;
+          this.#byteArrayLength = array.length;
+        }
       } catch (err) {
         throwSanitizedError(err);
       }
@@ -324,6 +457,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze([]),
+    },
   });
 
   Object.defineProperties(AnalyserNode.prototype, {
@@ -344,6 +485,9 @@ module.exports = (jsExport, nativeBinding) => {
     getByteFrequencyData: kEnumerableProperty,
     getFloatTimeDomainData: kEnumerableProperty,
     getByteTimeDomainData: kEnumerableProperty,
+    windowFunction: kEnumerableProperty,
+    channelMode: kEnumerableProperty,
+    getFloatFrequencyDataForChannel: kEnumerableProperty,
   });
 
   return AnalyserNode;
@@ -4,6 +4,7 @@ const {
   throwSanitizedError,
 } = require('./lib/errors.js');
 const {
+  isIterable,
   kEnumerableProperty,
   kHiddenProperty,
 } = require('./lib/utils.js');
@@ -79,6 +80,53 @@ module.exports = (_jsExport, nativeBinding) => {
       }
     }
 
+    // Non-spec: return a new AudioBuffer which is the sequential concatenation
+    // of the given buffers. All buffers must share the same sample rate and
+    // number of channels, a NotSupportedError is thrown otherwise.
+    static concat(buffers) {
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'concat' on 'AudioBuffer': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (!isIterable(buffers)) {
+        throw new TypeError(`Failed to execute 'concat' on 'AudioBuffer': parameter 1 is not iterable`);
+      }
+
+      buffers = Array.from(buffers);
+
+      if (buffers.length === 0) {
+        throw new TypeError(`Failed to execute 'concat' on 'AudioBuffer': parameter 1 must contain at least one AudioBuffer`);
+      }
+
+      buffers.forEach((buffer, index) => {
+        if (!(buffer instanceof AudioBuffer)) {
+          throw new TypeError(`Failed to execute 'concat' on 'AudioBuffer': Element at index ${index} is not of type 'AudioBuffer'`);
+        }
+      });
+
+      const { sampleRate, numberOfChannels } = buffers[0];
+
+      buffers.forEach((buffer, index) => {
+        if (buffer.sampleRate !== sampleRate) {
+          throw new DOMException(`Failed to execute 'concat' on 'AudioBuffer': Element at index ${index} has a different sample rate (${buffer.sampleRate}) than the first buffer (${sampleRate})`, 'NotSupportedError');
+        }
+
+        if (buffer.numberOfChannels !== numberOfChannels) {
+          throw new DOMException(`Failed to execute 'concat' on 'AudioBuffer': Element at index ${index} has a different number of channels (${buffer.numberOfChannels}) than the first buffer (${numberOfChannels})`, 'NotSupportedError');
+        }
+      });
+
+      let napiObj;
+
+      try {
+        napiObj = nativeBinding.AudioBuffer.concat(buffers.map(buffer => buffer[kNapiObj]));
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      return new AudioBuffer({ [kNapiObj]: napiObj });
+    }
+
     get sampleRate() {
       if (!(this instanceof AudioBuffer)) {
         throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'AudioBuffer'`);
@@ -181,6 +229,52 @@ module.exports = (_jsExport, nativeBinding) => {
       }
     }
 
+    // Non-spec: return a new AudioBuffer containing the frames in the
+    // [startFrame, endFrame[ range, with the same sample rate.
+    // As for `TypedArray.prototype.slice`, negative indices are counted from
+    // the end of the buffer and out of range indices are clamped. Throws an
+    // IndexSizeError if the resulting range is empty.
+    slice(startFrame = 0, endFrame = undefined) {
+      if (!(this instanceof AudioBuffer)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'AudioBuffer'`);
+      }
+
+      const length = this.length;
+
+      startFrame = conversions['long'](startFrame, {
+        context: `Failed to execute 'slice' on 'AudioBuffer': startFrame`,
+      });
+
+      if (endFrame === undefined) {
+        endFrame = length;
+      } else {
+        endFrame = conversions['long'](endFrame, {
+          context: `Failed to execute 'slice' on 'AudioBuffer': endFrame`,
+        });
+      }
+
+      const clamp = index => index < 0
+        ? Math.max(0, length + index)
+        : Math.min(index, length);
+
+      const start = clamp(startFrame);
+      const end = clamp(endFrame);
+
+      if (end <= start) {
+        throw new DOMException(`Failed to execute 'slice' on 'AudioBuffer': The resulting frame range [${start}, ${end}[ is empty`, 'IndexSizeError');
+      }
+
+      let napiObj;
+
+      try {
+        napiObj = this[kNapiObj].slice(start, end);
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      return new AudioBuffer({ [kNapiObj]: napiObj });
+    }
+
     getChannelData(channel) {
       if (!(this instanceof AudioBuffer)) {
         throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'AudioBuffer'`);
@@ -217,6 +311,7 @@ module.exports = (_jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    concat: kEnumerableProperty,
   });
 
   Object.defineProperties(AudioBuffer.prototype, {
@@ -235,6 +330,7 @@ module.exports = (_jsExport, nativeBinding) => {
     copyFromChannel: kEnumerableProperty,
     copyToChannel: kEnumerableProperty,
     getChannelData: kEnumerableProperty,
+    slice: kEnumerableProperty,
   });
 
   return AudioBuffer;
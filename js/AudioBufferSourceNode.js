@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,13 +34,36 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
 
+const {
+  kInsertOutputGate,
+  kStartTime,
+} = require('./lib/symbols.js');
+const {
+  kPauseFadeQuanta,
+  holdAutomation,
+  scheduleAutomationEvents,
+} = require('./lib/pause.js');
+// non spec, cf. pause
+const kRenderQuantumSize = 128;
+
 const AudioScheduledSourceNode = require('./AudioScheduledSourceNode.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class AudioBufferSourceNode extends AudioScheduledSourceNode {
+    // value of the loop attribute, cf. #syncLoop
+    #loop = false;
+    // non spec, cf. resampleBuffer
+    #resampleBuffer = false;
+    // non spec, cf. pause
+    #gate = null;
+    #paused = null;
+    // non spec, cf. getPlaybackPosition
+    #ended = false;
 
     #playbackRate = null;
     #detune = null;
@@ -115,6 +140,31 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.playbackRate = 1;
       }
 
+      if (options && options.resampleBuffer !== undefined) {
+        parsedOptions.resampleBuffer = conversions['boolean'](options.resampleBuffer);
+      } else {
+        parsedOptions.resampleBuffer = false;
+      }
+
+      if (
+        parsedOptions.resampleBuffer
+        && parsedOptions.buffer !== null
+        && parsedOptions.buffer.sampleRate !== context[kNapiObj].sampleRate
+      ) {
+        parsedOptions.buffer = parsedOptions.buffer.resample(context[kNapiObj].sampleRate);
+      }
+
+      // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+      if (options && options.trackActualStartTime !== undefined) {
+        parsedOptions.trackActualStartTime = conversions['boolean'](options.trackActualStartTime);
+      } else {
+        parsedOptions.trackActualStartTime = false;
+      }
+
+      // cf. #syncLoop
+      const loop = parsedOptions.loop;
+      parsedOptions.loop = loop && parsedOptions.buffer !== null && options.buffer.length > 0;
+
       let napiObj;
 
       try {
@@ -141,10 +191,15 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#playbackRate = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].playbackRate,
+        context,
       });
       this.#detune = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].detune,
+        context,
       });
+
+      this.#loop = loop;
+      this.#resampleBuffer = parsedOptions.resampleBuffer;
     }
 
     get playbackRate() {
@@ -163,6 +218,175 @@ module.exports = (jsExport, nativeBinding) => {
       return this.#detune;
     }
 
+    // non spec, loop boundaries in sample frames of the buffer, i.e. converted
+    // from and to `loopStart` and `loopEnd` using the sample rate of the
+    // buffer (or of the context if no buffer is set)
+    get loopStartFrame() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      return Math.round(this.loopStart * this.#loopSampleRate());
+    }
+
+    // non spec
+    set loopStartFrame(value) {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      value = conversions['unsigned long'](value, {
+        enforceRange: true,
+        context: `Failed to set the 'loopStartFrame' property on 'AudioBufferSourceNode': Value`,
+      });
+
+      this.loopStart = value / this.#loopSampleRate();
+    }
+
+    // non spec
+    get loopEndFrame() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      return Math.round(this.loopEnd * this.#loopSampleRate());
+    }
+
+    // non spec
+    set loopEndFrame(value) {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      value = conversions['unsigned long'](value, {
+        enforceRange: true,
+        context: `Failed to set the 'loopEndFrame' property on 'AudioBufferSourceNode': Value`,
+      });
+
+      this.loopEnd = value / this.#loopSampleRate();
+    }
+
+    // non spec, if true the buffer is resampled to the rate of the context
+    // (i.e. the oversampled rate, cf. OfflineAudioContext renderOversample)
+    // once when it is assigned, instead of being interpolated while rendering.
+    // The `buffer` attribute still returns the assigned buffer.
+    get resampleBuffer() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      return this.#resampleBuffer;
+    }
+
+    #loopSampleRate() {
+      return this[kAudioBuffer] !== null ? this[kAudioBuffer].sampleRate : this.context.sampleRate;
+    }
+
+    // Looping over an empty buffer crashes the renderer while the source should
+    // just render silence and end, hence looping is only enabled on the native
+    // node once a buffer with some frames is set
+    #syncLoop() {
+      const buffer = this[kAudioBuffer];
+
+      try {
+        this[kNapiObj].loop = this.#loop && buffer !== null && buffer.length > 0;
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+    }
+
+    // non spec, pauses the playback at `currentTime`: the output is faded out
+    // over a few milliseconds, then the playhead is held and the output is
+    // silenced until `resume` is called, which fades the output in and continues
+    // the playback from the same position. The `ended` event is not dispatched
+    // on pause, nor is the time spent paused counted in the `duration` given to
+    // `start`.
+    //
+    // The playhead is held by setting the playbackRate to 0 at the end of the
+    // fade out. The automation events of the playbackRate scheduled after that
+    // time are saved and scheduled again on resume, delayed by the time spent
+    // paused. The value of a k-rate param is computed before the events
+    // scheduled at the start of the render quantum are applied, hence pause and
+    // resume are effective one render quantum after `currentTime`.
+    pause() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      if (this.#paused !== null) {
+        return;
+      }
+
+      // the gate is only inserted once needed, cf. AudioNode::connect
+      if (this.#gate === null) {
+        this.#gate = new jsExport.GainNode(this.context);
+        this[kInsertOutputGate](this.#gate);
+      }
+
+      const quantum = this.#renderQuantumDuration();
+      const now = this.context.currentTime;
+      // the playhead is held once the output is faded out
+      const holdTime = now + kPauseFadeQuanta * quantum;
+      const events = holdAutomation(this.#playbackRate, now, holdTime, 0);
+
+      this.#fadeGate(0, now + quantum);
+      this.#paused = { holdTime, events };
+    }
+
+    // non spec, cf. pause
+    resume() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      if (this.#paused === null) {
+        return;
+      }
+
+      const now = this.context.currentTime;
+      const { holdTime, events } = this.#paused;
+      // if resumed during the fade out, the playhead is not held
+      const resumeTime = Math.max(now, holdTime);
+
+      // the events scheduled while paused are replaced by the saved ones
+      holdAutomation(this.#playbackRate, now, resumeTime, 0);
+      scheduleAutomationEvents(this.#playbackRate, events, resumeTime - holdTime);
+      this.#fadeGate(1, now + this.#renderQuantumDuration());
+      this.#paused = null;
+    }
+
+    // ramp the gain of the gate from its current value at `time`
+    #fadeGate(value, time) {
+      const gain = this.#gate.gain;
+      const duration = kPauseFadeQuanta * this.#renderQuantumDuration();
+
+      holdAutomation(gain, this.context.currentTime, time, gain.getValueAtTime(time));
+      gain.linearRampToValueAtTime(value, time + duration);
+    }
+
+    // the rate of the napi context differs from `sampleRate` if the graph is
+    // oversampled, cf. OfflineAudioContext renderOversample
+    #renderQuantumDuration() {
+      return kRenderQuantumSize / this.context[kNapiObj].sampleRate;
+    }
+
+    // non spec, current position of the playhead in the buffer in seconds,
+    // i.e. the offset given to `start` plus the time played scaled by the
+    // playbackRate, wrapped into the loop if any. Returns -1 before the start
+    // time is reached and once the source has ended. The position is updated at
+    // the end of each render quantum.
+    getPlaybackPosition() {
+      if (!(this instanceof AudioBufferSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
+      }
+
+      if (this.getActualStartTime() === null || this.#ended) {
+        return -1;
+      }
+
+      return this[kNapiObj].position();
+    }
+
     get buffer() {
       if (!(this instanceof AudioBufferSourceNode)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
@@ -183,13 +407,21 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError('Failed to set the \'buffer\' property on \'AudioBufferSourceNode\': Failed to convert value to \'AudioBuffer\'');
       }
 
+      let napiBuffer = value[kNapiObj];
+      const renderSampleRate = this.context[kNapiObj].sampleRate;
+
+      if (this.#resampleBuffer && napiBuffer.sampleRate !== renderSampleRate) {
+        napiBuffer = napiBuffer.resample(renderSampleRate);
+      }
+
       try {
-        this[kNapiObj].buffer = value[kNapiObj];
+        this[kNapiObj].buffer = napiBuffer;
       } catch (err) {
         throwSanitizedError(err);
       }
 
       this[kAudioBuffer] = value;
+      this.#syncLoop();
     }
 
     get loop() {
@@ -197,7 +429,7 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioBufferSourceNode\'');
       }
 
-      return this[kNapiObj].loop;
+      return this.#loop;
     }
 
     set loop(value) {
@@ -209,11 +441,8 @@ module.exports = (jsExport, nativeBinding) => {
         context: `Failed to set the 'loop' property on 'AudioBufferSourceNode': Value`,
       });
 
-      try {
-        this[kNapiObj].loop = value;
-      } catch (err) {
-        throwSanitizedError(err);
-      }
+      this.#loop = value;
+      this.#syncLoop();
     }
 
     get loopStart() {
@@ -287,11 +516,20 @@ module.exports = (jsExport, nativeBinding) => {
         });
       }
 
+      when = clampToCurrentTime(this.context, when);
+
       try {
-        return this[kNapiObj].start(when, offset, duration);
+        this[kNapiObj].start(when, offset, duration);
       } catch (err) {
         throwSanitizedError(err);
       }
+
+      // keep track of the started sources, cf. BaseAudioContext::panic
+      this.context[kScheduledSourceNodes].add(this);
+      // cf. AudioScheduledSourceNode::getActualStartTime
+      this[kStartTime] = when;
+
+      this.addEventListener('ended', () => this.#ended = true, { once: true });
     }
 
   }
@@ -304,6 +542,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['playbackRate', 'detune']),
+    },
   });
 
   Object.defineProperties(AudioBufferSourceNode.prototype, {
@@ -321,6 +567,12 @@ module.exports = (jsExport, nativeBinding) => {
     loopStart: kEnumerableProperty,
     loopEnd: kEnumerableProperty,
     start: kEnumerableProperty,
+    resampleBuffer: kEnumerableProperty,
+    loopStartFrame: kEnumerableProperty,
+    loopEndFrame: kEnumerableProperty,
+    pause: kEnumerableProperty,
+    resume: kEnumerableProperty,
+    getPlaybackPosition: kEnumerableProperty,
   });
 
   return AudioBufferSourceNode;
@@ -11,20 +11,49 @@ const {
   kNapiObj,
   kOnStateChange,
   kOnSinkChange,
+  kOnRenderQuantum,
   kWorkletRelease,
+  kWorkletSetProcessBudget,
+  kGetUnderrunCount,
+  kMuteGain,
+  kTeardown,
+  kScheduledSourceNodes,
+  kStopTime,
 } = require('./lib/symbols.js');
+const {
+  registerContext,
+} = require('./lib/contexts.js');
 const {
   propagateEvent,
 } = require('./lib/events.js');
 
 let contextId = 0;
 
+// duration of the ramp applied when toggling `muted`, to avoid clicks
+const kMuteDeclickDuration = 0.01;
+
+function nextPowerOfTwo(value) {
+  return Math.pow(2, Math.ceil(Math.log2(Math.max(1, value))));
+}
+
 module.exports = function(jsExport, nativeBinding) {
 
   class AudioContext extends jsExport.BaseAudioContext {
     #sinkId = '';
+    // 'none' if rendering to the null sink, given either as an AudioSinkOptions
+    // or as the 'none' string, 'device' otherwise, cf. getSinkType
+    #sinkType = 'device';
+    #callbackBufferSize = null;
+    #latencyCategory = null;
     #renderCapacity = null;
     #onsinkchange = null;
+    #onrenderquantum = null;
+    // last average load reported by renderCapacity, cf. onrenderquantum
+    #renderLoad = null;
+    #muted = false;
+    // releases the references held by the process, cf. closeAllContexts
+    #releaseProcess = null;
+    #teardown = null;
 
     constructor(options = {}) {
       if (typeof options !== 'object') {
@@ -45,6 +74,28 @@ module.exports = function(jsExport, nativeBinding) {
         targetOptions.latencyHint = 'interactive';
       }
 
+      // Non-spec: how far ahead of the output the render thread computes the
+      // audio, in seconds. The render thread processes the whole system-level
+      // audio callback at once, so the look-ahead is forwarded to the backend
+      // as a numeric latency hint, which excludes `latencyHint`, cf.
+      // getSchedulingLookAhead
+      if (options.schedulingLookAhead !== undefined) {
+        // the requested latency category would be silently replaced
+        if (options.latencyHint !== undefined) {
+          throw new TypeError(`Failed to construct 'AudioContext': The 'schedulingLookAhead' and 'latencyHint' properties of AudioContextOptions are mutually exclusive`);
+        }
+
+        const lookAhead = conversions['double'](options.schedulingLookAhead, {
+          context: `Failed to construct 'AudioContext': Failed to read the 'schedulingLookAhead' property from AudioContextOptions: The provided value (${options.schedulingLookAhead})`,
+        });
+
+        if (lookAhead <= 0) {
+          throw new RangeError(`Failed to construct 'AudioContext': Failed to read the 'schedulingLookAhead' property from AudioContextOptions: The provided value (${lookAhead}) should be strictly positive`);
+        }
+
+        targetOptions.latencyHint = lookAhead;
+      }
+
       if (options.sampleRate !== undefined) {
         targetOptions.sampleRate = conversions['float'](options.sampleRate, {
           context: `Failed to construct 'AudioContext': Failed to read the 'sinkId' property from AudioNodeOptions: The provided value (${options.sampleRate})`,
@@ -56,8 +107,8 @@ module.exports = function(jsExport, nativeBinding) {
       if (options.sinkId !== undefined) {
         if (typeof options.sinkId === 'object') {
           // https://webaudio.github.io/web-audio-api/#enumdef-audiosinktype
-          if (!('type' in options.sinkId) || options.sinkId.type !== 'none') {
-            throw TypeError(`Failed to construct 'AudioContext': Failed to read the 'sinkId' property from AudioNodeOptions: Failed to read the 'type' property from 'AudioSinkOptions': The provided value (${options.sinkId.type}) is not a valid enum value of type AudioSinkType.`);
+          if (options.sinkId === null || !('type' in options.sinkId) || options.sinkId.type !== 'none') {
+            throw TypeError(`Failed to construct 'AudioContext': Failed to read the 'sinkId' property from AudioNodeOptions: Failed to read the 'type' property from 'AudioSinkOptions': The provided value (${options.sinkId?.type}) is not a valid enum value of type AudioSinkType.`);
           }
 
           targetOptions.sinkId = 'none';
@@ -70,6 +121,21 @@ module.exports = function(jsExport, nativeBinding) {
         targetOptions.sinkId = '';
       }
 
+      // Non-spec: explicit size of the system-level audio callbacks in frames,
+      // takes precedence over `latencyHint`
+      if (options.outputBufferSize !== undefined) {
+        targetOptions.outputBufferSize = conversions['unsigned long'](options.outputBufferSize, {
+          enforceRange: true,
+          context: `Failed to construct 'AudioContext': Failed to read the 'outputBufferSize' property from AudioContextOptions: The provided value (${options.outputBufferSize})`,
+        });
+
+        if (targetOptions.outputBufferSize === 0) {
+          throw new RangeError(`Failed to construct 'AudioContext': Failed to read the 'outputBufferSize' property from AudioContextOptions: The provided value (0) should be strictly positive`);
+        }
+      } else {
+        targetOptions.outputBufferSize = null;
+      }
+
       let napiObj;
 
       try {
@@ -84,8 +150,44 @@ module.exports = function(jsExport, nativeBinding) {
         this.#sinkId = options.sinkId;
       }
 
+      this.#sinkType = targetOptions.sinkId === 'none' ? 'none' : 'device';
+
+      // Non-spec: brickwall limiter applied just before the device
+      if (conversions['boolean'](options.masterLimiter)) {
+        this.destination[kNapiObj].setLimiter(true);
+      }
+
+      // Non-spec: CPU budget of the AudioWorkletProcessors
+      if (options.workletProcessBudget !== undefined) {
+        this.audioWorklet[kWorkletSetProcessBudget](options.workletProcessBudget, 'AudioContext');
+      }
+
+      // a numeric hint or an explicit `outputBufferSize` is a custom latency
+      if (targetOptions.outputBufferSize !== null || typeof targetOptions.latencyHint === 'number') {
+        this.#latencyCategory = 'custom';
+      } else {
+        this.#latencyCategory = targetOptions.latencyHint;
+      }
+
+      // Size of the system-level audio callbacks, as picked by the upstream
+      // crate according to the latency hint (the null sink always uses 128)
+      if (targetOptions.outputBufferSize !== null) {
+        this.#callbackBufferSize = targetOptions.outputBufferSize;
+      } else if (targetOptions.sinkId === 'none') {
+        this.#callbackBufferSize = 128;
+      } else if (typeof targetOptions.latencyHint === 'number') {
+        this.#callbackBufferSize = nextPowerOfTwo(targetOptions.latencyHint * this.sampleRate);
+      } else {
+        this.#callbackBufferSize = {
+          interactive: 128,
+          balanced: 512,
+          playback: 1024,
+        }[targetOptions.latencyHint];
+      }
+
       this.#renderCapacity = new jsExport.AudioRenderCapacity({
         [kNapiObj]: this[kNapiObj].renderCapacity,
+        callbackDuration: this.#callbackBufferSize / this.sampleRate,
       });
 
       // Add function to Napi object to bridge from Rust events to JS EventTarget
@@ -99,6 +201,18 @@ module.exports = function(jsExport, nativeBinding) {
         propagateEvent(this, event);
       }).bind(this);
 
+      // Non-spec, called at each render quantum while `onrenderquantum` is set,
+      // notifications still queued when the callback is removed are ignored
+      this[kNapiObj][kOnRenderQuantum] = (function(err, frame) {
+        if (this.#onrenderquantum !== null) {
+          this.#onrenderquantum(frame, this.#renderLoad);
+        }
+      }).bind(this);
+
+      this.#renderCapacity.addEventListener('update', event => {
+        this.#renderLoad = event.averageLoad;
+      });
+
       // Workaround to bind the `sinkchange` and `statechange` events to EventTarget.
       // This must be called from JS facade ctor as the JS handler are added to the Napi
       // object after its instantiation, and that we don't have any initial `resume` call.
@@ -117,21 +231,33 @@ module.exports = function(jsExport, nativeBinding) {
       });
       // keep process awake until context is closed
       const keepAwakeId = setInterval(() => {}, 10 * 1000);
+      // allow to garbage collect the context and to the close the process
+      this.#releaseProcess = () => {
+        delete process[kAudioContextId];
+        clearTimeout(keepAwakeId);
+      };
       // clear on close
       this.addEventListener('statechange', () => {
         if (this.state === 'closed') {
-          // allow to garbage collect the context and to the close the process
-          delete process[kAudioContextId];
-          clearTimeout(keepAwakeId);
+          this.#releaseProcess();
         }
       });
 
+      registerContext(this);
+
       // for wpt tests, see ./.scripts/wpt_harness.mjs for informations
       if (process.WPT_TEST_RUNNER) {
         process.WPT_TEST_RUNNER.once('cleanup', () => this.close());
       }
     }
 
+    // Non-spec: maximum number of output channels of the default output device,
+    // i.e. the `destination.maxChannelCount` of a context opened on the default
+    // sink. Falls back to the limit of the renderer if there is no device.
+    static getMaxChannelCount() {
+      return nativeBinding.getMaxChannelCount();
+    }
+
     get baseLatency() {
       if (!(this instanceof AudioContext)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
@@ -182,6 +308,152 @@ module.exports = function(jsExport, nativeBinding) {
       }
     }
 
+    // Non-spec: `(frame, load) => {}` called at each render quantum with the
+    // index of its first frame and the last average load reported by
+    // `renderCapacity` (`null` if it has not been started). Notifications
+    // are dropped if the main thread lags behind.
+    get onrenderquantum() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#onrenderquantum;
+    }
+
+    set onrenderquantum(value) {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      if (!isFunction(value) && value !== null) {
+        return;
+      }
+
+      const listening = this.#onrenderquantum !== null;
+      this.#onrenderquantum = value;
+
+      if (listening !== (value !== null)) {
+        this[kNapiObj].listen_to_render_quantum(value !== null);
+      }
+    }
+
+    // Non-spec: silence the output sent to the device, the graph is still
+    // processed so that e.g. analysers still receive the signal
+    get muted() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#muted;
+    }
+
+    set muted(value) {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      value = conversions['boolean'](value);
+
+      if (value === this.#muted) {
+        return;
+      }
+
+      this.#muted = value;
+
+      const now = this.currentTime;
+      const muteGain = this.destination[kMuteGain];
+
+      muteGain.cancelScheduledValues(now);
+      muteGain.setValueAtTime(muteGain.value, now);
+      muteGain.linearRampToValueAtTime(value ? 0 : 1, now + kMuteDeclickDuration);
+    }
+
+    // Non-spec: cumulative number of render deadline misses. This is an
+    // estimate computed from the underrun ratio of the `renderCapacity`
+    // updates, which are collected internally while `renderCapacity` is not
+    // started by the user
+    getUnderrunCount() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#renderCapacity[kGetUnderrunCount]();
+    }
+
+    // Non-spec: sample rate the output device runs the stream at, i.e.
+    // `sampleRate` if the device supports it, the rate of its default
+    // configuration otherwise, in which case the system resamples the output
+    getDeviceSampleRate() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this[kNapiObj].getDeviceSampleRate();
+    }
+
+    // Non-spec: size in frames of the system-level audio callbacks the stream
+    // is opened with, i.e. `outputBufferSize` if given, which is checked
+    // against the range supported by the device, or the size picked by the
+    // upstream crate according to the latency hint otherwise
+    getOutputBufferSize() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#callbackBufferSize;
+    }
+
+    // Non-spec: look-ahead of the render thread in seconds, i.e. the duration
+    // of the system-level audio callbacks. Events scheduled at least this far
+    // ahead of `currentTime` are rendered at their exact time.
+    getSchedulingLookAhead() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.getOutputBufferSize() / this.sampleRate;
+    }
+
+    // Non-spec: latency category the context was created with, i.e.
+    // 'interactive' (the default), 'balanced', 'playback', or 'custom' if
+    // `latencyHint` is a number or if `outputBufferSize` is given
+    getLatencyCategory() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#latencyCategory;
+    }
+
+    // Non-spec: kind of sink the context is rendering to, i.e. 'device' for
+    // an audio output device or 'none' for the null sink, follows `setSinkId`
+    getSinkType() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      return this.#sinkType;
+    }
+
+    // Non-spec: diagnostics about the backend the context is rendering with,
+    // i.e. the host API (e.g. 'CoreAudio', 'WASAPI', 'ALSA', 'JACK' or 'none'
+    // for the null sink), the name and sample rate of the output device, and the
+    // buffer size of the audio stream
+    getBackendInfo() {
+      if (!(this instanceof AudioContext)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
+      }
+
+      const { hostApi, deviceName } = this[kNapiObj].getBackendInfo();
+
+      return {
+        hostApi,
+        deviceName,
+        sampleRate: this.getDeviceSampleRate(),
+        bufferSize: this.getOutputBufferSize(),
+      };
+    }
+
     getOutputTimestamp() {
       if (!(this instanceof AudioContext)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
@@ -206,17 +478,70 @@ module.exports = function(jsExport, nativeBinding) {
       await this[kNapiObj].suspend();
     }
 
-    async close() {
+    async close(options = {}) {
       if (!(this instanceof AudioContext)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
       }
 
+      if (typeof options !== 'object') {
+        throw new TypeError('Failed to execute \'close\' on \'AudioContext\': argument 1 is not of type \'AudioContextCloseOptions\'');
+      }
+
+      // Non-spec: wait for the scheduled audio to be played out, so that the
+      // end of the rendered audio is not cut off
+      const flush = options ? conversions['boolean'](options.flush) : false;
+
+      if (flush && this.state === 'running') {
+        await this.#drain();
+      }
+
       // Close audioWorklet first so that `run_audio_worklet_global_scope` exit first
       // The other way around works too because of `recv_timeout` but cleaner this way
       await this.audioWorklet[kWorkletRelease]();
+      // stop the render quantum notifications, cf. onrenderquantum
+      this.onrenderquantum = null;
       await this[kNapiObj].close();
     }
 
+    // Wait for the render thread to reach the last stop time of the playing
+    // sources, then for the rendered audio to go through the output latency.
+    // Sources that are not stopped are not waited for.
+    async #drain() {
+      const sleep = duration => new Promise(resolve => setTimeout(resolve, duration * 1000));
+      let endTime = this.currentTime;
+
+      for (const node of this[kScheduledSourceNodes]) {
+        if (node[kStopTime] !== null) {
+          endTime = Math.max(endTime, node[kStopTime]);
+        }
+      }
+
+      // the render thread may lag behind the wall clock
+      while (this.state === 'running' && this.currentTime < endTime) {
+        await sleep(endTime - this.currentTime);
+      }
+
+      if (this.state === 'running') {
+        await sleep(this.outputLatency);
+      }
+    }
+
+    // Non-spec: cf. closeAllContexts, the process references are released
+    // without waiting for the `statechange` event
+    [kTeardown]() {
+      if (this.#teardown === null) {
+        this.#teardown = (async () => {
+          if (this.state !== 'closed') {
+            await this.close();
+          }
+
+          this.#releaseProcess();
+        })();
+      }
+
+      return this.#teardown;
+    }
+
     async setSinkId(sinkId) {
       if (!(this instanceof AudioContext)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioContext\'');
@@ -229,8 +554,8 @@ module.exports = function(jsExport, nativeBinding) {
       let targetSinkId = '';
 
       if (typeof sinkId === 'object') {
-        if (!('type' in sinkId) || sinkId.type !== 'none') {
-          throw new TypeError(`Failed to execute 'setSinkId' on 'AudioContext': Failed to read the 'type' property from 'AudioSinkOptions': The provided value '${sinkId.type}' is not a valid enum value of type AudioSinkType.`);
+        if (sinkId === null || !('type' in sinkId) || sinkId.type !== 'none') {
+          throw new TypeError(`Failed to execute 'setSinkId' on 'AudioContext': Failed to read the 'type' property from 'AudioSinkOptions': The provided value '${sinkId?.type}' is not a valid enum value of type AudioSinkType.`);
         }
 
         targetSinkId = 'none';
@@ -247,6 +572,8 @@ module.exports = function(jsExport, nativeBinding) {
       } catch (err) {
         throwSanitizedError(err);
       }
+
+      this.#sinkType = targetSinkId === 'none' ? 'none' : 'device';
     }
 
     // online context only AudioNodes
@@ -315,6 +642,15 @@ module.exports = function(jsExport, nativeBinding) {
     sinkId: kEnumerableProperty,
     renderCapacity: kEnumerableProperty,
     onsinkchange: kEnumerableProperty,
+    onrenderquantum: kEnumerableProperty,
+    muted: kEnumerableProperty,
+    getUnderrunCount: kEnumerableProperty,
+    getDeviceSampleRate: kEnumerableProperty,
+    getOutputBufferSize: kEnumerableProperty,
+    getSchedulingLookAhead: kEnumerableProperty,
+    getLatencyCategory: kEnumerableProperty,
+    getSinkType: kEnumerableProperty,
+    getBackendInfo: kEnumerableProperty,
     getOutputTimestamp: kEnumerableProperty,
     resume: kEnumerableProperty,
     suspend: kEnumerableProperty,
@@ -1,6 +1,13 @@
-const { kNapiObj } = require('./lib/symbols.js');
-const { kEnumerableProperty } = require('./lib/utils.js');
+const {
+  kNapiObj,
+  kMuteGain,
+} = require('./lib/symbols.js');
+const {
+  kEnumerableProperty,
+  kHiddenProperty,
+} = require('./lib/utils.js');
 const AudioNode = require('./AudioNode.js');
+const AudioParam = require('./AudioParam.js');
 
 class AudioDestinationNode extends AudioNode {
   constructor(context, options) {
@@ -16,6 +23,12 @@ class AudioDestinationNode extends AudioNode {
     super(context, {
       [kNapiObj]: options[kNapiObj],
     });
+
+    // Gain applied just before the device, cf. `AudioContext.muted`
+    Object.defineProperty(this, kMuteGain, {
+      value: new AudioParam({ [kNapiObj]: this[kNapiObj].muteGain }),
+      ...kHiddenProperty,
+    });
   }
 
   get maxChannelCount() {
@@ -25,6 +38,48 @@ class AudioDestinationNode extends AudioNode {
 
     return this[kNapiObj].maxChannelCount;
   }
+
+  // Non-spec: peak and RMS levels of each channel of the signal reaching the
+  // device, i.e. after the mixdown to the channel count of the destination and
+  // after the master limiter, computed over the render quanta rendered since
+  // the last call, so that short peaks are not missed. The meter is only
+  // inserted in the graph on the first call, which returns an empty list.
+  getOutputLevel() {
+    if (!(this instanceof AudioDestinationNode)) {
+      throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'AudioDestinationNode'`);
+    }
+
+    return this[kNapiObj].getOutputLevel();
+  }
+
+  // Channel config must be propagated to the gain stages, so that they mix
+  // their inputs exactly as the destination would do
+  get channelCount() {
+    return super.channelCount;
+  }
+
+  set channelCount(value) {
+    super.channelCount = value;
+    this[kNapiObj].syncChannelConfig();
+  }
+
+  get channelCountMode() {
+    return super.channelCountMode;
+  }
+
+  set channelCountMode(value) {
+    super.channelCountMode = value;
+    this[kNapiObj].syncChannelConfig();
+  }
+
+  get channelInterpretation() {
+    return super.channelInterpretation;
+  }
+
+  set channelInterpretation(value) {
+    super.channelInterpretation = value;
+    this[kNapiObj].syncChannelConfig();
+  }
 }
 
 Object.defineProperties(AudioDestinationNode, {
@@ -47,7 +102,10 @@ Object.defineProperties(AudioDestinationNode.prototype, {
   },
 
   maxChannelCount: kEnumerableProperty,
+  getOutputLevel: kEnumerableProperty,
+  channelCount: kEnumerableProperty,
+  channelCountMode: kEnumerableProperty,
+  channelInterpretation: kEnumerableProperty,
 });
 
 module.exports = AudioDestinationNode;
-
@@ -2,7 +2,7 @@ const conversions = require('webidl-conversions');
 
 const { throwSanitizedError } = require('./lib/errors.js');
 const { kEnumerableProperty, kHiddenProperty } = require('./lib/utils.js');
-const { kNapiObj } = require('./lib/symbols.js');
+const { kNapiObj, kAudioParamNames } = require('./lib/symbols.js');
 
 const AudioParam = require('./AudioParam.js');
 
@@ -32,15 +32,15 @@ class AudioListener {
       ...kHiddenProperty,
     });
 
-    this.#positionX = new AudioParam({ [kNapiObj]: this[kNapiObj].positionX });
-    this.#positionY = new AudioParam({ [kNapiObj]: this[kNapiObj].positionY });
-    this.#positionZ = new AudioParam({ [kNapiObj]: this[kNapiObj].positionZ });
-    this.#forwardX = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardX });
-    this.#forwardY = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardY });
-    this.#forwardZ = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardZ });
-    this.#upX = new AudioParam({ [kNapiObj]: this[kNapiObj].upX });
-    this.#upY = new AudioParam({ [kNapiObj]: this[kNapiObj].upY });
-    this.#upZ = new AudioParam({ [kNapiObj]: this[kNapiObj].upZ });
+    this.#positionX = new AudioParam({ [kNapiObj]: this[kNapiObj].positionX, context: options.context });
+    this.#positionY = new AudioParam({ [kNapiObj]: this[kNapiObj].positionY, context: options.context });
+    this.#positionZ = new AudioParam({ [kNapiObj]: this[kNapiObj].positionZ, context: options.context });
+    this.#forwardX = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardX, context: options.context });
+    this.#forwardY = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardY, context: options.context });
+    this.#forwardZ = new AudioParam({ [kNapiObj]: this[kNapiObj].forwardZ, context: options.context });
+    this.#upX = new AudioParam({ [kNapiObj]: this[kNapiObj].upX, context: options.context });
+    this.#upY = new AudioParam({ [kNapiObj]: this[kNapiObj].upY, context: options.context });
+    this.#upZ = new AudioParam({ [kNapiObj]: this[kNapiObj].upZ, context: options.context });
   }
 
   get positionX() {
@@ -192,6 +192,18 @@ Object.defineProperties(AudioListener, {
     configurable: true,
     value: 0,
   },
+  // non spec, names of the AudioParams of the listener, cf. lib/graph.js
+  [kAudioParamNames]: {
+    __proto__: null,
+    writable: false,
+    enumerable: false,
+    configurable: false,
+    value: Object.freeze([
+      'positionX', 'positionY', 'positionZ',
+      'forwardX', 'forwardY', 'forwardZ',
+      'upX', 'upY', 'upZ',
+    ]),
+  },
 });
 
 Object.defineProperties(AudioListener.prototype, {
@@ -28,12 +28,58 @@ const {
 } = require('./lib/utils.js');
 const {
   kNapiObj,
+  kBypassRouting,
+  kFeedbackGuard,
+  kChannelAnalysers,
+  kOutputGate,
+  kInsertOutputGate,
+  kRerouteInput,
+  kRerouteOutput,
+  kGetConnections,
+  kGraphNodes,
 } = require('./lib/symbols.js');
 
 const AudioParam = require('./AudioParam.js');
 
+const {
+  addGraphNode,
+} = require('./lib/graph.js');
+
+// Nodes that can be bypassed are connected through their dry / wet routing,
+// cf. lib/bypass.js
+// AnalyserNodes created with `channelMode: 'perChannel'` are connected through
+// their channel splitter, cf. lib/analyser.js
+function napiInput(dest) {
+  if (dest[kChannelAnalysers]) {
+    return dest[kChannelAnalysers].input[kNapiObj];
+  }
+
+  return dest[kBypassRouting] ? dest[kBypassRouting].input[kNapiObj] : dest[kNapiObj];
+}
+
+// DelayNodes created with `clampFeedback` are connected through their limiter,
+// cf. lib/feedback.js
+// Paused AudioBufferSourceNodes are connected through their gate, cf.
+// AudioBufferSourceNode::pause
+function napiOutput(src) {
+  if (src[kOutputGate]) {
+    return src[kOutputGate][kNapiObj];
+  }
+
+  if (src[kFeedbackGuard]) {
+    return src[kFeedbackGuard].output[kNapiObj];
+  }
+
+  return src[kBypassRouting] ? src[kBypassRouting].output[kNapiObj] : src[kNapiObj];
+}
+
 class AudioNode extends EventTarget {
   #context = null;
+  // keep track of connections, cf. connect, connections made with a gain go
+  // through their own `edge` GainNode
+  #connections = [];
+  // registered in the graph of the context, cf. BaseAudioContext::exportGraph
+  #inGraph = false;
 
   constructor(context, options) {
     // Make constructor "private"
@@ -160,10 +206,11 @@ class AudioNode extends EventTarget {
     let destination;
     let output;
     let input;
+    let gain;
 
     // note that audio listener params are not wrapped
     if (args[0] instanceof AudioNode) {
-      destination = args[0][kNapiObj];
+      destination = napiInput(args[0]);
 
       if (args[1] !== undefined) {
         output = conversions['unsigned long'](args[1], {
@@ -182,12 +229,25 @@ class AudioNode extends EventTarget {
       } else {
         input = 0;
       }
+
+      // non spec, gain applied on the connection
+      if (args[3] !== undefined) {
+        if (args[3] === null || typeof args[3] !== 'object') {
+          throw new TypeError('Failed to execute \'connect\' on \'AudioNode\': parameter 4 is not of type \'object\'');
+        }
+
+        if (args[3].gain !== undefined) {
+          gain = conversions['float'](args[3].gain, {
+            context: 'Failed to execute \'connect\' on \'AudioNode\': Failed to read the \'gain\' property: The provided value',
+          });
+        }
+      }
     } else if (args[0] instanceof AudioParam) {
       if (arguments.length > 2) {
         throw new TypeError('Failed to execute \'connect\' on \'AudioNode\': parameter 1 is not of type \'AudioNode\'');
       }
 
-      destination = args[0][kNapiObj];
+      destination = napiInput(args[0]);
 
       if (args[1] !== undefined) {
         output = conversions['unsigned long'](args[1], {
@@ -204,16 +264,102 @@ class AudioNode extends EventTarget {
       throw new TypeError('Failed to execute \'connect\' on \'AudioNode\': Overload resolution failed');
     }
 
-    try {
-      this[kNapiObj].connect(destination, output, input);
-    } catch (err) {
-      throwSanitizedError(err);
+    // Connecting the same output to the same input several times is ignored,
+    // while the underlying graph would sum every connection
+    const connection = this.#connections.find(c => {
+      return c.destination === args[0] && c.output === output && c.input === input;
+    });
+
+    if (connection === undefined) {
+      let edge = null;
+
+      try {
+        if (gain === undefined) {
+          napiOutput(this).connect(destination, output, input);
+        } else {
+          edge = this.#createEdge(gain);
+          napiOutput(this).connect(edge[kNapiObj], output, 0);
+          edge[kNapiObj].connect(destination, 0, input);
+        }
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      this.#connections.push({ destination: args[0], output, input, edge });
+      this.#registerInGraph();
+
+      if (edge !== null) {
+        return edge.gain;
+      }
+    } else if (gain !== undefined) {
+      // non spec, the gain of an existing connection is updated
+      if (connection.edge === null) {
+        const edge = this.#createEdge(gain);
+
+        napiOutput(this).disconnect(destination, output, input);
+        napiOutput(this).connect(edge[kNapiObj], output, 0);
+        edge[kNapiObj].connect(destination, 0, input);
+        connection.edge = edge;
+      } else {
+        connection.edge.gain.value = gain;
+      }
+
+      return connection.edge.gain;
     }
 
     // return given destination
     return args[0];
   }
 
+  // Non-spec: `connect(destination, output, input, { gain })` routes the
+  // connection through its own GainNode, so that the connection can be
+  // attenuated without inserting a node in the graph. The gain AudioParam of
+  // the connection is returned by `connect` to change it later. The GainNode
+  // is released when the connection is removed by `disconnect`.
+  #createEdge(gain) {
+    const edge = this.#context.createGain();
+    edge.gain.value = gain;
+
+    return edge;
+  }
+
+  // Removes the gain nodes of the given connections made with a gain. This is
+  // done once the native disconnection succeeded, so that the connections are
+  // kept in sync with the native graph if it throws. `outputReleased` tells if
+  // the node is already disconnected from the gain nodes, i.e. if its whole
+  // output has been disconnected.
+  #releaseEdges(connections, outputReleased = false) {
+    for (const { edge, output } of connections) {
+      if (edge === null) {
+        continue;
+      }
+
+      if (!outputReleased) {
+        napiOutput(this).disconnect(edge[kNapiObj], output);
+      }
+
+      edge[kNapiObj].disconnect();
+    }
+  }
+
+  // Removes the connections matching `predicate`, `disconnect` removes the
+  // native connections made without gain. It is also called if no connection
+  // matches, so that the native node throws as required by the spec.
+  #disconnectMatching(predicate, disconnect) {
+    const matching = this.#connections.filter(predicate);
+
+    if (matching.length === 0 || matching.some(c => c.edge === null)) {
+      try {
+        disconnect();
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+    }
+
+    this.#releaseEdges(matching);
+    this.#connections = this.#connections.filter(c => !predicate(c));
+  }
+
   disconnect(...args) {
     if (!(this instanceof AudioNode)) {
       throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioNode\'');
@@ -221,7 +367,7 @@ class AudioNode extends EventTarget {
 
     if (arguments.length > 2) {
       if (args[0] instanceof AudioNode) {
-        const destination = args[0][kNapiObj];
+        const destination = napiInput(args[0]);
         const output = conversions['unsigned long'](args[1], {
           enforceRange: true,
           context: 'Failed to execute \'disconnect\' on \'AudioNode\':',
@@ -231,39 +377,39 @@ class AudioNode extends EventTarget {
           context: 'Failed to execute \'disconnect\' on \'AudioNode\':',
         });
 
-        try {
-          return this[kNapiObj].disconnect(destination, output, input);
-        } catch (err) {
-          throwSanitizedError(err);
-        }
+        this.#disconnectMatching(c => {
+          return c.destination === args[0] && c.output === output && c.input === input;
+        }, () => napiOutput(this).disconnect(destination, output, input));
+
+        return;
       } else {
         throw new TypeError('Failed to execute \'disconnect\' on \'AudioNode\': : Overload resolution failed');
       }
     } else if (arguments.length === 2) {
       if (args[0] instanceof AudioNode || args[0] instanceof AudioParam) {
-        const destination = args[0][kNapiObj];
+        const destination = napiInput(args[0]);
         const output = conversions['unsigned long'](args[1], {
           enforceRange: true,
           context: 'Failed to execute \'disconnect\' on \'AudioNode\':',
         });
 
-        try {
-          return this[kNapiObj].disconnect(destination, output);
-        } catch (err) {
-          throwSanitizedError(err);
-        }
+        this.#disconnectMatching(c => {
+          return c.destination === args[0] && c.output === output;
+        }, () => napiOutput(this).disconnect(destination, output));
+
+        return;
       } else {
         throw new TypeError('Failed to execute \'disconnect\' on \'AudioNode\': : Overload resolution failed');
       }
     } else if (arguments.length === 1) {
       if (args[0] instanceof AudioNode || args[0] instanceof AudioParam) {
-        const destination = args[0][kNapiObj];
+        const destination = napiInput(args[0]);
 
-        try {
-          return this[kNapiObj].disconnect(destination);
-        } catch (err) {
-          throwSanitizedError(err);
-        }
+        this.#disconnectMatching(c => {
+          return c.destination === args[0];
+        }, () => napiOutput(this).disconnect(destination));
+
+        return;
       } else if (Number.isFinite(args[0])) {
         const output = conversions['unsigned long'](args[0], {
           enforceRange: true,
@@ -271,10 +417,15 @@ class AudioNode extends EventTarget {
         });
 
         try {
-          return this[kNapiObj].disconnect(output);
+          napiOutput(this).disconnect(output);
         } catch (err) {
           throwSanitizedError(err);
         }
+
+        this.#releaseEdges(this.#connections.filter(c => c.output === output), true);
+        this.#connections = this.#connections.filter(c => c.output !== output);
+
+        return;
       }
 
       // Note that we don't have the "overload resolution failed" branch here
@@ -284,10 +435,75 @@ class AudioNode extends EventTarget {
     // Just call disconnect for remaning cases
     // - i.e. including node.disconnect(NaN), node.disconnect(null), etc.
     try {
-      this[kNapiObj].disconnect();
+      napiOutput(this).disconnect();
     } catch (err) {
       throwSanitizedError(err);
     }
+
+    this.#releaseEdges(this.#connections, true);
+    this.#connections = [];
+  }
+
+  // Non-spec: connections made with `connect`, cf. BaseAudioContext::exportGraph
+  [kGetConnections]() {
+    return this.#connections.slice();
+  }
+
+  // The node is kept weakly so that the graph does not prevent the node from
+  // being collected, cf. lib/graph.js
+  #registerInGraph() {
+    if (this.#inGraph) {
+      return;
+    }
+
+    addGraphNode(this.#context[kGraphNodes], this);
+    this.#inGraph = true;
+  }
+
+  // Move the current connections of the (single) output of the node to the
+  // given napi node, cf. lib/bypass.js
+  [kRerouteOutput](napiNode) {
+    this[kNapiObj].disconnect();
+
+    for (const { destination, input, edge } of this.#connections) {
+      if (edge === null) {
+        napiNode.connect(napiInput(destination), 0, input);
+      } else {
+        napiNode.connect(edge[kNapiObj], 0, 0);
+      }
+    }
+  }
+
+  // Move the connections made to the napi `previousInput` of `destination`
+  // to its current input, cf. lib/bypass.js
+  [kRerouteInput](destination, previousInput) {
+    for (const connection of this.#connections) {
+      if (connection.destination !== destination) {
+        continue;
+      }
+
+      const { output, input, edge } = connection;
+
+      if (edge === null) {
+        napiOutput(this).disconnect(previousInput, output, input);
+        napiOutput(this).connect(napiInput(destination), output, input);
+      } else {
+        edge[kNapiObj].disconnect(previousInput, 0, input);
+        edge[kNapiObj].connect(napiInput(destination), 0, input);
+      }
+    }
+  }
+
+  // Route the (single) output of the node through the given GainNode, the
+  // current connections are moved to the gate
+  [kInsertOutputGate](gate) {
+    this[kRerouteOutput](gate[kNapiObj]);
+    this[kNapiObj].connect(gate[kNapiObj], 0, 0);
+
+    Object.defineProperty(this, kOutputGate, {
+      value: gate,
+      ...kHiddenProperty,
+    });
   }
 }
 
@@ -27,6 +27,8 @@ const {
 } = require('./lib/errors.js');
 
 const {
+  assertWithinRenderDuration,
+  clampToCurrentTime,
   kEnumerableProperty,
   kHiddenProperty,
 } = require('./lib/utils.js');
@@ -34,7 +36,168 @@ const {
   kNapiObj,
 } = require('./lib/symbols.js');
 
+// Arguments of the automation methods, in order, cf. exportTimeline and importTimeline
+const kAutomationMethods = {
+  setValueAtTime: ['value', 'startTime'],
+  linearRampToValueAtTime: ['value', 'endTime'],
+  exponentialRampToValueAtTime: ['value', 'endTime'],
+  setTargetAtTime: ['target', 'startTime', 'timeConstant'],
+  setValueCurveAtTime: ['values', 'startTime', 'duration'],
+  cancelAndHoldAtTime: ['cancelTime'],
+};
+
+// Description of a recorded automation event, cf. getScheduledEvents
+function toScheduledEvent(event) {
+  switch (event.type) {
+    case 'setValueAtTime':
+      return { type: 'setValue', time: event.startTime, value: event.value };
+    case 'linearRampToValueAtTime':
+      return { type: 'linearRamp', time: event.endTime, value: event.value };
+    case 'exponentialRampToValueAtTime':
+      return { type: 'expRamp', time: event.endTime, value: event.value };
+    case 'setTargetAtTime':
+      return {
+        type: 'setTarget',
+        time: event.startTime,
+        value: event.target,
+        timeConstant: event.timeConstant,
+      };
+    case 'setValueCurveAtTime':
+      return {
+        type: 'setValueCurve',
+        time: event.startTime,
+        // value reached at the end of the curve
+        value: event.values[event.values.length - 1],
+        values: event.values.slice(),
+        duration: event.duration,
+      };
+    case 'cancelAndHoldAtTime':
+      return { type: 'cancelAndHold', time: event.cancelTime };
+  }
+}
+
+// Value of the automation described by the sorted scheduled `events` at
+// `time`, cf. getValueAtTime. `initialValue` is the value of the param before
+// the first event. The end of a value curve truncated by a cancelAndHold is
+// given by its `holdTime`.
+function valueAtTime(events, time, initialValue) {
+  // value and time reached by the previous events, `target` is the ongoing
+  // setTarget automation if any
+  let endTime = 0;
+  let endValue = initialValue;
+  let target = null;
+
+  const currentValue = t => {
+    if (target === null) {
+      return endValue;
+    }
+
+    return target.value + (target.startValue - target.value) * Math.exp(-(t - target.time) / target.timeConstant);
+  };
+
+  for (const event of events) {
+    switch (event.type) {
+      case 'linearRamp':
+      case 'expRamp': {
+        if (time < event.time) {
+          const startValue = currentValue(endTime);
+          const ratio = (time - endTime) / (event.time - endTime);
+
+          if (event.type === 'linearRamp') {
+            return startValue + (event.value - startValue) * ratio;
+          }
+
+          // the ramp holds its start value until its end if values are
+          // null or of opposite signs
+          if (startValue * event.value <= 0) {
+            return startValue;
+          }
+
+          return startValue * Math.pow(event.value / startValue, ratio);
+        }
+
+        endValue = event.value;
+        break;
+      }
+      case 'setTarget': {
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        target = {
+          time: event.time,
+          startValue: currentValue(event.time),
+          value: event.value,
+          timeConstant: event.timeConstant,
+        };
+        endTime = event.time;
+        continue;
+      }
+      case 'setValueCurve': {
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        const curveEndTime = event.time + event.duration;
+        const holdTime = event.holdTime !== undefined ? event.holdTime : curveEndTime;
+        const values = event.values;
+        // position in the curve, the end of the curve is held
+        const position = Math.min(time, holdTime);
+
+        if (position < curveEndTime) {
+          const k = (values.length - 1) * (position - event.time) / event.duration;
+          const index = Math.floor(k);
+          const value = values[index] + (values[index + 1] - values[index]) * (k - index);
+
+          if (time < holdTime) {
+            return value;
+          }
+
+          endValue = value;
+        } else {
+          endValue = values[values.length - 1];
+        }
+
+        target = null;
+        endTime = holdTime;
+        continue;
+      }
+      default: {
+        // setValue and cancelAndHold
+        if (time < event.time) {
+          return currentValue(time);
+        }
+
+        endValue = event.value;
+        break;
+      }
+    }
+
+    target = null;
+    endTime = event.time;
+  }
+
+  return currentValue(time);
+}
+
+// minimum number of recorded automation events before the timeline is pruned,
+// cf. #pruneAutomationEvents
+const kMinPruneLength = 32;
+
 class AudioParam {
+  #automationEvents = [];
+  // sorted list of the recorded events, resolved on demand, cf.
+  // #resolveAutomationEvents
+  #resolvedEvents = null;
+  // number of recorded events at which the timeline is pruned next
+  #pruneLength = kMinPruneLength;
+  // value of the param before the first automation event, cf. getValueAtTime
+  #initialValue = null;
+  // context of the param if known, cf. clampToCurrentTime
+  #context = null;
+  // non spec, the nominal range is bounded by the Nyquist frequency, cf. minValue
+  #nyquist = false;
+
   constructor(options) {
     // Make constructor "private"
     if (
@@ -49,6 +212,16 @@ class AudioParam {
       value: options[kNapiObj],
       ...kHiddenProperty,
     });
+
+    if (options.context !== undefined) {
+      this.#context = options.context;
+    }
+
+    if (options.nyquist === true) {
+      this.#nyquist = true;
+    }
+
+    this.#initialValue = this[kNapiObj].value;
   }
 
   get value() {
@@ -73,6 +246,24 @@ class AudioParam {
     } catch (err) {
       throwSanitizedError(err);
     }
+
+    // the value setter acts as setValueAtTime at the current time, cf.
+    // getValueAtTime
+    const startTime = this.#context !== null ? this.#context.currentTime : 0;
+    const events = this.#automationEvents;
+    const last = events[0];
+
+    // a timeline made of a single past set event, e.g. a param driven by the
+    // value setter: the new event replaces it, its value is held before, as
+    // it would be once pruned
+    if (events.length === 1 && last.type === 'setValueAtTime' && last.startTime <= startTime) {
+      this.#initialValue = last.value;
+      events[0] = { type: 'setValueAtTime', value, startTime };
+      this.#resolvedEvents = null;
+      return;
+    }
+
+    this.#recordAutomationEvent('setValueAtTime', { value, startTime });
   }
 
   get automationRate() {
@@ -113,6 +304,12 @@ class AudioParam {
       throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
     }
 
+    // non spec, the graph of an oversampled context is rendered at a higher
+    // rate, report the Nyquist frequency of the context, cf. renderOversample
+    if (this.#nyquist && this.#context !== null) {
+      return Math.sign(this[kNapiObj].minValue) * this.#context.sampleRate / 2;
+    }
+
     return this[kNapiObj].minValue;
   }
 
@@ -121,6 +318,12 @@ class AudioParam {
       throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
     }
 
+    // non spec, the graph of an oversampled context is rendered at a higher
+    // rate, report the Nyquist frequency of the context, cf. renderOversample
+    if (this.#nyquist && this.#context !== null) {
+      return Math.sign(this[kNapiObj].maxValue) * this.#context.sampleRate / 2;
+    }
+
     return this[kNapiObj].maxValue;
   }
 
@@ -141,12 +344,18 @@ class AudioParam {
       context: `Failed to execute 'setValueAtTime' on 'AudioParam': Parameter 2`,
     });
 
+    startTime = clampToCurrentTime(this.#context, startTime);
+    assertWithinRenderDuration(this.#context, 'setValueAtTime', startTime);
+
     try {
       this[kNapiObj].setValueAtTime(value, startTime);
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('setValueAtTime', { value, startTime });
+
     return this;
   }
 
@@ -167,12 +376,18 @@ class AudioParam {
       context: `Failed to execute 'linearRampToValueAtTime' on 'AudioParam': Parameter 2`,
     });
 
+    endTime = clampToCurrentTime(this.#context, endTime);
+    assertWithinRenderDuration(this.#context, 'linearRampToValueAtTime', endTime);
+
     try {
       this[kNapiObj].linearRampToValueAtTime(value, endTime);
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('linearRampToValueAtTime', { value, endTime });
+
     return this;
   }
 
@@ -193,12 +408,18 @@ class AudioParam {
       context: `Failed to execute 'exponentialRampToValueAtTime' on 'AudioParam': Parameter 2`,
     });
 
+    endTime = clampToCurrentTime(this.#context, endTime);
+    assertWithinRenderDuration(this.#context, 'exponentialRampToValueAtTime', endTime);
+
     try {
       this[kNapiObj].exponentialRampToValueAtTime(value, endTime);
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('exponentialRampToValueAtTime', { value, endTime });
+
     return this;
   }
 
@@ -223,12 +444,18 @@ class AudioParam {
       context: `Failed to execute 'setTargetAtTime' on 'AudioParam': Parameter 3`,
     });
 
+    startTime = clampToCurrentTime(this.#context, startTime);
+    assertWithinRenderDuration(this.#context, 'setTargetAtTime', startTime);
+
     try {
       this[kNapiObj].setTargetAtTime(target, startTime, timeConstant);
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('setTargetAtTime', { target, startTime, timeConstant });
+
     return this;
   }
 
@@ -255,12 +482,18 @@ class AudioParam {
       context: `Failed to execute 'setValueCurveAtTime' on 'AudioParam': Parameter 3`,
     });
 
+    startTime = clampToCurrentTime(this.#context, startTime);
+    assertWithinRenderDuration(this.#context, 'setValueCurveAtTime', startTime + duration);
+
     try {
       this[kNapiObj].setValueCurveAtTime(values, startTime, duration);
     } catch (err) {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('setValueCurveAtTime', { values, startTime, duration });
+
     return this;
   }
 
@@ -283,6 +516,9 @@ class AudioParam {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('cancelScheduledValues', { cancelTime });
+
     return this;
   }
 
@@ -305,9 +541,255 @@ class AudioParam {
       throwSanitizedError(err);
     }
 
+    // keep track of the automation events, cf. exportTimeline
+    this.#recordAutomationEvent('cancelAndHoldAtTime', { cancelTime });
+
     return this;
   }
 
+  // non spec
+  exportTimeline() {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
+    }
+
+    // prune the timeline before it is exposed
+    this.#scheduledAutomationEvents();
+
+    return this.#automationEvents.map(event => {
+      const copy = { ...event };
+
+      if (Array.isArray(copy.values)) {
+        copy.values = copy.values.slice();
+      }
+
+      return copy;
+    });
+  }
+
+  // non spec, read-only list of the automation events of the timeline, sorted
+  // by time. Events scheduled after a `cancelAndHoldAtTime` are removed, as
+  // they are by the render thread.
+  getScheduledEvents() {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
+    }
+
+    return this.#scheduledAutomationEvents().map(event => {
+      const { type, time, value, timeConstant, values, duration } = event;
+      const scheduledEvent = { type, time };
+
+      if (type !== 'cancelAndHold') {
+        scheduledEvent.value = value;
+      }
+
+      if (type === 'setTarget') {
+        scheduledEvent.timeConstant = timeConstant;
+      } else if (type === 'setValueCurve') {
+        scheduledEvent.values = values.slice();
+        scheduledEvent.duration = duration;
+      }
+
+      return scheduledEvent;
+    });
+  }
+
+  // non spec, value of the param at the given time computed from the
+  // scheduled automation events, cf. getScheduledEvents. After a
+  // `cancelAndHoldAtTime`, the value at the cancel time is held as it is by
+  // the render thread. Setting the `value` attribute schedules a setValue
+  // event at the current time.
+  getValueAtTime(time) {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
+    }
+
+    if (arguments.length < 1) {
+      throw new TypeError(`Failed to execute 'getValueAtTime' on 'AudioParam': 1 argument required, but only ${arguments.length} present`);
+    }
+
+    time = conversions['double'](time, {
+      context: `Failed to execute 'getValueAtTime' on 'AudioParam': Parameter 1`,
+    });
+
+    return valueAtTime(this.#scheduledAutomationEvents(), time, this.#initialValue);
+  }
+
+  // non spec
+  importTimeline(events) {
+    if (!(this instanceof AudioParam)) {
+      throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioParam\'');
+    }
+
+    if (arguments.length < 1) {
+      throw new TypeError(`Failed to execute 'importTimeline' on 'AudioParam': 1 argument required, but only ${arguments.length} present`);
+    }
+
+    if (events === null || typeof events !== 'object' || !(Symbol.iterator in events)) {
+      throw new TypeError(`Failed to execute 'importTimeline' on 'AudioParam': Parameter 1 is not iterable`);
+    }
+
+    events = Array.from(events);
+    // check the whole timeline before clearing the current one
+    events.forEach((event, index) => {
+      const argumentNames = event !== null && typeof event === 'object'
+        ? kAutomationMethods[event.type]
+        : undefined;
+
+      const isValid = argumentNames !== undefined && argumentNames.every(name => {
+        if (name === 'values') {
+          return event.values !== null
+            && typeof event.values === 'object'
+            && Symbol.iterator in event.values
+            && Array.from(event.values).every(value => typeof value === 'number');
+        } else {
+          return typeof event[name] === 'number';
+        }
+      });
+
+      if (!isValid) {
+        throw new TypeError(`Failed to execute 'importTimeline' on 'AudioParam': Invalid automation event at index ${index}`);
+      }
+    });
+
+    this.cancelScheduledValues(0);
+    this.#automationEvents = [];
+    this.#resolvedEvents = null;
+
+    events.forEach(event => {
+      const args = kAutomationMethods[event.type].map(name => event[name]);
+      this[event.type](...args);
+    });
+  }
+
+  #recordAutomationEvent(type, args) {
+    this.#resolvedEvents = null;
+
+    if (type === 'cancelScheduledValues') {
+      this.#automationEvents = this.#automationEvents.filter(event => {
+        const time = event.startTime !== undefined ? event.startTime
+          : event.endTime !== undefined ? event.endTime
+          : event.cancelTime;
+        return time < args.cancelTime;
+      });
+    } else {
+      const event = { type, ...args };
+
+      if (event.values !== undefined) {
+        event.values = Array.from(event.values);
+      }
+
+      this.#automationEvents.push(event);
+
+      if (this.#automationEvents.length >= this.#pruneLength) {
+        this.#pruneAutomationEvents();
+        // the timeline is pruned again once it has doubled, so that recording
+        // an event stays constant time on average
+        this.#pruneLength = Math.max(kMinPruneLength, 2 * this.#automationEvents.length);
+      }
+    }
+  }
+
+  // drop the events that ended before the current time so that the timeline
+  // does not grow without bound, the value reached by the last of them is
+  // kept as a `setValueAtTime` event
+  #pruneAutomationEvents() {
+    const currentTime = this.#context !== null ? this.#context.currentTime : 0;
+    const events = this.#resolveAutomationEvents();
+    // index of the last ended event, a setTarget is ended only by the next one
+    let last = -1;
+    let holdTime = 0;
+
+    events.forEach((event, index) => {
+      const endTime = event.type === 'setValueCurve'
+        ? (event.holdTime !== undefined ? event.holdTime : event.time + event.duration)
+        : event.time;
+
+      if (event.type !== 'setTarget' && endTime <= currentTime) {
+        last = index;
+        holdTime = endTime;
+      }
+    });
+
+    if (last < 1) {
+      return;
+    }
+
+    const holdValue = valueAtTime(events, holdTime, this.#initialValue);
+
+    // recorded events still needed, including the ones cancelled by a
+    // pending `cancelAndHoldAtTime` which contribute to its held value
+    const kept = new Set();
+    const keep = event => {
+      kept.add(event.recordedEvent);
+      event.cancelledEvents.forEach(keep);
+    };
+
+    events.slice(last + 1).forEach(keep);
+
+    this.#initialValue = holdValue;
+    this.#automationEvents = [
+      { type: 'setValueAtTime', value: holdValue, startTime: holdTime },
+      ...this.#automationEvents.filter(event => kept.has(event)),
+    ];
+    this.#resolvedEvents = null;
+  }
+
+  // resolved automation events, the timeline is pruned first if events have
+  // been recorded since it was last resolved
+  #scheduledAutomationEvents() {
+    if (this.#resolvedEvents === null) {
+      this.#pruneAutomationEvents();
+    }
+
+    return this.#resolveAutomationEvents();
+  }
+
+  // scheduled automation events sorted by time, with the value held by each
+  // `cancelAndHold` and the hold time of the value curves it truncates, cf.
+  // getValueAtTime. The list is cached until an event is recorded.
+  #resolveAutomationEvents() {
+    if (this.#resolvedEvents !== null) {
+      return this.#resolvedEvents;
+    }
+
+    let events = [];
+
+    this.#automationEvents.forEach(event => {
+      const scheduledEvent = toScheduledEvent(event);
+      // recorded event and events it cancels, cf. #pruneAutomationEvents
+      scheduledEvent.recordedEvent = event;
+      scheduledEvent.cancelledEvents = [];
+
+      if (scheduledEvent.type === 'cancelAndHold') {
+        const cancelTime = scheduledEvent.time;
+        // value reached at the cancel time by the events cancelled
+        scheduledEvent.value = valueAtTime(events, cancelTime, this.#initialValue);
+        scheduledEvent.cancelledEvents = events.filter(e => e.time >= cancelTime);
+
+        events = events.filter(e => e.time < cancelTime);
+        // value curves are truncated at the cancel time
+        events.forEach(e => {
+          if (e.type === 'setValueCurve' && e.time + e.duration > cancelTime) {
+            e.holdTime = cancelTime;
+          }
+        });
+      }
+
+      // insert after the events at the same time to keep the list sorted
+      let index = events.length;
+
+      while (index > 0 && events[index - 1].time > scheduledEvent.time) {
+        index -= 1;
+      }
+
+      events.splice(index, 0, scheduledEvent);
+    });
+
+    this.#resolvedEvents = events;
+
+    return events;
+  }
 }
 
 Object.defineProperties(AudioParam, {
@@ -340,6 +822,10 @@ Object.defineProperties(AudioParam.prototype, {
   setValueCurveAtTime: kEnumerableProperty,
   cancelScheduledValues: kEnumerableProperty,
   cancelAndHoldAtTime: kEnumerableProperty,
+  exportTimeline: kEnumerableProperty,
+  importTimeline: kEnumerableProperty,
+  getScheduledEvents: kEnumerableProperty,
+  getValueAtTime: kEnumerableProperty,
 });
 
 module.exports = AudioParam;
@@ -3,8 +3,10 @@ const conversions = require('webidl-conversions');
 const {
   kNapiObj,
   kOnUpdate,
+  kGetUnderrunCount,
 } = require('./lib/symbols.js');
 const {
+  isFunction,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -14,8 +16,20 @@ const {
   AudioRenderCapacityEvent,
 } = require('./Events.js');
 
+// Non-spec: update interval of the internal monitoring of the underruns while
+// the capacity is not started by the user, cf. AudioContext::getUnderrunCount.
+// Upstream rounds the underrun ratio up to the percent, a short interval keeps
+// the estimate close to the actual count.
+const kUnderrunMonitorInterval = 0.1;
+
 class AudioRenderCapacity extends EventTarget {
   #onupdate = null;
+  #updateInterval = kUnderrunMonitorInterval;
+  // the `update` events are only dispatched once started by the user
+  #started = false;
+  // duration of a system-level audio callback, cf. AudioContext
+  #callbackDuration = null;
+  #underrunCount = 0;
 
   constructor(options) {
     // Make constructor "private"
@@ -30,13 +44,32 @@ class AudioRenderCapacity extends EventTarget {
     super();
 
     this[kNapiObj] = options[kNapiObj];
+    this.#callbackDuration = options.callbackDuration;
 
     this[kNapiObj][kOnUpdate] = (function(err, rawEvent) {
+      // Upstream only gives us the ratio of underruns over the update interval,
+      // so we can only estimate the number of underruns from the number of
+      // system-level audio callbacks that occured during this interval
+      const numCallbacks = Math.round(this.#updateInterval / this.#callbackDuration);
+      this.#underrunCount += Math.round(rawEvent.underrunRatio * numCallbacks);
+
+      if (!this.#started) {
+        return;
+      }
+
       const event = new AudioRenderCapacityEvent('update', rawEvent);
       propagateEvent(this, event);
     }).bind(this);
 
     this[kNapiObj].listen_to_events();
+    this.#monitorUnderruns();
+  }
+
+  // keep collecting the metrics without dispatching the `update` events
+  #monitorUnderruns() {
+    this.#started = false;
+    this.#updateInterval = kUnderrunMonitorInterval;
+    this[kNapiObj].start({ updateInterval: kUnderrunMonitorInterval });
   }
 
   get onupdate() {
@@ -76,6 +109,9 @@ class AudioRenderCapacity extends EventTarget {
       targetOptions.updateInterval = 1;
     }
 
+    this.#started = true;
+    this.#updateInterval = targetOptions.updateInterval;
+
     return this[kNapiObj].start(targetOptions);
   }
 
@@ -84,7 +120,12 @@ class AudioRenderCapacity extends EventTarget {
       throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'AudioRenderCapacity'`);
     }
 
-    return this[kNapiObj].start();
+    // the underruns are still monitored, cf. AudioContext::getUnderrunCount
+    this.#monitorUnderruns();
+  }
+
+  [kGetUnderrunCount]() {
+    return this.#underrunCount;
   }
 }
 
@@ -108,7 +149,7 @@ Object.defineProperties(AudioRenderCapacity.prototype, {
   },
 
   onupdate: kEnumerableProperty,
-  stop: kEnumerableProperty,
+  start: kEnumerableProperty,
   stop: kEnumerableProperty,
 });
 
@@ -7,12 +7,16 @@ const {
   propagateEvent,
 } = require('./lib/events.js');
 const {
+  clampToCurrentTime,
   isFunction,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
   kNapiObj,
   kOnEnded,
+  kScheduledSourceNodes,
+  kStartTime,
+  kStopTime,
 } = require('./lib/symbols.js');
 
 const AudioNode = require('./AudioNode.js');
@@ -31,6 +35,22 @@ class AudioScheduledSourceNode extends AudioNode {
 
     super(context, options);
 
+    // time given to `start`, cf. getActualStartTime
+    Object.defineProperty(this, kStartTime, {
+      __proto__: null,
+      enumerable: false,
+      writable: true,
+      value: null,
+    });
+
+    // time given to the last call to `stop`, cf. AudioContext::close
+    Object.defineProperty(this, kStopTime, {
+      __proto__: null,
+      enumerable: false,
+      writable: true,
+      value: null,
+    });
+
     // Add function to Napi object to bridge from Rust events to JS EventTarget
     // It will be effectively registered on rust side when `start` is called
     //
@@ -39,6 +59,8 @@ class AudioScheduledSourceNode extends AudioNode {
     // oversized graphs and performance issues.
     // cf. https://github.com/ircam-ismm/node-web-audio-api/tree/fix/118
     this[kNapiObj][kOnEnded] = (function(_err, rawEvent) {
+      this.context[kScheduledSourceNodes].delete(this);
+
       const event = new Event(rawEvent.type);
       propagateEvent(this, event);
     }).bind(this);
@@ -71,11 +93,40 @@ class AudioScheduledSourceNode extends AudioNode {
       context: `Failed to execute 'start' on 'AudioScheduledSourceNode': Parameter 1`,
     });
 
+    when = clampToCurrentTime(this.context, when);
+
     try {
-      return this[kNapiObj].start(when);
+      this[kNapiObj].start(when);
     } catch (err) {
       throwSanitizedError(err);
     }
+
+    // keep track of the started sources, cf. BaseAudioContext::panic
+    this.context[kScheduledSourceNodes].add(this);
+    this[kStartTime] = when;
+  }
+
+  // non spec, context time at which the first sample of the source is
+  // rendered, i.e. the time given to `start` (with sub-quantum accuracy). If
+  // the source is created with the `trackActualStartTime` option, this is the
+  // time of the render quantum in which the render thread applied the call to
+  // `start` if it was in the past, which requires to probe the render thread
+  // at each call to `start`. `null` until the source has started.
+  getActualStartTime() {
+    if (!(this instanceof AudioScheduledSourceNode)) {
+      throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioScheduledSourceNode\'');
+    }
+
+    // recorded by the render thread, `null` until it has applied the `start`
+    const renderStartTime = this[kNapiObj].actualStartTime();
+
+    if (this[kStartTime] === null || renderStartTime === null) {
+      return null;
+    }
+
+    const startTime = Math.max(this[kStartTime], renderStartTime);
+
+    return this.context.currentTime <= startTime ? null : startTime;
   }
 
   stop(when = 0) {
@@ -87,11 +138,15 @@ class AudioScheduledSourceNode extends AudioNode {
       context: `Failed to execute 'stop' on 'AudioScheduledSourceNode': Parameter 1`,
     });
 
+    when = clampToCurrentTime(this.context, when);
+
     try {
-      return this[kNapiObj].stop(when);
+      this[kNapiObj].stop(when);
     } catch (err) {
       throwSanitizedError(err);
     }
+
+    this[kStopTime] = when;
   }
 }
 
@@ -116,6 +171,7 @@ Object.defineProperties(AudioScheduledSourceNode.prototype, {
   onended: kEnumerableProperty,
   start: kEnumerableProperty,
   stop: kEnumerableProperty,
+  getActualStartTime: kEnumerableProperty,
 });
 
 module.exports = AudioScheduledSourceNode;
@@ -15,12 +15,15 @@ const {
   kCreateProcessor,
   kPrivateConstructor,
   kWorkletRelease,
+  kWorkletSetProcessBudget,
   kCheckProcessorsCreated,
 } = require('./lib/symbols.js');
 const {
   kEnumerableProperty,
 } = require('./lib/utils.js');
 
+const conversions = require('webidl-conversions');
+
 const caller = require('caller');
 // cf. https://www.npmjs.com/package/node-fetch#commonjs
 const fetch = (...args) => import('node-fetch').then(({default: fetch}) => fetch(...args));
@@ -111,6 +114,7 @@ class AudioWorklet {
   #promiseId = 0;
   #workletParamDescriptorsMap = new Map();
   #pendingCreateProcessors = new Set();
+  #processBudget = null;
 
   constructor(options) {
     if (
@@ -171,6 +175,7 @@ class AudioWorklet {
           workerData: {
             workletId: this.#workletId,
             sampleRate: this.#sampleRate,
+            processBudget: this.#processBudget,
           },
         });
         this.#port.on('online', resolve);
@@ -193,6 +198,46 @@ class AudioWorklet {
     });
   }
 
+  // Non-spec: names of the processors registered by the modules added so far,
+  // in registration order. The processors registered by a module before it
+  // throws are kept, as they can still be used to create AudioWorkletNodes.
+  getRegisteredProcessors() {
+    return Array.from(this.#workletParamDescriptorsMap.keys());
+  }
+
+  // Non-spec: CPU budget of the processors, processors whose `process` method
+  // exceeds `maxDuration` seconds for `maxConsecutiveOverruns` consecutive render
+  // quanta are disabled and a `processorerror` event is dispatched on their node.
+  // Must be called before the Worker is launched, i.e. by the context constructor
+  [kWorkletSetProcessBudget](budget, interfaceName) {
+    if (typeof budget !== 'object' || budget === null) {
+      throw new TypeError(`Failed to construct '${interfaceName}': Failed to read the 'workletProcessBudget' property: The provided value is not of type 'object'`);
+    }
+
+    const maxDuration = conversions['double'](budget.maxDuration, {
+      context: `Failed to construct '${interfaceName}': Failed to read the 'maxDuration' property from 'workletProcessBudget': The provided value (${budget.maxDuration})`,
+    });
+
+    if (maxDuration <= 0) {
+      throw new RangeError(`Failed to construct '${interfaceName}': Failed to read the 'maxDuration' property from 'workletProcessBudget': The provided value (${maxDuration}) should be strictly positive`);
+    }
+
+    let maxConsecutiveOverruns = 3;
+
+    if (budget.maxConsecutiveOverruns !== undefined) {
+      maxConsecutiveOverruns = conversions['unsigned long'](budget.maxConsecutiveOverruns, {
+        enforceRange: true,
+        context: `Failed to construct '${interfaceName}': Failed to read the 'maxConsecutiveOverruns' property from 'workletProcessBudget': The provided value (${budget.maxConsecutiveOverruns})`,
+      });
+
+      if (maxConsecutiveOverruns < 1) {
+        throw new RangeError(`Failed to construct '${interfaceName}': Failed to read the 'maxConsecutiveOverruns' property from 'workletProcessBudget': The provided value (${maxConsecutiveOverruns}) should be greater than or equal to 1`);
+      }
+    }
+
+    this.#processBudget = { maxDuration, maxConsecutiveOverruns };
+  }
+
   // For OfflineAudioContext only, check that all processors have been properly
   // created before actual `startRendering`
   async [kCheckProcessorsCreated]() {
@@ -262,6 +307,7 @@ Object.defineProperties(AudioWorklet.prototype, {
   },
   addModule: kEnumerableProperty,
   port: kEnumerableProperty,
+  getRegisteredProcessors: kEnumerableProperty,
 });
 
 module.exports = AudioWorklet;
@@ -15,6 +15,7 @@ const {
 const {
   workletId,
   sampleRate,
+  processBudget,
 } = workerData;
 
 const kWorkletQueueTask = Symbol.for('node-web-audio-api:worklet-queue-task');
@@ -27,6 +28,7 @@ const kWorkletGetBuffer = Symbol.for('node-web-audio-api:worklet-get-buffer');
 const kWorkletRecycleBuffer = Symbol.for('node-web-audio-api:worklet-recycle-buffer');
 const kWorkletRecycleBuffer1 = Symbol.for('node-web-audio-api:worklet-recycle-buffer-1');
 const kWorkletMarkAsUntransferable = Symbol.for('node-web-audio-api:worklet-mark-as-untransferable');
+const kWorkletOverruns = Symbol.for('node-web-audio-api:worklet-overruns');
 // const kWorkletOrderedParamNames = Symbol.for('node-web-audio-api:worklet-ordered-param-names');
 
 
@@ -107,9 +109,13 @@ function isConstructor(f) {
   return true;
 }
 
+// Non-spec CPU budget of the processors, cf. `workletProcessBudget` context option
+const maxProcessDuration = processBudget ? processBudget.maxDuration : 0;
+const maxConsecutiveOverruns = processBudget ? processBudget.maxConsecutiveOverruns : 0;
+
 function runLoop() {
   // block until we need to render a quantum
-  run_audio_worklet_global_scope(workletId, processors);
+  run_audio_worklet_global_scope(workletId, processors, maxProcessDuration, maxConsecutiveOverruns);
   // yield to the event loop, and then repeat
   runLoopImmediateId = setImmediate(runLoop);
 }
@@ -138,6 +144,8 @@ globalThis.AudioWorkletProcessor = class AudioWorkletProcessor {
     // Mark [[callable process]] as true, set to false in render quantum
     // either "process" doese not exists, either it throws an error
     this[kWorkletCallableProcess] = true;
+    // Number of consecutive render quanta for which `process` exceeded the CPU budget
+    this[kWorkletOverruns] = 0;
 
     // Populate with dummy values which will be replaced in first render call
     this[kWorkletInputs] = new Array(numberOfInputs).fill([]);
@@ -14,6 +14,7 @@ const {
   kCreateProcessor,
 } = require('./lib/symbols.js');
 const {
+  isFunction,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -33,6 +34,7 @@ module.exports = (jsExport, nativeBinding) => {
   class AudioWorkletNode extends AudioNode {
     #port = null;
     #parameters = {};
+    #onended = null;
 
     constructor(context, name, options) {
       if (arguments.length < 2) {
@@ -204,6 +206,7 @@ module.exports = (jsExport, nativeBinding) => {
       for (let name in this[kNapiObj].parameters) {
         const audioParam = new jsExport.AudioParam({
           [kNapiObj]: this[kNapiObj].parameters[name],
+          context,
         });
 
         parameters.set(name, audioParam);
@@ -245,6 +248,12 @@ module.exports = (jsExport, nativeBinding) => {
             propagateEvent(this, event);
             break;
           }
+          // Non-spec: processor without input returned false from `process`
+          case 'node-web-audio-api:worklet:process-ended': {
+            const event = new Event('ended');
+            propagateEvent(this, event);
+            break;
+          }
         }
       });
     }
@@ -264,6 +273,29 @@ module.exports = (jsExport, nativeBinding) => {
 
       return this.#port;
     }
+
+    // Non-spec: `ended` is dispatched once when a processor without input
+    // returns false from `process`, after which it is not called anymore.
+    // Limitation: it is never dispatched for a processor with inputs, which
+    // keeps being called as long as its inputs are active, as per the spec,
+    // and which can thus always be revived by connecting a new source
+    get onended() {
+      if (!(this instanceof AudioWorkletNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioWorkletNode\'');
+      }
+
+      return this.#onended;
+    }
+
+    set onended(value) {
+      if (!(this instanceof AudioWorkletNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'AudioWorkletNode\'');
+      }
+
+      if (isFunction(value) || value === null) {
+        this.#onended = value;
+      }
+    }
   }
 
   Object.defineProperties(AudioWorkletNode, {
@@ -286,6 +318,7 @@ module.exports = (jsExport, nativeBinding) => {
     },
     parameters: kEnumerableProperty,
     port: kEnumerableProperty,
+    onended: kEnumerableProperty,
   });
 
   return AudioWorkletNode;
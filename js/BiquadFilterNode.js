@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,8 +34,14 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  isBypassed,
+  setBypass,
+} = require('./lib/bypass.js');
 
 const AudioNode = require('./AudioNode.js');
 
@@ -139,15 +147,20 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#frequency = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].frequency,
+        context,
+        nyquist: true,
       });
       this.#detune = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].detune,
+        context,
       });
       this.#Q = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].Q,
+        context,
       });
       this.#gain = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].gain,
+        context,
       });
     }
 
@@ -183,6 +196,26 @@ module.exports = (jsExport, nativeBinding) => {
       return this.#gain;
     }
 
+    // non spec
+    get bypass() {
+      if (!(this instanceof BiquadFilterNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BiquadFilterNode\'');
+      }
+
+      return isBypassed(this);
+    }
+
+    // non spec
+    set bypass(value) {
+      if (!(this instanceof BiquadFilterNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BiquadFilterNode\'');
+      }
+
+      value = conversions['boolean'](value);
+      // the dry / wet routing is created on first bypass, cf. lib/bypass.js
+      setBypass(this, value, jsExport.GainNode);
+    }
+
     get type() {
       if (!(this instanceof BiquadFilterNode)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BiquadFilterNode\'');
@@ -191,14 +224,17 @@ module.exports = (jsExport, nativeBinding) => {
       return this[kNapiObj].type;
     }
 
+    // lowpass, highpass, bandpass, notch and allpass ignore the gain param,
+    // lowshelf and highshelf ignore Q, peaking uses both
     set type(value) {
       if (!(this instanceof BiquadFilterNode)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'BiquadFilterNode\'');
       }
 
+      // Non-spec: an unknown value throws instead of being ignored, as
+      // silently keeping the previous value makes mistakes hard to spot
       if (!['lowpass', 'highpass', 'bandpass', 'lowshelf', 'highshelf', 'peaking', 'notch', 'allpass'].includes(value)) {
-        console.warn(`Failed to set the 'type' property on 'BiquadFilterNode': Value '${value}' is not a valid 'BiquadFilterType' enum value`);
-        return;
+        throw new TypeError(`Failed to set the 'type' property on 'BiquadFilterNode': The provided value '${value}' is not a valid enum value of type BiquadFilterType`);
       }
 
       try {
@@ -246,6 +282,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['frequency', 'detune', 'Q', 'gain']),
+    },
   });
 
   Object.defineProperties(BiquadFilterNode.prototype, {
@@ -262,6 +306,7 @@ module.exports = (jsExport, nativeBinding) => {
     gain: kEnumerableProperty,
     type: kEnumerableProperty,
     getFrequencyResponse: kEnumerableProperty,
+    bypass: kEnumerableProperty,
   });
 
   return BiquadFilterNode;
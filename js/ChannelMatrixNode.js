@@ -0,0 +1,138 @@
+const conversions = require('webidl-conversions');
+
+const {
+  throwSanitizedError,
+} = require('./lib/errors.js');
+const {
+  kEnumerableProperty,
+} = require('./lib/utils.js');
+const {
+  kNapiObj,
+} = require('./lib/symbols.js');
+
+const AudioNode = require('./AudioNode.js');
+
+// Non-spec: mixes the `inputChannelCount` channels of its input into the
+// `outputChannelCount` channels of its output with an explicit gain matrix.
+// The matrix is given row by row, i.e. output channel `o` is the sum of the
+// input channels `i` weighted by `matrix[o * inputChannelCount + i]`. The
+// input is discrete, i.e. missing input channels are silent and extra ones
+// are dropped.
+module.exports = (jsExport, nativeBinding) => {
+  class ChannelMatrixNode extends AudioNode {
+    #inputChannelCount = null;
+    #outputChannelCount = null;
+    #matrix = null;
+
+    constructor(context, options) {
+      if (arguments.length < 2) {
+        throw new TypeError(`Failed to construct 'ChannelMatrixNode': 2 arguments required, but only ${arguments.length} present`);
+      }
+
+      if (!(context instanceof jsExport.BaseAudioContext)) {
+        throw new TypeError(`Failed to construct 'ChannelMatrixNode': argument 1 is not of type BaseAudioContext`);
+      }
+
+      if (options === null || typeof options !== 'object') {
+        throw new TypeError('Failed to construct \'ChannelMatrixNode\': argument 2 is not of type \'ChannelMatrixOptions\'');
+      }
+
+      // parsed version of the option to be passed to NAPI
+      const parsedOptions = {};
+
+      for (const name of ['inputChannelCount', 'outputChannelCount']) {
+        if (options[name] === undefined) {
+          throw new TypeError(`Failed to construct 'ChannelMatrixNode': Failed to read the '${name}' property from ChannelMatrixOptions: Required member is undefined`);
+        }
+
+        parsedOptions[name] = conversions['unsigned long'](options[name], {
+          enforceRange: true,
+          context: `Failed to construct 'ChannelMatrixNode': Failed to read the '${name}' property from ChannelMatrixOptions: The provided value (${options[name]})`,
+        });
+      }
+
+      if (options.matrix === undefined) {
+        throw new TypeError('Failed to construct \'ChannelMatrixNode\': Failed to read the \'matrix\' property from ChannelMatrixOptions: Required member is undefined');
+      }
+
+      if (options.matrix instanceof Float32Array) {
+        // copy so that the node is not affected by later changes of the array
+        parsedOptions.matrix = new Float32Array(options.matrix);
+      } else if (Array.isArray(options.matrix)) {
+        parsedOptions.matrix = Float32Array.from(options.matrix, value => {
+          return conversions['float'](value, {
+            context: `Failed to construct 'ChannelMatrixNode': Failed to read the 'matrix' property from ChannelMatrixOptions: The provided value`,
+          });
+        });
+      } else {
+        throw new TypeError('Failed to construct \'ChannelMatrixNode\': Failed to read the \'matrix\' property from ChannelMatrixOptions: The provided value cannot be converted to \'Float32Array\'');
+      }
+
+      let napiObj;
+
+      try {
+        napiObj = new nativeBinding.ChannelMatrixNode(context[kNapiObj], parsedOptions);
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      super(context, {
+        [kNapiObj]: napiObj,
+      });
+
+      this.#inputChannelCount = parsedOptions.inputChannelCount;
+      this.#outputChannelCount = parsedOptions.outputChannelCount;
+      this.#matrix = parsedOptions.matrix;
+    }
+
+    get inputChannelCount() {
+      if (!(this instanceof ChannelMatrixNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ChannelMatrixNode\'');
+      }
+
+      return this.#inputChannelCount;
+    }
+
+    get outputChannelCount() {
+      if (!(this instanceof ChannelMatrixNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ChannelMatrixNode\'');
+      }
+
+      return this.#outputChannelCount;
+    }
+
+    // Copy of the matrix, the matrix can't be changed once the node is created
+    get matrix() {
+      if (!(this instanceof ChannelMatrixNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ChannelMatrixNode\'');
+      }
+
+      return new Float32Array(this.#matrix);
+    }
+  }
+
+  Object.defineProperties(ChannelMatrixNode, {
+    length: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 2,
+    },
+  });
+
+  Object.defineProperties(ChannelMatrixNode.prototype, {
+    [Symbol.toStringTag]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 'ChannelMatrixNode',
+    },
+    inputChannelCount: kEnumerableProperty,
+    outputChannelCount: kEnumerableProperty,
+    matrix: kEnumerableProperty,
+  });
+
+  return ChannelMatrixNode;
+};
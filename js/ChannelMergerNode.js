@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,6 +34,8 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
 
@@ -109,6 +113,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze([]),
+    },
   });
 
   Object.defineProperties(ChannelMergerNode.prototype, {
@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,6 +34,8 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
 
@@ -67,6 +71,13 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.offset = 1;
       }
 
+      // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+      if (options && options.trackActualStartTime !== undefined) {
+        parsedOptions.trackActualStartTime = conversions['boolean'](options.trackActualStartTime);
+      } else {
+        parsedOptions.trackActualStartTime = false;
+      }
+
       let napiObj;
 
       try {
@@ -81,6 +92,7 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#offset = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].offset,
+        context,
       });
     }
 
@@ -102,6 +114,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['offset']),
+    },
   });
 
   Object.defineProperties(ConstantSourceNode.prototype, {
@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,13 +34,27 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  isBypassed,
+  setBypass,
+} = require('./lib/bypass.js');
+const {
+  createImpulseSwap,
+  swapImpulse,
+  resetImpulseSwap,
+  toRenderImpulse,
+} = require('./lib/convolver.js');
 
 const AudioNode = require('./AudioNode.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class ConvolverNode extends AudioNode {
+    // non spec, cf. swapBuffer
+    #impulseSwap = null;
 
     constructor(context, options) {
 
@@ -80,6 +96,10 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.disableNormalization = false;
       }
 
+      if (parsedOptions.buffer !== null) {
+        parsedOptions.buffer = toRenderImpulse(context, parsedOptions.buffer);
+      }
+
       if (options && options.channelCount !== undefined) {
         parsedOptions.channelCount = conversions['unsigned long'](options.channelCount, {
           enforceRange: true,
@@ -122,7 +142,90 @@ module.exports = (jsExport, nativeBinding) => {
       if (options && options.buffer !== undefined) {
         this[kAudioBuffer] = options.buffer;
       }
+    }
+
+    // non spec
+    get bypass() {
+      if (!(this instanceof ConvolverNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ConvolverNode\'');
+      }
+
+      return isBypassed(this);
+    }
+
+    // non spec
+    set bypass(value) {
+      if (!(this instanceof ConvolverNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ConvolverNode\'');
+      }
+
+      value = conversions['boolean'](value);
+      // the dry / wet routing is created on first bypass, cf. lib/bypass.js
+      setBypass(this, value, jsExport.GainNode);
+    }
+
+    // non spec, length of the buffer without copying its data to JS
+    get bufferLength() {
+      if (!(this instanceof ConvolverNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ConvolverNode\'');
+      }
+
+      return this[kAudioBuffer] !== null ? this[kAudioBuffer].length : 0;
+    }
+
+    // non spec, set the impulse response of the node with a crossfade of
+    // `crossfadeSeconds` (defaults to 0.05) between the old and the new
+    // impulse responses, to prevent clicks. A crossfade of 0 is equivalent to
+    // setting the `buffer` attribute. A swap requested while a crossfade is
+    // running restarts the crossfade from the current mix.
+    swapBuffer(buffer, options = {}) {
+      if (!(this instanceof ConvolverNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ConvolverNode\'');
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'swapBuffer' on 'ConvolverNode': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (!(buffer instanceof jsExport.AudioBuffer)) {
+        throw new TypeError(`Failed to execute 'swapBuffer' on 'ConvolverNode': Parameter 1 is not of type 'AudioBuffer'`);
+      }
+
+      if (options === null || typeof options !== 'object') {
+        throw new TypeError(`Failed to execute 'swapBuffer' on 'ConvolverNode': Parameter 2 is not of type 'object'`);
+      }
+
+      let crossfadeSeconds = 0.05;
 
+      if (options.crossfadeSeconds !== undefined) {
+        crossfadeSeconds = conversions['double'](options.crossfadeSeconds, {
+          context: `Failed to execute 'swapBuffer' on 'ConvolverNode': Failed to read the 'crossfadeSeconds' property from options: The provided value (${options.crossfadeSeconds})`,
+        });
+      }
+
+      if (crossfadeSeconds < 0) {
+        throw new RangeError(`Failed to execute 'swapBuffer' on 'ConvolverNode': crossfadeSeconds (${crossfadeSeconds}) should be positive`);
+      }
+
+      if (crossfadeSeconds === 0) {
+        this.buffer = buffer;
+        return;
+      }
+
+      if (this.#impulseSwap === null) {
+        this.#impulseSwap = createImpulseSwap(this, nativeBinding, jsExport.GainNode, jsExport.ConstantSourceNode);
+      }
+
+      // cf. OfflineAudioContext renderOversample
+      const napiBuffer = toRenderImpulse(this.context, buffer[kNapiObj]);
+
+      try {
+        swapImpulse(this.#impulseSwap, napiBuffer, crossfadeSeconds);
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      this[kAudioBuffer] = buffer;
     }
 
     get buffer() {
@@ -145,13 +248,21 @@ module.exports = (jsExport, nativeBinding) => {
         throw new TypeError('Failed to set the \'buffer\' property on \'ConvolverNode\': Failed to convert value to \'AudioBuffer\'');
       }
 
+      // cf. OfflineAudioContext renderOversample
+      const napiBuffer = toRenderImpulse(this.context, value[kNapiObj]);
+
       try {
-        this[kNapiObj].buffer = value[kNapiObj];
+        this[kNapiObj].buffer = napiBuffer;
       } catch (err) {
         throwSanitizedError(err);
       }
 
       this[kAudioBuffer] = value;
+
+      // the buffer is given to the node, cf. swapBuffer
+      if (this.#impulseSwap !== null) {
+        resetImpulseSwap(this.#impulseSwap);
+      }
     }
 
     get normalize() {
@@ -188,6 +299,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze([]),
+    },
   });
 
   Object.defineProperties(ConvolverNode.prototype, {
@@ -201,6 +320,9 @@ module.exports = (jsExport, nativeBinding) => {
 
     buffer: kEnumerableProperty,
     normalize: kEnumerableProperty,
+    bypass: kEnumerableProperty,
+    bufferLength: kEnumerableProperty,
+    swapBuffer: kEnumerableProperty,
 
   });
 
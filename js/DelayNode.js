@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,14 +34,25 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  kFeedbackGuard,
+} = require('./lib/symbols.js');
+const {
+  createFeedbackGuard,
+} = require('./lib/feedback.js');
 
 const AudioNode = require('./AudioNode.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class DelayNode extends AudioNode {
 
+    // non spec, cf. maxDelayTime
+    #maxDelayTime = null;
+
     #delayTime = null;
 
     constructor(context, options) {
@@ -75,6 +88,12 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.delayTime = 0;
       }
 
+      if (options && options.clampFeedback !== undefined) {
+        parsedOptions.clampFeedback = conversions['boolean'](options.clampFeedback);
+      } else {
+        parsedOptions.clampFeedback = false;
+      }
+
       if (options && options.channelCount !== undefined) {
         parsedOptions.channelCount = conversions['unsigned long'](options.channelCount, {
           enforceRange: true,
@@ -108,7 +127,19 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#delayTime = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].delayTime,
+        context,
+      });
+
+      // non spec, route the output of the node through a limiter, cf. clampFeedback
+      Object.defineProperty(this, kFeedbackGuard, {
+        __proto__: null,
+        enumerable: false,
+        value: parsedOptions.clampFeedback
+          ? createFeedbackGuard(this, jsExport.GainNode, jsExport.WaveShaperNode)
+          : null,
       });
+
+      this.#maxDelayTime = parsedOptions.maxDelayTime;
     }
 
     get delayTime() {
@@ -119,6 +150,35 @@ module.exports = (jsExport, nativeBinding) => {
       return this.#delayTime;
     }
 
+    // non spec
+    get clampFeedback() {
+      if (!(this instanceof DelayNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DelayNode\'');
+      }
+
+      return this[kFeedbackGuard] !== null;
+    }
+
+    // non spec
+    get maxDelayTime() {
+      if (!(this instanceof DelayNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DelayNode\'');
+      }
+
+      return this.#maxDelayTime;
+    }
+
+    // non spec, number of frames (per channel) reserved by the native delay
+    // line, i.e. `maxDelayTime` rounded up to a render quantum at the rate of
+    // the render graph, plus the render quantum being written
+    getAllocatedFrames() {
+      if (!(this instanceof DelayNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DelayNode\'');
+      }
+
+      return this[kNapiObj].allocatedFrames();
+    }
+
   }
 
   Object.defineProperties(DelayNode, {
@@ -129,6 +189,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['delayTime']),
+    },
   });
 
   Object.defineProperties(DelayNode.prototype, {
@@ -140,6 +208,9 @@ module.exports = (jsExport, nativeBinding) => {
       value: 'DelayNode',
     },
     delayTime: kEnumerableProperty,
+    clampFeedback: kEnumerableProperty,
+    maxDelayTime: kEnumerableProperty,
+    getAllocatedFrames: kEnumerableProperty,
 
   });
 
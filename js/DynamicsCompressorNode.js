@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,8 +34,14 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  isBypassed,
+  setBypass,
+} = require('./lib/bypass.js');
 
 const AudioNode = require('./AudioNode.js');
 
@@ -136,18 +144,23 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#threshold = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].threshold,
+        context,
       });
       this.#knee = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].knee,
+        context,
       });
       this.#ratio = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].ratio,
+        context,
       });
       this.#attack = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].attack,
+        context,
       });
       this.#release = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].release,
+        context,
       });
     }
 
@@ -191,6 +204,26 @@ module.exports = (jsExport, nativeBinding) => {
       return this.#release;
     }
 
+    // non spec
+    get bypass() {
+      if (!(this instanceof DynamicsCompressorNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DynamicsCompressorNode\'');
+      }
+
+      return isBypassed(this);
+    }
+
+    // non spec
+    set bypass(value) {
+      if (!(this instanceof DynamicsCompressorNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DynamicsCompressorNode\'');
+      }
+
+      value = conversions['boolean'](value);
+      // the dry / wet routing is created on first bypass, cf. lib/bypass.js
+      setBypass(this, value, jsExport.GainNode);
+    }
+
     get reduction() {
       if (!(this instanceof DynamicsCompressorNode)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'DynamicsCompressorNode\'');
@@ -209,6 +242,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['threshold', 'knee', 'ratio', 'attack', 'release']),
+    },
   });
 
   Object.defineProperties(DynamicsCompressorNode.prototype, {
@@ -225,6 +266,7 @@ module.exports = (jsExport, nativeBinding) => {
     attack: kEnumerableProperty,
     release: kEnumerableProperty,
     reduction: kEnumerableProperty,
+    bypass: kEnumerableProperty,
 
   });
 
@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,8 +34,12 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+// non spec, lowest value reached by an exponential fade, i.e. -120dB
+const kFadeEpsilon = 1e-6;
 
 const AudioNode = require('./AudioNode.js');
 
@@ -100,6 +106,7 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#gain = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].gain,
+        context,
       });
     }
 
@@ -111,6 +118,63 @@ module.exports = (jsExport, nativeBinding) => {
       return this.#gain;
     }
 
+    // non spec, ramps the gain from its current value to `targetValue` over
+    // `duration` seconds starting at `currentTime`, e.g. to declick the
+    // start or the end of a sound. An exponential fade can't reach or start
+    // from zero, so it goes through `kFadeEpsilon` instead.
+    fade(targetValue, duration, options = {}) {
+      if (!(this instanceof GainNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'GainNode\'');
+      }
+
+      if (arguments.length < 2) {
+        throw new TypeError(`Failed to execute 'fade' on 'GainNode': 2 arguments required, but only ${arguments.length} present`);
+      }
+
+      targetValue = conversions['float'](targetValue, {
+        context: `Failed to execute 'fade' on 'GainNode': Parameter 1`,
+      });
+
+      duration = conversions['double'](duration, {
+        context: `Failed to execute 'fade' on 'GainNode': Parameter 2`,
+      });
+
+      if (duration < 0) {
+        throw new RangeError(`Failed to execute 'fade' on 'GainNode': Parameter 2 (${duration}) must be greater than or equal to 0`);
+      }
+
+      if (typeof options !== 'object' || options === null) {
+        throw new TypeError(`Failed to execute 'fade' on 'GainNode': Parameter 3 is not of type 'object'`);
+      }
+
+      const curve = options.curve === undefined ? 'linear' : options.curve;
+
+      if (!['linear', 'exponential'].includes(curve)) {
+        throw new TypeError(`Failed to execute 'fade' on 'GainNode': The provided value '${curve}' is not a valid fade curve, should be one of 'linear', 'exponential'`);
+      }
+
+      const gain = this.gain;
+      const startTime = this.context.currentTime;
+      const endTime = startTime + duration;
+
+      // keep the value of an ongoing automation
+      gain.cancelAndHoldAtTime(startTime);
+
+      if (curve === 'linear') {
+        gain.linearRampToValueAtTime(targetValue, endTime);
+      } else {
+        if (gain.getValueAtTime(startTime) < kFadeEpsilon) {
+          gain.setValueAtTime(kFadeEpsilon, startTime);
+        }
+
+        gain.exponentialRampToValueAtTime(Math.max(targetValue, kFadeEpsilon), endTime);
+
+        if (targetValue < kFadeEpsilon) {
+          gain.setValueAtTime(targetValue, endTime);
+        }
+      }
+    }
+
   }
 
   Object.defineProperties(GainNode, {
@@ -121,6 +185,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['gain']),
+    },
   });
 
   Object.defineProperties(GainNode.prototype, {
@@ -132,6 +204,7 @@ module.exports = (jsExport, nativeBinding) => {
       value: 'GainNode',
     },
     gain: kEnumerableProperty,
+    fade: kEnumerableProperty,
 
   });
 
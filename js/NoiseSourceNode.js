@@ -0,0 +1,115 @@
+const conversions = require('webidl-conversions');
+
+const {
+  throwSanitizedError,
+} = require('./lib/errors.js');
+const {
+  kEnumerableProperty,
+} = require('./lib/utils.js');
+const {
+  kNapiObj,
+} = require('./lib/symbols.js');
+
+const AudioScheduledSourceNode = require('./AudioScheduledSourceNode.js');
+
+// Non-spec: source node generating white, pink or brown noise on the render
+// thread, mono output
+const kNoiseTypes = ['white', 'pink', 'brown'];
+
+module.exports = (jsExport, nativeBinding) => {
+  class NoiseSourceNode extends AudioScheduledSourceNode {
+    constructor(context, options) {
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to construct 'NoiseSourceNode': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (!(context instanceof jsExport.BaseAudioContext)) {
+        throw new TypeError(`Failed to construct 'NoiseSourceNode': argument 1 is not of type BaseAudioContext`);
+      }
+
+      // parsed version of the option to be passed to NAPI
+      const parsedOptions = {};
+
+      if (options && typeof options !== 'object') {
+        throw new TypeError('Failed to construct \'NoiseSourceNode\': argument 2 is not of type \'NoiseSourceOptions\'');
+      }
+
+      if (options && options.type !== undefined) {
+        if (!kNoiseTypes.includes(options.type)) {
+          throw new TypeError(`Failed to construct 'NoiseSourceNode': Failed to read the 'type' property from NoiseSourceOptions: The provided value '${options.type}' is not a valid enum value of type NoiseType`);
+        }
+
+        parsedOptions.type = options.type;
+      } else {
+        parsedOptions.type = 'white';
+      }
+
+      // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+      if (options && options.trackActualStartTime !== undefined) {
+        parsedOptions.trackActualStartTime = conversions['boolean'](options.trackActualStartTime);
+      } else {
+        parsedOptions.trackActualStartTime = false;
+      }
+
+      let napiObj;
+
+      try {
+        napiObj = new nativeBinding.NoiseSourceNode(context[kNapiObj], parsedOptions);
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      super(context, {
+        [kNapiObj]: napiObj,
+      });
+    }
+
+    get type() {
+      if (!(this instanceof NoiseSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'NoiseSourceNode\'');
+      }
+
+      return this[kNapiObj].type;
+    }
+
+    set type(value) {
+      if (!(this instanceof NoiseSourceNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'NoiseSourceNode\'');
+      }
+
+      if (!kNoiseTypes.includes(value)) {
+        console.warn(`Failed to set the 'type' property on 'NoiseSourceNode': Value '${value}' is not a valid 'NoiseType' enum value`);
+        return;
+      }
+
+      try {
+        this[kNapiObj].type = value;
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+    }
+  }
+
+  Object.defineProperties(NoiseSourceNode, {
+    length: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 1,
+    },
+  });
+
+  Object.defineProperties(NoiseSourceNode.prototype, {
+    [Symbol.toStringTag]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 'NoiseSourceNode',
+    },
+    type: kEnumerableProperty,
+  });
+
+  return NoiseSourceNode;
+};
@@ -10,23 +10,56 @@ const {
   isFunction,
   kEnumerableProperty,
 } = require('./lib/utils.js');
+const {
+  kOversampleFactors,
+  downsample,
+} = require('./lib/oversampling.js');
+const {
+  acquireRenderSlot,
+  releaseRenderSlot,
+} = require('./lib/render-pool.js');
 const {
   kNapiObj,
   kWorkletRelease,
+  kWorkletSetProcessBudget,
   kOnStateChange,
   kOnComplete,
   kCheckProcessorsCreated,
+  kTeardown,
 } = require('./lib/symbols.js');
+const {
+  registerContext,
+} = require('./lib/contexts.js');
+
+const kRenderQuantumSize = 128;
 
 module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
   class OfflineAudioContext extends jsExport.BaseAudioContext {
     #renderedBuffer = null;
+    #completeEvent = null;
+    #renderOversample = 1;
+    #strictAutomation = false;
+    // first call to startRendering, cf. closeAllContexts
+    #rendering = null;
+    // discarded before rendering by closeAllContexts
+    #discarded = false;
+    // the rendering has been suspended and not resumed yet, cf. kTeardown
+    #suspended = false;
+    #onsuspended = null;
+    // render quanta at which a suspension is scheduled, upstream only allows
+    // one suspension per render quantum
+    #suspendQuanta = new Set();
 
     constructor(...args) {
       if (arguments.length < 1) {
         throw new TypeError(`Failed to construct 'OfflineAudioContext': 1 argument required, but only ${arguments.length} present`);
       }
 
+      let masterLimiter = false;
+      let workletProcessBudget;
+      let renderOversample = 1;
+      let strictAutomation = false;
+
       // https://webaudio.github.io/web-audio-api/#dom-offlineaudiocontext-constructor-contextoptions-contextoptions
       if (arguments.length === 1) {
         const options = args[0];
@@ -52,6 +85,21 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
           options.length,
           options.sampleRate,
         ];
+
+        masterLimiter = conversions['boolean'](options.masterLimiter);
+        strictAutomation = conversions['boolean'](options.strictAutomation);
+        workletProcessBudget = options.workletProcessBudget;
+
+        if (options.renderOversample !== undefined) {
+          renderOversample = conversions['unsigned long'](options.renderOversample, {
+            enforceRange: true,
+            context: `Failed to construct 'OfflineAudioContext': Failed to read the 'renderOversample' property from OfflineContextOptions; The provided value (${options.renderOversample})`,
+          });
+
+          if (!kOversampleFactors.includes(renderOversample)) {
+            throw new DOMException(`Failed to construct 'OfflineAudioContext': The provided value (${renderOversample}) for 'renderOversample' is not supported, must be one of ${kOversampleFactors.join(', ')}`, 'NotSupportedError');
+          }
+        }
       }
 
       let [numberOfChannels, length, sampleRate] = args;
@@ -73,13 +121,32 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
       let napiObj;
 
       try {
-        napiObj = new nativeBinding.OfflineAudioContext(numberOfChannels, length, sampleRate);
+        // Non-spec: the whole graph is rendered at `renderOversample` times
+        // the requested sample rate
+        napiObj = new nativeBinding.OfflineAudioContext(
+          numberOfChannels,
+          length * renderOversample,
+          sampleRate * renderOversample,
+        );
       } catch (err) {
         throwSanitizedError(err);
       }
 
       super({ [kNapiObj]: napiObj });
 
+      this.#renderOversample = renderOversample;
+      this.#strictAutomation = strictAutomation;
+
+      // Non-spec: brickwall limiter applied just before the destination
+      if (masterLimiter) {
+        this.destination[kNapiObj].setLimiter(true);
+      }
+
+      // Non-spec: CPU budget of the AudioWorkletProcessors
+      if (workletProcessBudget !== undefined) {
+        this.audioWorklet[kWorkletSetProcessBudget](workletProcessBudget, 'OfflineAudioContext');
+      }
+
       // Add function to Napi object to bridge from Rust events to JS EventTarget
       // They will be effectively registered on rust side when `startRendering` is called
       this[kNapiObj][kOnStateChange] = (function(_err, rawEvent) {
@@ -91,17 +158,69 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
       this[kNapiObj][kOnComplete] = (function(err, rawEvent) {
         // workaround the fact that the oncomplete event is triggered before
         // startRendering fulfills and that we want to return the exact same instance
-        this.#renderedBuffer = new jsExport.AudioBuffer({ [kNapiObj]: rawEvent.renderedBuffer });
+        this.#renderedBuffer = this.#downsampleRenderedBuffer(rawEvent.renderedBuffer);
 
-        const event = new jsExport.OfflineAudioCompletionEvent(rawEvent.type, {
+        // the event is dispatched once startRendering fulfills, cf. #startRendering
+        this.#completeEvent = new jsExport.OfflineAudioCompletionEvent(rawEvent.type, {
           renderedBuffer: this.#renderedBuffer,
         });
-
-        // delay event propagation to next tick that it is executed after startRendering fulfills
-        setImmediate(() => {
-          propagateEvent(this, event);
-        }, 0);
       }).bind(this);
+
+      registerContext(this);
+    }
+
+    // Non-spec: creates a context matching the number of channels and sample
+    // rate of `buffer`, with a length of `buffer.length` plus `tailSeconds`
+    // worth of frames, e.g. to bounce a buffer through a reverb. The other
+    // options are given to the constructor as is, cf. OfflineAudioContextOptions
+    static forBuffer(buffer, options = {}) {
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'forBuffer' on 'OfflineAudioContext': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (!(buffer instanceof jsExport.AudioBuffer)) {
+        throw new TypeError(`Failed to execute 'forBuffer' on 'OfflineAudioContext': parameter 1 is not of type 'AudioBuffer'`);
+      }
+
+      if (options === null || typeof options !== 'object') {
+        throw new TypeError(`Failed to execute 'forBuffer' on 'OfflineAudioContext': parameter 2 is not of type 'object'`);
+      }
+
+      const { tailSeconds = 0, ...contextOptions } = options;
+
+      const tail = conversions['double'](tailSeconds, {
+        context: `Failed to execute 'forBuffer' on 'OfflineAudioContext': Failed to read the 'tailSeconds' property: The provided value (${tailSeconds})`,
+      });
+
+      if (tail < 0) {
+        throw new RangeError(`Failed to execute 'forBuffer' on 'OfflineAudioContext': The provided value for 'tailSeconds' (${tail}) must be positive`);
+      }
+
+      return new OfflineAudioContext({
+        ...contextOptions,
+        numberOfChannels: buffer.numberOfChannels,
+        length: buffer.length + Math.ceil(tail * buffer.sampleRate),
+        sampleRate: buffer.sampleRate,
+      });
+    }
+
+    // Non-spec: a context discarded by closeAllContexts is closed
+    get state() {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      return this.#discarded ? 'closed' : super.state;
+    }
+
+    // Non-spec: overrides BaseAudioContext.sampleRate to hide the internal
+    // rate used when `renderOversample` is greater than 1
+    get sampleRate() {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      return this[kNapiObj].sampleRate / this.#renderOversample;
     }
 
     get length() {
@@ -109,7 +228,41 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
         throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
       }
 
-      return this[kNapiObj].length;
+      return this[kNapiObj].length / this.#renderOversample;
+    }
+
+    // Non-spec
+    get renderOversample() {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      return this.#renderOversample;
+    }
+
+    // Non-spec: automation events ending after the end of the rendering throw
+    // a RangeError, cf. AudioParam
+    get strictAutomation() {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      return this.#strictAutomation;
+    }
+
+    // Non-spec: cf. AudioContext.getBackendInfo, an offline context is not
+    // bound to any backend and renders by blocks of one render quantum
+    getBackendInfo() {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      return {
+        hostApi: 'offline',
+        deviceName: null,
+        sampleRate: this.sampleRate,
+        bufferSize: kRenderQuantumSize,
+      };
     }
 
     get oncomplete() {
@@ -135,28 +288,181 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
         throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
       }
 
+      if (this.#discarded) {
+        throw new DOMException(`Failed to execute 'startRendering' on 'OfflineAudioContext': The context has been closed by closeAllContexts`, 'InvalidStateError');
+      }
+
+      const rendering = this.#startRendering();
+
+      if (this.#rendering === null) {
+        this.#rendering = rendering;
+      }
+
+      return rendering;
+    }
+
+    async #startRendering() {
       // ensure all AudioWorkletProcessor have finished their instanciation
       await this.audioWorklet[kCheckProcessorsCreated]();
 
       let nativeAudioBuffer;
+      // Non-spec: wait for a free slot if none is available, cf.
+      // setOfflineRenderConcurrency
+      const slot = acquireRenderSlot();
+
+      if (slot !== null) {
+        await slot;
+      }
 
       try {
         nativeAudioBuffer = await this[kNapiObj].startRendering();
       } catch (err) {
         throwSanitizedError(err);
+      } finally {
+        releaseRenderSlot();
       }
 
       // release audio worklets
       await this.audioWorklet[kWorkletRelease]();
 
+      // delay event propagation to the next macrotask so that it is executed
+      // after startRendering fulfills
+      const event = this.#completeEvent;
+
+      if (event !== null) {
+        this.#completeEvent = null;
+        setImmediate(() => propagateEvent(this, event));
+      }
+
       return this.#renderedBuffer;
     }
 
+    // Non-spec: starts the rendering and yields `{ framesRendered, total }`
+    // each `step` frames (rounded to the render quantum), the last iteration
+    // also yields the `renderedBuffer`, e.g.:
+    //
+    // for await (const { framesRendered, total } of context.renderProgress()) {}
+    //
+    // The rendering is suspended while the loop body runs. Breaking out of the
+    // loop cancels the rendering: nothing is rejected, `startRendering`
+    // fulfills with null, the context stays 'suspended' and its resources are
+    // released, pending `suspend` promises fulfill as if the suspension was
+    // reached.
+    async *renderProgress(options = {}) {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      if (options === null || typeof options !== 'object') {
+        throw new TypeError(`Failed to execute 'renderProgress' on 'OfflineAudioContext': parameter 1 is not of type 'object'`);
+      }
+
+      const total = this.length;
+      let step = Math.ceil(total / 10 / kRenderQuantumSize) * kRenderQuantumSize;
+
+      if (options.step !== undefined) {
+        step = conversions['unsigned long'](options.step, {
+          enforceRange: true,
+          context: `Failed to execute 'renderProgress' on 'OfflineAudioContext': Failed to read the 'step' property: The provided value (${options.step})`,
+        });
+      }
+
+      step = Math.max(kRenderQuantumSize, step);
+
+      // suspensions can't be scheduled once the rendering is started
+      if (this.#rendering !== null || this.#discarded) {
+        throw new DOMException(`Failed to execute 'renderProgress' on 'OfflineAudioContext': The rendering has already been started`, 'InvalidStateError');
+      }
+
+      for (let frame = step; frame < total; frame += step) {
+        if (this.#suspendQuanta.has(this.#suspendQuantum(this.#suspendTimeAtFrame(frame)))) {
+          throw new DOMException(`Failed to execute 'renderProgress' on 'OfflineAudioContext': A suspension is already scheduled at frame ${frame}`, 'InvalidStateError');
+        }
+      }
+
+      // the promises are awaited in order, the ones left behind when a step
+      // rejects or when breaking out of the loop must not reject unhandled
+      const suspensions = [];
+
+      for (let frame = step; frame < total; frame += step) {
+        const suspended = this.suspendAtFrame(frame);
+        suspended.catch(() => {});
+        suspensions.push(suspended);
+      }
+
+      const rendering = this.startRendering();
+      rendering.catch(() => {});
+      let completed = false;
+
+      try {
+        for (const suspended of suspensions) {
+          await suspended;
+
+          const framesRendered = Math.round(this.currentTime * this.sampleRate);
+          yield { framesRendered, total, renderedBuffer: null };
+
+          await this.resume();
+        }
+
+        const renderedBuffer = await rendering;
+        completed = true;
+
+        yield { framesRendered: total, total, renderedBuffer };
+      } finally {
+        if (!completed) {
+          this.#suspended = false;
+          // the remaining suspensions fulfill without suspending the context
+          this[kNapiObj].cancelRendering();
+          // the worklets and the render slot are released by startRendering,
+          // the error interrupting the loop, if any, is the one thrown
+          await rendering.catch(() => {});
+        }
+      }
+    }
+
+    // Non-spec: cf. closeAllContexts, a context which rendering is not started
+    // is discarded, an ongoing rendering is resumed each time it is suspended
+    // until it is finished
+    async [kTeardown]() {
+      if (this.#rendering === null) {
+        if (!this.#discarded) {
+          this.#discarded = true;
+          await this.audioWorklet[kWorkletRelease]();
+        }
+
+        return;
+      }
+
+      // the state of the context can't be used as it is also 'suspended'
+      // before the rendering starts
+      const resumeIfSuspended = () => {
+        if (this.#suspended) {
+          this.#suspended = false;
+          this[kNapiObj].resume().catch(() => {});
+        }
+      };
+
+      // let the handlers of the suspend promise run first, as they may resume
+      // the rendering themselves
+      this.#onsuspended = () => setImmediate(resumeIfSuspended);
+      resumeIfSuspended();
+
+      try {
+        await this.#rendering;
+      } catch (err) {
+        // errors are reported by startRendering
+      } finally {
+        this.#onsuspended = null;
+      }
+    }
+
     async resume() {
       if (!(this instanceof OfflineAudioContext)) {
         throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
       }
 
+      this.#suspended = false;
+
       try {
         await this[kNapiObj].resume();
       } catch (err) {
@@ -177,11 +483,104 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
         context: `Failed to execute 'suspend' on 'OfflineAudioContext': argument 1`,
       });
 
+      await this.#suspend(suspendTime);
+    }
+
+    // Non-spec: suspend the rendering for sample accurate manipulation of the
+    // graph at the given frame. As the graph is rendered by blocks of 128 frames,
+    // the rendering is suspended at the beginning of the render quantum that
+    // contains `frame` (while `suspend` rounds up to the next one), i.e. before
+    // `frame` is rendered. Hence events scheduled at `frame / sampleRate` while
+    // suspended are applied exactly at `frame`.
+    async suspendAtFrame(frame) {
+      if (!(this instanceof OfflineAudioContext)) {
+        throw new TypeError(`Invalid Invocation: Value of 'this' must be of type 'OfflineAudioContext'`);
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'suspendAtFrame' on 'OfflineAudioContext': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      frame = conversions['unsigned long'](frame, {
+        enforceRange: true,
+        context: `Failed to execute 'suspendAtFrame' on 'OfflineAudioContext': argument 1`,
+      });
+
+      if (frame >= this.length) {
+        throw new RangeError(`Failed to execute 'suspendAtFrame' on 'OfflineAudioContext': frame (${frame}) must be less than context length (${this.length})`);
+      }
+
+      await this.#suspend(this.#suspendTimeAtFrame(frame));
+    }
+
+    // time given to upstream to suspend the rendering before `frame`
+    #suspendTimeAtFrame(frame) {
+      // render quanta are defined at the internal (i.e. oversampled) rate
+      const internalFrame = frame * this.#renderOversample;
+      const quantumFrame = Math.floor(internalFrame / kRenderQuantumSize) * kRenderQuantumSize;
+      // upstream quantizes the suspend time by rounding it up to the next render
+      // quantum, target the middle of the previous render quantum to be robust
+      // to floating point errors
+      return quantumFrame === 0
+        ? 0
+        : (quantumFrame - kRenderQuantumSize / 2) / this[kNapiObj].sampleRate;
+    }
+
+    // render quantum at which upstream suspends the rendering for `suspendTime`
+    #suspendQuantum(suspendTime) {
+      return Math.ceil(suspendTime * this[kNapiObj].sampleRate / kRenderQuantumSize);
+    }
+
+    async #suspend(suspendTime) {
+      const quantum = this.#suspendQuantum(suspendTime);
+
+      if (this.#suspendQuanta.has(quantum)) {
+        throw new DOMException(`Failed to execute 'suspend' on 'OfflineAudioContext': Cannot suspend multiple times at the same render quantum`, 'InvalidStateError');
+      }
+
+      this.#suspendQuanta.add(quantum);
+
+      let suspended;
+
       try {
-        await this[kNapiObj].suspend(suspendTime);
+        suspended = await this[kNapiObj].suspend(suspendTime);
       } catch (err) {
         throwSanitizedError(err);
       }
+
+      // the rendering has been cancelled, cf. renderProgress
+      if (!suspended) {
+        return;
+      }
+
+      this.#suspended = true;
+
+      if (this.#onsuspended !== null) {
+        this.#onsuspended();
+      }
+    }
+
+    // Non-spec: bring the rendered buffer back to the context sample rate
+    // when the graph has been oversampled
+    #downsampleRenderedBuffer(napiBuffer) {
+      const renderedBuffer = new jsExport.AudioBuffer({ [kNapiObj]: napiBuffer });
+
+      if (this.#renderOversample === 1) {
+        return renderedBuffer;
+      }
+
+      const buffer = new jsExport.AudioBuffer({
+        numberOfChannels: renderedBuffer.numberOfChannels,
+        length: this.length,
+        sampleRate: this.sampleRate,
+      });
+
+      for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+        const data = downsample(renderedBuffer.getChannelData(channel), this.#renderOversample, buffer.length);
+        buffer.copyToChannel(data, channel);
+      }
+
+      return buffer;
     }
   }
 
@@ -193,6 +592,7 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
       configurable: true,
       value: 1,
     },
+    forBuffer: kEnumerableProperty,
   });
 
   Object.defineProperties(OfflineAudioContext.prototype, {
@@ -204,11 +604,18 @@ module.exports = function patchOfflineAudioContext(jsExport, nativeBinding) {
       value: 'OfflineAudioContext',
     },
 
+    sampleRate: kEnumerableProperty,
     length: kEnumerableProperty,
+    state: kEnumerableProperty,
+    renderOversample: kEnumerableProperty,
+    strictAutomation: kEnumerableProperty,
+    getBackendInfo: kEnumerableProperty,
     oncomplete: kEnumerableProperty,
     startRendering: kEnumerableProperty,
     resume: kEnumerableProperty,
     suspend: kEnumerableProperty,
+    suspendAtFrame: kEnumerableProperty,
+    renderProgress: kEnumerableProperty,
   });
 
   return OfflineAudioContext;
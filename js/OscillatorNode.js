@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,6 +34,8 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
 
@@ -106,6 +110,13 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.type = 'custom';
       }
 
+      // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+      if (options && options.trackActualStartTime !== undefined) {
+        parsedOptions.trackActualStartTime = conversions['boolean'](options.trackActualStartTime);
+      } else {
+        parsedOptions.trackActualStartTime = false;
+      }
+
       if (options && options.channelCount !== undefined) {
         parsedOptions.channelCount = conversions['unsigned long'](options.channelCount, {
           enforceRange: true,
@@ -139,9 +150,12 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#frequency = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].frequency,
+        context,
+        nyquist: true,
       });
       this.#detune = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].detune,
+        context,
       });
     }
 
@@ -218,6 +232,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['frequency', 'detune']),
+    },
   });
 
   Object.defineProperties(OscillatorNode.prototype, {
@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,6 +34,8 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
 
@@ -217,21 +221,27 @@ module.exports = (jsExport, nativeBinding) => {
 
       this.#positionX = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].positionX,
+        context,
       });
       this.#positionY = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].positionY,
+        context,
       });
       this.#positionZ = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].positionZ,
+        context,
       });
       this.#orientationX = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].orientationX,
+        context,
       });
       this.#orientationY = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].orientationY,
+        context,
       });
       this.#orientationZ = new jsExport.AudioParam({
         [kNapiObj]: this[kNapiObj].orientationZ,
+        context,
       });
     }
 
@@ -543,6 +553,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze(['positionX', 'positionY', 'positionZ', 'orientationX', 'orientationY', 'orientationZ']),
+    },
   });
 
   Object.defineProperties(PannerNode.prototype, {
@@ -3,7 +3,16 @@ const conversions = require('webidl-conversions');
 const { throwSanitizedError } = require('./lib/errors.js');
 const { toSanitizedSequence } = require('./lib/cast.js');
 const { kNapiObj } = require('./lib/symbols.js');
-const { kHiddenProperty } = require('./lib/utils.js');
+const { kEnumerableProperty, kHiddenProperty } = require('./lib/utils.js');
+
+// Non-spec: Fourier coefficients of the classic waveforms, k being the index of
+// the partial, cf. https://webaudio.github.io/web-audio-api/#oscillator-coefficients
+const kWaveforms = {
+  sine: k => k === 1 ? 1 : 0,
+  square: k => 2 / (k * Math.PI) * (1 - Math.pow(-1, k)),
+  sawtooth: k => Math.pow(-1, k + 1) * 2 / (k * Math.PI),
+  triangle: k => 8 * Math.sin(k * Math.PI / 2) / Math.pow(Math.PI * k, 2),
+};
 
 module.exports = (jsExport, nativeBinding) => {
   class PeriodicWave {
@@ -53,6 +62,49 @@ module.exports = (jsExport, nativeBinding) => {
         throwSanitizedError(err);
       }
     }
+
+    // Non-spec: band-limited PeriodicWave of the given classic waveform, i.e. only
+    // made of its first `partials` harmonics (default to 64)
+    static fromWaveform(context, type, options = {}) {
+      if (arguments.length < 2) {
+        throw new TypeError(`Failed to execute 'fromWaveform' on 'PeriodicWave': 2 arguments required, but only ${arguments.length} present`);
+      }
+
+      if (!Object.hasOwn(kWaveforms, type)) {
+        throw new TypeError(`Failed to execute 'fromWaveform' on 'PeriodicWave': The provided value '${type}' is not a valid waveform, should be one of ${Object.keys(kWaveforms).map(name => `'${name}'`).join(', ')}`);
+      }
+
+      if (typeof options !== 'object' || options === null) {
+        throw new TypeError(`Failed to execute 'fromWaveform' on 'PeriodicWave': Options must be an object`);
+      }
+
+      let partials = 64;
+
+      if (options.partials !== undefined) {
+        partials = conversions['unsigned long'](options.partials, {
+          enforceRange: true,
+          context: `Failed to execute 'fromWaveform' on 'PeriodicWave': Failed to read the 'partials' property from options: The provided value (${options.partials})`,
+        });
+
+        if (partials < 1) {
+          throw new RangeError(`Failed to execute 'fromWaveform' on 'PeriodicWave': The provided value (${partials}) for 'partials' must be greater than or equal to 1`);
+        }
+      }
+
+      const real = new Float32Array(partials + 1);
+      const imag = new Float32Array(partials + 1);
+      const coefficient = kWaveforms[type];
+
+      for (let k = 1; k <= partials; k++) {
+        imag[k] = coefficient(k);
+      }
+
+      return new PeriodicWave(context, {
+        real,
+        imag,
+        disableNormalization: options.disableNormalization,
+      });
+    }
   }
 
   Object.defineProperties(PeriodicWave, {
@@ -63,6 +115,7 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    fromWaveform: kEnumerableProperty,
   });
 
   Object.defineProperties(PeriodicWave.prototype, {
@@ -0,0 +1,381 @@
+const conversions = require('webidl-conversions');
+
+const {
+  throwSanitizedError,
+} = require('./lib/errors.js');
+const {
+  kEnumerableProperty,
+} = require('./lib/utils.js');
+const {
+  kNapiObj,
+} = require('./lib/symbols.js');
+
+const AudioNode = require('./AudioNode.js');
+
+// duration of the fade out of a stolen voice, so that it does not click
+const kStealFadeDuration = 0.005;
+
+// Non-spec: plays an AudioBuffer each time `trigger` or `noteOn` is called. As
+// a stopped AudioBufferSourceNode can't be restarted, each playback (voice)
+// goes through a new source node, which is released once it has ended.
+// Playbacks can overlap and are summed into the output of the sampler.
+//
+// Notes are played through their own gain and stereo panner, the buffer is
+// pitched relatively to `rootPitch` (MIDI note number of the buffer). At most
+// `maxVoices` voices play at the same time, the oldest voice is stolen to make
+// room for a new one, i.e. it is quickly faded out.
+module.exports = (jsExport, nativeBinding) => {
+  class Sampler extends AudioNode {
+    #buffer = null;
+    #maxVoices = 32;
+    #rootPitch = 60;
+    // voices started and not yet ended, in start order, each voice is a list
+    // of nodes, the source node first and the envelope gain last
+    #voices = new Set();
+    // voices whose source has been stopped, a source can only be stopped once
+    #stoppedVoices = new WeakSet();
+    // voices started by noteOn, by pitch
+    #notes = new Map();
+
+    constructor(context, options) {
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to construct 'Sampler': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (!(context instanceof jsExport.BaseAudioContext)) {
+        throw new TypeError(`Failed to construct 'Sampler': argument 1 is not of type BaseAudioContext`);
+      }
+
+      if (options && typeof options !== 'object') {
+        throw new TypeError('Failed to construct \'Sampler\': argument 2 is not of type \'SamplerOptions\'');
+      }
+
+      let buffer = null;
+
+      if (options && options.buffer !== undefined && options.buffer !== null) {
+        if (!(options.buffer instanceof jsExport.AudioBuffer)) {
+          throw new TypeError('Failed to construct \'Sampler\': Failed to read the \'buffer\' property from SamplerOptions: The provided value cannot be converted to \'AudioBuffer\'');
+        }
+
+        buffer = options.buffer;
+      }
+
+      let maxVoices = 32;
+
+      if (options && options.maxVoices !== undefined) {
+        maxVoices = conversions['unsigned long'](options.maxVoices, {
+          enforceRange: true,
+          context: `Failed to construct 'Sampler': Failed to read the 'maxVoices' property from SamplerOptions: The provided value (${options.maxVoices})`,
+        });
+
+        if (maxVoices === 0) {
+          throw new DOMException(`Failed to construct 'Sampler': maxVoices (0) must be greater than 0`, 'NotSupportedError');
+        }
+      }
+
+      let rootPitch = 60;
+
+      if (options && options.rootPitch !== undefined) {
+        rootPitch = conversions['double'](options.rootPitch, {
+          context: `Failed to construct 'Sampler': Failed to read the 'rootPitch' property from SamplerOptions: The provided value (${options.rootPitch})`,
+        });
+      }
+
+      let napiObj;
+
+      // the output of the sampler, all voices are connected to it
+      try {
+        napiObj = new nativeBinding.GainNode(context[kNapiObj], { gain: 1 });
+      } catch (err) {
+        throwSanitizedError(err);
+      }
+
+      super(context, {
+        [kNapiObj]: napiObj,
+      });
+
+      this.#buffer = buffer;
+      this.#maxVoices = maxVoices;
+      this.#rootPitch = rootPitch;
+    }
+
+    get buffer() {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      return this.#buffer;
+    }
+
+    // Applies to the next triggers, ongoing playbacks are not affected
+    set buffer(value) {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      if (value !== null && !(value instanceof jsExport.AudioBuffer)) {
+        throw new TypeError('Failed to set the \'buffer\' property on \'Sampler\': Failed to convert value to \'AudioBuffer\'');
+      }
+
+      this.#buffer = value;
+    }
+
+    // Number of playbacks started and not yet ended
+    get activeVoices() {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      return this.#voices.size;
+    }
+
+    get maxVoices() {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      return this.#maxVoices;
+    }
+
+    get rootPitch() {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      return this.#rootPitch;
+    }
+
+    // Plays the buffer from `offset` at time `when`, for `duration` seconds if
+    // given, cf. AudioBufferSourceNode::start
+    trigger(when = 0, offset = 0, duration = undefined) {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      if (this.#buffer === null) {
+        throw new DOMException(`Failed to execute 'trigger' on 'Sampler': No buffer has been set`, 'InvalidStateError');
+      }
+
+      when = conversions['double'](when, {
+        context: `Failed to execute 'trigger' on 'Sampler': Parameter 1`,
+      });
+
+      offset = conversions['double'](offset, {
+        context: `Failed to execute 'trigger' on 'Sampler': Parameter 2`,
+      });
+
+      if (duration !== undefined) {
+        duration = conversions['double'](duration, {
+          context: `Failed to execute 'trigger' on 'Sampler': Parameter 3`,
+        });
+      }
+
+      const source = new jsExport.AudioBufferSourceNode(this.context, {
+        buffer: this.#buffer,
+      });
+
+      if (duration === undefined) {
+        source.start(when, offset);
+      } else {
+        source.start(when, offset, duration);
+      }
+
+      this.#addVoice(when, [source]);
+    }
+
+    // Plays the buffer at time `when`, pitched from `rootPitch` to `pitch`
+    // (MIDI note number) with a gain of `velocity / 127`, and panned by `pan`
+    // in [-1, 1]. The voice plays until the end of the buffer or until
+    // `noteOff` is called with the same pitch.
+    noteOn(pitch, velocity = 127, when = 0, pan = 0) {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'noteOn' on 'Sampler': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      if (this.#buffer === null) {
+        throw new DOMException(`Failed to execute 'noteOn' on 'Sampler': No buffer has been set`, 'InvalidStateError');
+      }
+
+      pitch = conversions['double'](pitch, {
+        context: `Failed to execute 'noteOn' on 'Sampler': Parameter 1`,
+      });
+
+      velocity = conversions['double'](velocity, {
+        context: `Failed to execute 'noteOn' on 'Sampler': Parameter 2`,
+      });
+
+      if (velocity < 0 || velocity > 127) {
+        throw new RangeError(`Failed to execute 'noteOn' on 'Sampler': Parameter 2 (${velocity}) is outside of the range [0, 127]`);
+      }
+
+      when = conversions['double'](when, {
+        context: `Failed to execute 'noteOn' on 'Sampler': Parameter 3`,
+      });
+
+      pan = conversions['float'](pan, {
+        context: `Failed to execute 'noteOn' on 'Sampler': Parameter 4`,
+      });
+
+      const source = new jsExport.AudioBufferSourceNode(this.context, {
+        buffer: this.#buffer,
+        playbackRate: Math.pow(2, (pitch - this.#rootPitch) / 12),
+      });
+      source.start(when);
+
+      const voice = this.#addVoice(when, [
+        source,
+        new jsExport.GainNode(this.context, { gain: velocity / 127 }),
+        new jsExport.StereoPannerNode(this.context, { pan }),
+      ]);
+
+      if (!this.#notes.has(pitch)) {
+        this.#notes.set(pitch, new Set());
+      }
+
+      const notes = this.#notes.get(pitch);
+      notes.add(voice);
+      voice[0].addEventListener('ended', () => {
+        notes.delete(voice);
+
+        if (notes.size === 0 && this.#notes.get(pitch) === notes) {
+          this.#notes.delete(pitch);
+        }
+      }, { once: true });
+    }
+
+    // Stops the voices started by `noteOn` with `pitch` at time `when`
+    noteOff(pitch, when = 0) {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      if (arguments.length < 1) {
+        throw new TypeError(`Failed to execute 'noteOff' on 'Sampler': 1 argument required, but only ${arguments.length} present`);
+      }
+
+      pitch = conversions['double'](pitch, {
+        context: `Failed to execute 'noteOff' on 'Sampler': Parameter 1`,
+      });
+
+      when = conversions['double'](when, {
+        context: `Failed to execute 'noteOff' on 'Sampler': Parameter 2`,
+      });
+
+      const notes = this.#notes.get(pitch);
+
+      if (notes !== undefined) {
+        this.#notes.delete(pitch);
+
+        // the voices are released once they have ended
+        for (const voice of notes) {
+          this.#stopVoice(voice, when);
+        }
+      }
+    }
+
+    // Stops all ongoing playbacks at time `when`
+    stopAll(when = 0) {
+      if (!(this instanceof Sampler)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'Sampler\'');
+      }
+
+      when = conversions['double'](when, {
+        context: `Failed to execute 'stopAll' on 'Sampler': Parameter 1`,
+      });
+
+      for (const voice of this.#voices) {
+        this.#stopVoice(voice, when);
+      }
+    }
+
+    #stopVoice(voice, when) {
+      if (this.#stoppedVoices.has(voice)) {
+        return;
+      }
+
+      this.#stoppedVoices.add(voice);
+      voice[0].stop(when);
+    }
+
+    // Fades out the voice from `when`, the voice is not counted in the active
+    // voices nor stopped by `noteOff` anymore
+    #stealVoice(voice, when) {
+      this.#voices.delete(voice);
+
+      for (const [pitch, notes] of this.#notes) {
+        if (notes.delete(voice) && notes.size === 0) {
+          this.#notes.delete(pitch);
+        }
+      }
+
+      const startTime = Math.max(when, this.context.currentTime);
+      const envelope = voice[voice.length - 1];
+
+      envelope.gain.setValueAtTime(1, startTime);
+      envelope.gain.linearRampToValueAtTime(0, startTime + kStealFadeDuration);
+      this.#stopVoice(voice, startTime + kStealFadeDuration);
+    }
+
+    // Chains the nodes of a started voice and its envelope into the output of
+    // the sampler, the voice is released once its source has ended. If
+    // `maxVoices` voices are already playing, the oldest one is stolen at `when`.
+    #addVoice(when, nodes) {
+      if (this.#voices.size >= this.#maxVoices) {
+        const [oldest] = this.#voices;
+        this.#stealVoice(oldest, when);
+      }
+
+      nodes = [...nodes, new jsExport.GainNode(this.context)];
+
+      // connect the napi objects directly so that the voices are not part of
+      // the graph of the context, cf. BaseAudioContext::exportGraph
+      nodes.forEach((node, index) => {
+        const next = index < nodes.length - 1 ? nodes[index + 1] : this;
+        node[kNapiObj].connect(next[kNapiObj], 0, 0);
+      });
+
+      nodes[0].addEventListener('ended', () => {
+        nodes.forEach(node => node.disconnect());
+        this.#voices.delete(nodes);
+      }, { once: true });
+
+      this.#voices.add(nodes);
+
+      return nodes;
+    }
+  }
+
+  Object.defineProperties(Sampler, {
+    length: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 1,
+    },
+  });
+
+  Object.defineProperties(Sampler.prototype, {
+    [Symbol.toStringTag]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: true,
+      value: 'Sampler',
+    },
+    buffer: kEnumerableProperty,
+    activeVoices: kEnumerableProperty,
+    maxVoices: kEnumerableProperty,
+    rootPitch: kEnumerableProperty,
+    trigger: kEnumerableProperty,
+    noteOn: kEnumerableProperty,
+    noteOff: kEnumerableProperty,
+    stopAll: kEnumerableProperty,
+  });
+
+  return Sampler;
+};
@@ -76,6 +76,21 @@ module.exports = (jsExport, nativeBinding) => {
         parsedOptions.numberOfOutputChannels = 2;
       }
 
+      // Non-spec: maximum number of audioprocess events waiting to be
+      // dispatched, the events that don't fit in the queue are dropped
+      // (and counted in `droppedEvents`) instead of blocking the audio thread.
+      // Defaults to 0, i.e. an unbounded queue. Only the audioprocess events
+      // are bounded: the other events of nodes and contexts, e.g. `ended` or
+      // `statechange`, are dispatched a few times at most and are never dropped.
+      if (options && options.eventQueueSize !== undefined) {
+        parsedOptions.eventQueueSize = conversions['unsigned long'](options.eventQueueSize, {
+          enforceRange: true,
+          context: `Failed to construct 'ScriptProcessorNode': Failed to read the 'eventQueueSize' property from ScriptProcessorNodeOptions: The provided value '${options.eventQueueSize}'`,
+        });
+      } else {
+        parsedOptions.eventQueueSize = 0;
+      }
+
       if (options && options.channelCount !== undefined) {
         parsedOptions.channelCount = conversions['unsigned long'](options.channelCount, {
           enforceRange: true,
@@ -129,6 +144,16 @@ module.exports = (jsExport, nativeBinding) => {
       return this[kNapiObj].bufferSize;
     }
 
+    // Non-spec: number of audioprocess events dropped because the event
+    // queue was full, cf. `eventQueueSize` option
+    get droppedEvents() {
+      if (!(this instanceof ScriptProcessorNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ScriptProcessorNode\'');
+      }
+
+      return this[kNapiObj].droppedEvents;
+    }
+
     get onaudioprocess() {
       if (!(this instanceof ScriptProcessorNode)) {
         throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'ScriptProcessorNode\'');
@@ -167,6 +192,7 @@ module.exports = (jsExport, nativeBinding) => {
       value: 'ScriptProcessorNode',
     },
     bufferSize: kEnumerableProperty,
+    droppedEvents: kEnumerableProperty,
     onaudioprocess: kEnumerableProperty,
 
   });
@@ -23,7 +23,9 @@ const {
   toSanitizedSequence,
 } = require('./lib/cast.js');
 const {
+  clampToCurrentTime,
   isFunction,
+  isIterable,
   kEnumerableProperty,
 } = require('./lib/utils.js');
 const {
@@ -32,13 +34,21 @@ const {
 const {
   kNapiObj,
   kAudioBuffer,
+  kScheduledSourceNodes,
+  kAudioParamNames,
 } = require('./lib/symbols.js');
 /* eslint-enable no-unused-vars */
+const {
+  isBypassed,
+  setBypass,
+} = require('./lib/bypass.js');
 
 const AudioNode = require('./AudioNode.js');
 
 module.exports = (jsExport, nativeBinding) => {
   class WaveShaperNode extends AudioNode {
+    // non spec, cf. curveLength
+    #curveLength = 0;
 
     constructor(context, options) {
 
@@ -110,6 +120,38 @@ module.exports = (jsExport, nativeBinding) => {
         [kNapiObj]: napiObj,
       });
 
+      if (parsedOptions.curve !== null) {
+        this.#curveLength = parsedOptions.curve.length;
+      }
+    }
+
+    // non spec
+    get bypass() {
+      if (!(this instanceof WaveShaperNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'WaveShaperNode\'');
+      }
+
+      return isBypassed(this);
+    }
+
+    // non spec
+    set bypass(value) {
+      if (!(this instanceof WaveShaperNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'WaveShaperNode\'');
+      }
+
+      value = conversions['boolean'](value);
+      // the dry / wet routing is created on first bypass, cf. lib/bypass.js
+      setBypass(this, value, jsExport.GainNode);
+    }
+
+    // non spec, length of the curve without copying its data to JS
+    get curveLength() {
+      if (!(this instanceof WaveShaperNode)) {
+        throw new TypeError('Invalid Invocation: Value of \'this\' must be of type \'WaveShaperNode\'');
+      }
+
+      return this.#curveLength;
     }
 
     get curve() {
@@ -137,6 +179,8 @@ module.exports = (jsExport, nativeBinding) => {
       } catch (err) {
         throwSanitizedError(err);
       }
+
+      this.#curveLength = value.length;
     }
 
     get oversample() {
@@ -174,6 +218,14 @@ module.exports = (jsExport, nativeBinding) => {
       configurable: true,
       value: 1,
     },
+    // non spec, names of the AudioParams of the node, cf. lib/graph.js
+    [kAudioParamNames]: {
+      __proto__: null,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+      value: Object.freeze([]),
+    },
   });
 
   Object.defineProperties(WaveShaperNode.prototype, {
@@ -187,6 +239,8 @@ module.exports = (jsExport, nativeBinding) => {
 
     curve: kEnumerableProperty,
     oversample: kEnumerableProperty,
+    bypass: kEnumerableProperty,
+    curveLength: kEnumerableProperty,
 
   });
 
@@ -0,0 +1,214 @@
+const { kNapiObj } = require('./symbols.js');
+
+// Non spec, window functions that can be applied by the AnalyserNode before
+// computing the FFT. 'blackman' is the window defined by the spec and is
+// computed natively, the others are computed on the JS side from the time
+// domain data, cf. https://webaudio.github.io/web-audio-api/#fft-windowing-and-smoothing-over-time
+const kWindowFunctions = ['blackman', 'hann', 'hamming', 'none'];
+
+exports.kWindowFunctions = kWindowFunctions;
+
+function createWindow(windowFunction, size) {
+  const window = new Float32Array(size);
+
+  for (let n = 0; n < size; n++) {
+    const phase = 2 * Math.PI * n / size;
+
+    switch (windowFunction) {
+      case 'hann':
+        window[n] = 0.5 - 0.5 * Math.cos(phase);
+        break;
+      case 'hamming':
+        window[n] = 0.54 - 0.46 * Math.cos(phase);
+        break;
+      case 'none':
+        window[n] = 1;
+        break;
+    }
+  }
+
+  return window;
+}
+
+// in place iterative radix-2 FFT, `size` must be a power of 2
+function fft(real, imag) {
+  const size = real.length;
+
+  for (let i = 1, j = 0; i < size; i++) {
+    let bit = size >> 1;
+
+    for (; j & bit; bit >>= 1) {
+      j ^= bit;
+    }
+
+    j ^= bit;
+
+    if (i < j) {
+      [real[i], real[j]] = [real[j], real[i]];
+      [imag[i], imag[j]] = [imag[j], imag[i]];
+    }
+  }
+
+  for (let len = 2; len <= size; len <<= 1) {
+    const angle = -2 * Math.PI / len;
+    const wReal = Math.cos(angle);
+    const wImag = Math.sin(angle);
+
+    for (let i = 0; i < size; i += len) {
+      let curReal = 1;
+      let curImag = 0;
+
+      for (let j = 0; j < len / 2; j++) {
+        const aIndex = i + j;
+        const bIndex = i + j + len / 2;
+        const bReal = real[bIndex] * curReal - imag[bIndex] * curImag;
+        const bImag = real[bIndex] * curImag + imag[bIndex] * curReal;
+
+        real[bIndex] = real[aIndex] - bReal;
+        imag[bIndex] = imag[aIndex] - bImag;
+        real[aIndex] += bReal;
+        imag[aIndex] += bImag;
+
+        const nextReal = curReal * wReal - curImag * wImag;
+        curImag = curReal * wImag + curImag * wReal;
+        curReal = nextReal;
+      }
+    }
+  }
+}
+
+// Computes the frequency data of an AnalyserNode with a custom window function,
+// following the same steps as the native implementation
+class WindowedSpectrum {
+  #windowFunction = 'blackman';
+  #window = null;
+  #timeDomain = null;
+  #real = null;
+  #imag = null;
+  // smoothed magnitudes of the previous block
+  #magnitudes = null;
+
+  get windowFunction() {
+    return this.#windowFunction;
+  }
+
+  set windowFunction(value) {
+    this.#windowFunction = value;
+    this.#window = null;
+    this.#magnitudes = null;
+  }
+
+  getFloatFrequencyData(napiNode, array) {
+    const magnitudes = this.#computeMagnitudes(napiNode);
+    const length = Math.min(array.length, magnitudes.length);
+
+    for (let i = 0; i < length; i++) {
+      array[i] = 20 * Math.log10(magnitudes[i]);
+    }
+  }
+
+  getByteFrequencyData(napiNode, array) {
+    const magnitudes = this.#computeMagnitudes(napiNode);
+    const length = Math.min(array.length, magnitudes.length);
+    const minDecibels = napiNode.minDecibels;
+    const maxDecibels = napiNode.maxDecibels;
+
+    for (let i = 0; i < length; i++) {
+      const db = 20 * Math.log10(magnitudes[i]);
+      const value = Math.floor(255 / (maxDecibels - minDecibels) * (db - minDecibels));
+      // NaN is handled as 0, i.e. if magnitude is 0
+      array[i] = Math.min(255, Math.max(0, value || 0));
+    }
+  }
+
+  #computeMagnitudes(napiNode) {
+    const fftSize = napiNode.fftSize;
+
+    if (this.#window === null || this.#window.length !== fftSize) {
+      this.#window = createWindow(this.#windowFunction, fftSize);
+      this.#timeDomain = new Float32Array(fftSize);
+      this.#real = new Float64Array(fftSize);
+      this.#imag = new Float64Array(fftSize);
+      this.#magnitudes = new Float32Array(fftSize / 2);
+    }
+
+    napiNode.getFloatTimeDomainData(this.#timeDomain);
+
+    for (let i = 0; i < fftSize; i++) {
+      this.#real[i] = this.#timeDomain[i] * this.#window[i];
+      this.#imag[i] = 0;
+    }
+
+    fft(this.#real, this.#imag);
+
+    const smoothingTimeConstant = napiNode.smoothingTimeConstant;
+
+    for (let i = 0; i < this.#magnitudes.length; i++) {
+      const magnitude = Math.hypot(this.#real[i], this.#imag[i]) / fftSize;
+      const smoothed = smoothingTimeConstant * this.#magnitudes[i]
+        + (1 - smoothingTimeConstant) * magnitude;
+      // prevent NaN to propagate into the smoothed values
+      this.#magnitudes[i] = Number.isFinite(smoothed) ? smoothed : 0;
+    }
+
+    return this.#magnitudes;
+  }
+}
+
+exports.WindowedSpectrum = WindowedSpectrum;
+
+// Non spec, routing of an AnalyserNode created with `channelMode: 'perChannel'`,
+// its input is split into one internal AnalyserNode per channel:
+//
+// input ─┬─────────────> node
+//        └─> splitter ─┬─> analysers[0]
+//                      ├─> ...
+//                      └─> analysers[numberOfChannels - 1]
+//
+// Connections to the node are made through `input`, cf. AudioNode::connect
+exports.createChannelAnalysers = function createChannelAnalysers(node, numberOfChannels, jsExport) {
+  const context = node.context;
+  const input = new jsExport.GainNode(context);
+  const splitter = new jsExport.ChannelSplitterNode(context, { numberOfOutputs: numberOfChannels });
+  const analysers = [];
+
+  // connect the napi objects directly as AudioNode::connect resolves the routing
+  input[kNapiObj].connect(node[kNapiObj], 0, 0);
+  input[kNapiObj].connect(splitter[kNapiObj], 0, 0);
+
+  for (let i = 0; i < numberOfChannels; i++) {
+    const analyser = new jsExport.AnalyserNode(context);
+    splitter[kNapiObj].connect(analyser[kNapiObj], i, 0);
+    analysers.push(analyser);
+  }
+
+  return { input, splitter, analysers };
+};
+
+// Returns the internal analyser of the given channel, configured as `node`
+exports.getChannelAnalyser = function getChannelAnalyser(routing, node, channelNumber) {
+  const analyser = routing.analysers[channelNumber];
+
+  if (analyser.fftSize !== node.fftSize) {
+    analyser.fftSize = node.fftSize;
+  }
+
+  if (analyser.smoothingTimeConstant !== node.smoothingTimeConstant) {
+    analyser.smoothingTimeConstant = node.smoothingTimeConstant;
+  }
+
+  // minDecibels must stay lower than maxDecibels in between
+  if (node.minDecibels < analyser.maxDecibels) {
+    analyser.minDecibels = node.minDecibels;
+    analyser.maxDecibels = node.maxDecibels;
+  } else {
+    analyser.maxDecibels = node.maxDecibels;
+    analyser.minDecibels = node.minDecibels;
+  }
+
+  if (analyser.windowFunction !== node.windowFunction) {
+    analyser.windowFunction = node.windowFunction;
+  }
+
+  return analyser;
+};
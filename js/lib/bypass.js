@@ -0,0 +1,88 @@
+const {
+  kBypassRouting,
+  kGraphNodes,
+  kNapiObj,
+  kRerouteInput,
+  kRerouteOutput,
+} = require('./symbols.js');
+const {
+  kHiddenProperty,
+} = require('./utils.js');
+
+// duration of the crossfade between the dry and wet signals, to prevent clicks
+const kBypassCrossfadeDuration = 0.005;
+
+// Route an effect node through a dry / wet crossfade, so that it can be
+// bypassed without touching the connections of the graph:
+//
+// input ─┬─> node ─> wet ─┬─> output
+//        └────────> dry ──┘
+//
+// Connections to and from the node are made through `input` and `output`,
+// cf. AudioNode::connect. The routing is only created when first needed, the
+// existing connections of the node are then moved to `input` and `output`.
+function getBypassRouting(node, GainNode) {
+  if (node[kBypassRouting] !== undefined) {
+    return node[kBypassRouting];
+  }
+
+  const input = new GainNode(node.context);
+  const wet = new GainNode(node.context);
+  const dry = new GainNode(node.context, { gain: 0 });
+  const output = new GainNode(node.context);
+
+  node[kRerouteOutput](output[kNapiObj]);
+
+  // connect the napi objects directly as AudioNode::connect resolves the routing
+  input[kNapiObj].connect(node[kNapiObj], 0, 0);
+  node[kNapiObj].connect(wet[kNapiObj], 0, 0);
+  wet[kNapiObj].connect(output[kNapiObj], 0, 0);
+  input[kNapiObj].connect(dry[kNapiObj], 0, 0);
+  dry[kNapiObj].connect(output[kNapiObj], 0, 0);
+
+  const routing = { input, output, wet, dry, bypass: false };
+
+  Object.defineProperty(node, kBypassRouting, {
+    value: routing,
+    ...kHiddenProperty,
+  });
+
+  // the nodes connected to the node are registered in the graph of the
+  // context, cf. AudioNode::connect
+  for (const ref of node.context[kGraphNodes]) {
+    const source = ref.deref();
+
+    if (source !== undefined) {
+      source[kRerouteInput](node, node[kNapiObj]);
+    }
+  }
+
+  return routing;
+}
+
+exports.getBypassRouting = getBypassRouting;
+
+exports.isBypassed = function isBypassed(node) {
+  return node[kBypassRouting] !== undefined && node[kBypassRouting].bypass;
+};
+
+exports.setBypass = function setBypass(node, bypass, GainNode) {
+  if (exports.isBypassed(node) === bypass) {
+    return;
+  }
+
+  const routing = getBypassRouting(node, GainNode);
+  routing.bypass = bypass;
+
+  const now = routing.input.context.currentTime;
+  const endTime = now + kBypassCrossfadeDuration;
+
+  [
+    [routing.wet.gain, bypass ? 0 : 1],
+    [routing.dry.gain, bypass ? 1 : 0],
+  ].forEach(([gain, value]) => {
+    gain.cancelScheduledValues(now);
+    gain.setValueAtTime(gain.value, now);
+    gain.linearRampToValueAtTime(value, endTime);
+  });
+};
@@ -0,0 +1,35 @@
+const {
+  kTeardown,
+} = require('./symbols.js');
+
+// Non spec, weak references to the contexts created in the process, cf.
+// `closeAllContexts`. An AudioContext is kept alive by the process until it
+// is closed, the references are removed once the contexts are garbage
+// collected.
+const contexts = new Set();
+const registry = new FinalizationRegistry(ref => contexts.delete(ref));
+
+exports.registerContext = function registerContext(context) {
+  const ref = new WeakRef(context);
+  contexts.add(ref);
+  registry.register(context, ref);
+};
+
+// Closes all the AudioContexts of the process and releases their devices and
+// event callbacks. OfflineAudioContexts which rendering is not started are
+// discarded, the ongoing renderings are resumed and run to completion.
+// Resolves once all contexts are closed, contexts already closed are left
+// untouched so that it can be called several times.
+exports.closeAllContexts = async function closeAllContexts() {
+  const pending = [];
+
+  for (const ref of contexts) {
+    const context = ref.deref();
+
+    if (context !== undefined) {
+      pending.push(context[kTeardown]());
+    }
+  }
+
+  await Promise.all(pending);
+};
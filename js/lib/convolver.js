@@ -0,0 +1,180 @@
+const {
+  kNapiObj,
+} = require('./symbols.js');
+const {
+  getBypassRouting,
+} = require('./bypass.js');
+const {
+  toRenderRate,
+} = require('./oversampling.js');
+
+// Impulse response to give to the native convolver of `context`. When the
+// graph is oversampled the impulse response has `renderOversample` times more
+// frames at the same level, it is scaled down so that the output of the
+// convolver keeps the same level.
+exports.toRenderImpulse = function toRenderImpulse(context, napiBuffer) {
+  // checked here as the native node only knows about the oversampled rate
+  if (napiBuffer.sampleRate !== context.sampleRate) {
+    throw new DOMException('sample rate of the convolution buffer must match the audio context', 'NotSupportedError');
+  }
+
+  const renderBuffer = toRenderRate(context, napiBuffer);
+
+  if (renderBuffer !== napiBuffer) {
+    const gain = napiBuffer.sampleRate / renderBuffer.sampleRate;
+
+    for (let channel = 0; channel < renderBuffer.numberOfChannels; channel++) {
+      const data = renderBuffer.getChannelData(channel);
+
+      for (let i = 0; i < data.length; i++) {
+        data[i] *= gain;
+      }
+    }
+  }
+
+  return renderBuffer;
+};
+
+// Crossfade between convolvers, so that the impulse response of a
+// ConvolverNode can be changed without click, cf. ConvolverNode::swapBuffer:
+//
+//        ┌─> node ────────> gain ─┬─> wet
+// input ─┤                        │
+//        └─> spare ───────> gain ─┘
+//
+// The new impulse response is given to a branch which is not heard, then the
+// gains are crossfaded. A swap requested during a crossfade restarts it from
+// the current gains toward a new spare. Once the crossfade is over the
+// branches faded out leave the render: the spare convolvers and their gains
+// are torn down, while the node is only disconnected from the input as it
+// cannot be dropped. `input` and `wet` are the ones of the bypass routing of
+// the node, which is created if needed, cf. lib/bypass.js
+exports.createImpulseSwap = function createImpulseSwap(node, nativeBinding, GainNode, ConstantSourceNode) {
+  const routing = getBypassRouting(node, GainNode);
+  const gain = new GainNode(node.context);
+
+  // connect the napi objects directly as AudioNode::connect resolves the routing
+  node[kNapiObj].disconnect();
+  node[kNapiObj].connect(gain[kNapiObj], 0, 0);
+  gain[kNapiObj].connect(routing.wet[kNapiObj], 0, 0);
+
+  const main = { convolver: node[kNapiObj], gain, released: false };
+
+  return {
+    node,
+    nativeBinding,
+    GainNode,
+    ConstantSourceNode,
+    routing,
+    main,
+    branches: [main],
+    active: main,
+    // timer of the running crossfade, cf. onCrossfadeEnd
+    timer: null,
+  };
+};
+
+function createSpareBranch(swap) {
+  const { node, nativeBinding, GainNode, routing } = swap;
+  const convolver = new nativeBinding.ConvolverNode(node.context[kNapiObj], {
+    buffer: null,
+    disableNormalization: false,
+  });
+  const gain = new GainNode(node.context, { gain: 0 });
+
+  // follow the configuration of the node
+  ['channelCount', 'channelCountMode', 'channelInterpretation', 'normalize'].forEach(name => {
+    convolver[name] = node[kNapiObj][name];
+  });
+
+  routing.input[kNapiObj].connect(convolver, 0, 0);
+  convolver.connect(gain[kNapiObj], 0, 0);
+  gain[kNapiObj].connect(routing.wet[kNapiObj], 0, 0);
+
+  return { convolver, gain, released: false };
+}
+
+// Take the branch out of the render
+function releaseBranch(swap, branch) {
+  swap.routing.input[kNapiObj].disconnect(branch.convolver, 0, 0);
+
+  if (branch === swap.main) {
+    branch.released = true;
+  } else {
+    branch.convolver.disconnect();
+    branch.gain[kNapiObj].disconnect();
+    swap.branches.splice(swap.branches.indexOf(branch), 1);
+  }
+}
+
+function onCrossfadeEnd(swap, timer) {
+  // the crossfade has been restarted or reset in the meantime
+  if (swap.timer !== timer) {
+    return;
+  }
+
+  swap.timer = null;
+  swap.branches
+    .filter(branch => branch !== swap.active)
+    .forEach(branch => releaseBranch(swap, branch));
+}
+
+// Give the buffer to a branch which is not heard and crossfade to it
+exports.swapImpulse = function swapImpulse(swap, napiBuffer, crossfadeDuration) {
+  let next;
+
+  // the node is silent once released, otherwise it may still be heard
+  if (swap.main.released) {
+    next = swap.main;
+    next.released = false;
+    swap.routing.input[kNapiObj].connect(next.convolver, 0, 0);
+  } else {
+    next = createSpareBranch(swap);
+    swap.branches.push(next);
+  }
+
+  next.convolver.buffer = napiBuffer;
+  swap.active = next;
+
+  const context = swap.node.context;
+  const now = context.currentTime;
+  const endTime = now + crossfadeDuration;
+
+  // ramp from the values of the running crossfade, cf. AudioParam::getValueAtTime
+  swap.branches.forEach(({ gain: { gain } }, index) => {
+    const value = gain.getValueAtTime(now);
+
+    gain.cancelScheduledValues(now);
+    gain.setValueAtTime(value, now);
+    gain.linearRampToValueAtTime(swap.branches[index] === next ? 1 : 0, endTime);
+  });
+
+  // the end of the crossfade is given by the render thread
+  const timer = new swap.ConstantSourceNode(context, { offset: 0 });
+  timer.addEventListener('ended', () => onCrossfadeEnd(swap, timer), { once: true });
+  timer.start(now);
+  timer.stop(endTime);
+
+  swap.timer = timer;
+};
+
+// Make the node heard again, e.g. when its buffer is set directly
+exports.resetImpulseSwap = function resetImpulseSwap(swap) {
+  const now = swap.node.context.currentTime;
+  const { gain } = swap.main.gain;
+
+  if (swap.main.released) {
+    swap.main.released = false;
+    swap.routing.input[kNapiObj].connect(swap.main.convolver, 0, 0);
+  }
+
+  swap.branches
+    .filter(branch => branch !== swap.main)
+    .forEach(branch => releaseBranch(swap, branch));
+
+  gain.cancelScheduledValues(now);
+  gain.setValueAtTime(1, now);
+
+  swap.active = swap.main;
+  swap.timer = null;
+};
@@ -0,0 +1,58 @@
+const { kNapiObj } = require('./symbols.js');
+
+// Non spec, soft limiter applied to the output of a DelayNode created with
+// `clampFeedback: true`, so that a feedback loop with a gain >= 1 stays bounded
+// instead of diverging to Infinity.
+//
+// The limiter is transparent below `kThreshold` and smoothly saturates toward
+// `kCeiling` above it (tanh knee, continuous derivative at the threshold).
+const kThreshold = 1;
+const kCeiling = 2;
+// The WaveShaperNode curve covers [-1, 1], input is scaled down so that the
+// curve covers [-kInputRange, kInputRange], inputs beyond are clamped
+const kInputRange = 4;
+// odd length so that 0 maps exactly to the middle point of the curve, which
+// also flushes denormals (i.e. `1 + x === 1` in the WaveShaperNode)
+const kCurveLength = 16385;
+
+function softLimit(value) {
+  const abs = Math.abs(value);
+
+  if (abs <= kThreshold) {
+    return value;
+  }
+
+  const range = kCeiling - kThreshold;
+  return Math.sign(value) * (kThreshold + range * Math.tanh((abs - kThreshold) / range));
+}
+
+let curve = null;
+
+function getCurve() {
+  if (curve === null) {
+    curve = new Float32Array(kCurveLength);
+    const middle = (kCurveLength - 1) / 2;
+
+    for (let i = 0; i < kCurveLength; i++) {
+      curve[i] = softLimit((i - middle) / middle * kInputRange);
+    }
+  }
+
+  return curve;
+}
+
+// Route the output of the node through the limiter:
+//
+// node ─> scale ─> shaper ─> output
+//
+// Connections from the node are made through `output`, cf. AudioNode::connect
+exports.createFeedbackGuard = function createFeedbackGuard(node, GainNode, WaveShaperNode) {
+  const scale = new GainNode(node.context, { gain: 1 / kInputRange });
+  const output = new WaveShaperNode(node.context, { curve: getCurve() });
+
+  // connect the napi objects directly as AudioNode::connect resolves the routing
+  node[kNapiObj].connect(scale[kNapiObj], 0, 0);
+  scale[kNapiObj].connect(output[kNapiObj], 0, 0);
+
+  return { scale, output };
+};
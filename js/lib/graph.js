@@ -0,0 +1,350 @@
+const {
+  kAudioParamNames,
+  kGetConnections,
+  kGraphNodes,
+} = require('./symbols.js');
+
+// Non spec, JSON description of the graph of a context, cf.
+// BaseAudioContext::exportGraph and BaseAudioContext.importGraph
+//
+// The graph is made of the nodes connected with `connect`, i.e. the internal
+// nodes (e.g. the routing of the bypassable nodes) are not part of it, and
+// describes their current state: their options and the current values of their
+// params. Automation events and the started state of the sources are not
+// exported. Nodes which state can't be read back (e.g. media sources, worklets
+// or custom oscillators) are exported as not `reconstructible`.
+
+// Options of the reconstructible nodes, the params and the channel options
+// are exported for every node
+const kNodeOptions = {
+  AnalyserNode: node => ({
+    fftSize: node.fftSize,
+    minDecibels: node.minDecibels,
+    maxDecibels: node.maxDecibels,
+    smoothingTimeConstant: node.smoothingTimeConstant,
+    channelMode: node.channelMode,
+  }),
+  AudioBufferSourceNode: node => ({
+    buffer: exportBuffer(node.buffer),
+    loop: node.loop,
+    loopStart: node.loopStart,
+    loopEnd: node.loopEnd,
+    resampleBuffer: node.resampleBuffer,
+  }),
+  BiquadFilterNode: node => ({
+    type: node.type,
+  }),
+  ChannelMatrixNode: node => ({
+    inputChannelCount: node.inputChannelCount,
+    outputChannelCount: node.outputChannelCount,
+    matrix: Array.from(node.matrix),
+  }),
+  ChannelMergerNode: node => ({
+    numberOfInputs: node.numberOfInputs,
+  }),
+  ChannelSplitterNode: node => ({
+    numberOfOutputs: node.numberOfOutputs,
+  }),
+  ConstantSourceNode: () => ({}),
+  ConvolverNode: node => ({
+    buffer: exportBuffer(node.buffer),
+    disableNormalization: !node.normalize,
+  }),
+  DelayNode: node => ({
+    maxDelayTime: node.maxDelayTime,
+    clampFeedback: node.clampFeedback,
+  }),
+  DynamicsCompressorNode: () => ({}),
+  GainNode: () => ({}),
+  NoiseSourceNode: node => ({
+    type: node.type,
+  }),
+  // the periodic wave of a custom oscillator can't be read back
+  OscillatorNode: node => node.type === 'custom' ? null : {
+    type: node.type,
+  },
+  PannerNode: node => ({
+    panningModel: node.panningModel,
+    distanceModel: node.distanceModel,
+    refDistance: node.refDistance,
+    maxDistance: node.maxDistance,
+    rolloffFactor: node.rolloffFactor,
+    coneInnerAngle: node.coneInnerAngle,
+    coneOuterAngle: node.coneOuterAngle,
+    coneOuterGain: node.coneOuterGain,
+  }),
+  Sampler: node => ({
+    buffer: exportBuffer(node.buffer),
+    maxVoices: node.maxVoices,
+    rootPitch: node.rootPitch,
+  }),
+  StereoPannerNode: () => ({}),
+  WaveShaperNode: node => ({
+    curve: node.curve === null ? null : Array.from(node.curve),
+    oversample: node.oversample,
+  }),
+};
+
+// Attributes that are not part of the options, applied once the node is created
+const kNodeAttributes = {
+  AnalyserNode: ['windowFunction'],
+  BiquadFilterNode: ['bypass'],
+  ConvolverNode: ['bypass'],
+  DynamicsCompressorNode: ['bypass'],
+  WaveShaperNode: ['bypass'],
+};
+
+// Options that are AudioBuffers or Float32Arrays, cf. exportBuffer
+const kBufferOptions = ['buffer'];
+const kArrayOptions = ['curve', 'matrix'];
+
+function exportBuffer(buffer) {
+  if (buffer === null) {
+    return null;
+  }
+
+  const channels = [];
+  for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+    channels.push(Array.from(buffer.getChannelData(channel)));
+  }
+
+  return {
+    numberOfChannels: buffer.numberOfChannels,
+    length: buffer.length,
+    sampleRate: buffer.sampleRate,
+    channels,
+  };
+}
+
+function importBuffer(json, AudioBuffer) {
+  if (json === null) {
+    return null;
+  }
+
+  const buffer = new AudioBuffer({
+    numberOfChannels: json.numberOfChannels,
+    length: json.length,
+    sampleRate: json.sampleRate,
+  });
+
+  json.channels.forEach((data, channel) => {
+    buffer.copyToChannel(Float32Array.from(data), channel);
+  });
+
+  return buffer;
+}
+
+// AudioParams exposed as attributes of the node (or listener), by name. The
+// params are listed by the class of the node, the nodes defined outside of the
+// generated ones (e.g. worklets) expose none.
+function getAudioParams(target) {
+  const names = target.constructor[kAudioParamNames] || [];
+
+  return new Map(names.map(name => [name, target[name]]));
+}
+
+// Minimum size of the graph of a context before the references of the
+// collected nodes are swept, cf. addGraphNode
+const kGraphSweepMinSize = 64;
+// graph -> size from which the graph is swept
+const graphSweepSizes = new WeakMap();
+
+// Add a weak reference to the node to the graph of its context, so that the
+// graph does not prevent the node from being collected. The references of the
+// collected nodes are swept once the graph has doubled in size since the last
+// sweep, rather than registering a finalizer for each node.
+exports.addGraphNode = function addGraphNode(graph, node) {
+  graph.add(new WeakRef(node));
+
+  if (graph.size < (graphSweepSizes.get(graph) || kGraphSweepMinSize)) {
+    return;
+  }
+
+  for (const ref of graph) {
+    if (ref.deref() === undefined) {
+      graph.delete(ref);
+    }
+  }
+
+  graphSweepSizes.set(graph, Math.max(kGraphSweepMinSize, 2 * graph.size));
+};
+
+exports.exportGraph = function exportGraph(context, jsExport) {
+  const { AudioNode } = jsExport;
+  const ids = new Map();
+  const nodes = [];
+  const connections = [];
+  // AudioParam -> { id, name } of its node, or { listener: name }
+  const paramOwners = new Map();
+
+  for (const [name, param] of getAudioParams(context.listener)) {
+    paramOwners.set(param, { listener: name });
+  }
+
+  function addNode(node) {
+    if (ids.has(node)) {
+      return ids.get(node);
+    }
+
+    const id = nodes.length;
+    const type = node[Symbol.toStringTag];
+    const params = getAudioParams(node);
+    const readOptions = kNodeOptions[type];
+    const options = readOptions ? readOptions(node) : null;
+
+    ids.set(node, id);
+
+    const desc = {
+      id,
+      type,
+      reconstructible: type === 'AudioDestinationNode' || options !== null,
+      options: {
+        ...options,
+        channelCount: node.channelCount,
+        channelCountMode: node.channelCountMode,
+        channelInterpretation: node.channelInterpretation,
+      },
+    };
+
+    for (const [name, param] of params) {
+      desc.options[name] = param.value;
+      paramOwners.set(param, { id, name });
+    }
+
+    for (const name of kNodeAttributes[type] || []) {
+      desc.options[name] = node[name];
+    }
+
+    nodes.push(desc);
+
+    return id;
+  }
+
+  const sources = [];
+
+  for (const ref of context[kGraphNodes]) {
+    const node = ref.deref();
+
+    if (node !== undefined && node[kGetConnections]().length > 0) {
+      sources.push(node);
+      addNode(node);
+    }
+  }
+
+  // destinations first so that params of nodes are known
+  for (const node of sources) {
+    for (const { destination } of node[kGetConnections]()) {
+      if (destination instanceof AudioNode) {
+        addNode(destination);
+      }
+    }
+  }
+
+  for (const node of sources) {
+    const source = ids.get(node);
+
+    for (const { destination, output, input, edge } of node[kGetConnections]()) {
+      if (destination instanceof AudioNode) {
+        const connection = { source, output, destination: ids.get(destination), input };
+
+        // non spec, cf. AudioNode::connect
+        if (edge !== null) {
+          connection.gain = edge.gain.value;
+        }
+
+        connections.push(connection);
+        continue;
+      }
+
+      const owner = paramOwners.get(destination);
+
+      // param of a node that is not connected itself
+      if (owner === undefined) {
+        continue;
+      }
+
+      if ('listener' in owner) {
+        connections.push({ source, output, listener: owner.listener });
+      } else {
+        connections.push({ source, output, destination: owner.id, param: owner.name });
+      }
+    }
+  }
+
+  return { nodes, connections };
+};
+
+exports.importGraph = function importGraph(context, json, jsExport) {
+  const nodes = new Map();
+
+  for (const desc of json.nodes) {
+    if (desc.type === 'AudioDestinationNode') {
+      nodes.set(desc.id, context.destination);
+      continue;
+    }
+
+    if (!desc.reconstructible) {
+      nodes.set(desc.id, null);
+      continue;
+    }
+
+    const options = { ...desc.options };
+    const attributes = kNodeAttributes[desc.type] || [];
+
+    for (const name of kBufferOptions) {
+      if (name in options) {
+        options[name] = importBuffer(options[name], jsExport.AudioBuffer);
+      }
+    }
+
+    for (const name of kArrayOptions) {
+      if (name in options && options[name] !== null) {
+        options[name] = Float32Array.from(options[name]);
+      }
+    }
+
+    for (const name of attributes) {
+      delete options[name];
+    }
+
+    const node = new jsExport[desc.type](context, options);
+
+    for (const name of attributes) {
+      if (name in desc.options) {
+        node[name] = desc.options[name];
+      }
+    }
+
+    nodes.set(desc.id, node);
+  }
+
+  for (const connection of json.connections) {
+    const source = nodes.get(connection.source);
+
+    if (!source) {
+      continue;
+    }
+
+    if ('listener' in connection) {
+      source.connect(context.listener[connection.listener], connection.output);
+      continue;
+    }
+
+    const destination = nodes.get(connection.destination);
+
+    if (!destination) {
+      continue;
+    }
+
+    if ('param' in connection) {
+      source.connect(destination[connection.param], connection.output);
+    } else {
+      const options = 'gain' in connection ? { gain: connection.gain } : undefined;
+      source.connect(destination, connection.output, connection.input, options);
+    }
+  }
+
+  // in the order of the description, `null` for the nodes that are not
+  // reconstructible
+  return json.nodes.map(desc => nodes.get(desc.id));
+};
@@ -0,0 +1,88 @@
+const {
+  kNapiObj,
+} = require('./symbols.js');
+
+// Non spec, oversampling factors supported by OfflineAudioContext `renderOversample`
+const kOversampleFactors = [1, 2, 4];
+
+exports.kOversampleFactors = kOversampleFactors;
+
+// number of zero crossings of the sinc on each side of the kernel center
+const kZeroCrossings = 32;
+// cutoff frequency of the anti-aliasing filter relative to the target Nyquist
+// frequency, leaves room for the transition band of the filter
+const kCutoff = 0.9;
+
+// Windowed sinc lowpass (Blackman window), cutoff expressed in cycles per
+// sample of the oversampled signal
+function createKernel(factor) {
+  const halfLength = kZeroCrossings * factor;
+  const size = 2 * halfLength + 1;
+  const cutoff = kCutoff * 0.5 / factor;
+  const kernel = new Float64Array(size);
+  let sum = 0;
+
+  for (let n = 0; n < size; n++) {
+    const x = n - halfLength;
+    const sinc = x === 0 ? 1 : Math.sin(2 * Math.PI * cutoff * x) / (Math.PI * x) / (2 * cutoff);
+    const phase = 2 * Math.PI * n / (size - 1);
+    const window = 0.42 - 0.5 * Math.cos(phase) + 0.08 * Math.cos(2 * phase);
+
+    kernel[n] = sinc * window;
+    sum += kernel[n];
+  }
+
+  // normalize for unity gain at DC
+  for (let n = 0; n < size; n++) {
+    kernel[n] /= sum;
+  }
+
+  return kernel;
+}
+
+// Lowpass filter and decimate `input` by `factor`. The kernel is centered on
+// each output frame so that no latency is introduced, missing input frames
+// at both ends are considered to be zeros.
+function downsample(input, factor, length) {
+  const output = new Float32Array(length);
+
+  if (factor === 1) {
+    output.set(input.subarray(0, length));
+    return output;
+  }
+
+  const kernel = createKernel(factor);
+  const halfLength = (kernel.length - 1) / 2;
+
+  for (let i = 0; i < length; i++) {
+    const center = i * factor;
+    const start = Math.max(0, center - halfLength);
+    const end = Math.min(input.length - 1, center + halfLength);
+    let value = 0;
+
+    for (let j = start; j <= end; j++) {
+      value += input[j] * kernel[j - center + halfLength];
+    }
+
+    output[i] = value;
+  }
+
+  return output;
+}
+
+exports.downsample = downsample;
+
+// Copy of `napiBuffer` at the rate the graph of `context` is rendered at, i.e.
+// `renderOversample` times the rate of the context, `napiBuffer` itself if the
+// graph is not oversampled
+function toRenderRate(context, napiBuffer) {
+  const renderSampleRate = context[kNapiObj].sampleRate;
+
+  if (renderSampleRate === context.sampleRate) {
+    return napiBuffer;
+  }
+
+  return napiBuffer.resample(renderSampleRate);
+}
+
+exports.toRenderRate = toRenderRate;
@@ -0,0 +1,138 @@
+// number of render quanta over which the output of a paused source is faded
+// out and in, i.e. ~5ms at 48kHz, to prevent clicks
+const kPauseFadeQuanta = 2;
+
+// Value of the scheduled value curve `event` at `time`, cf. setValueCurveAtTime
+function curveValueAtTime(event, time) {
+  const { values, duration } = event;
+  const position = Math.min(1, (time - event.time) / duration) * (values.length - 1);
+  const index = Math.min(Math.floor(position), values.length - 2);
+  const ratio = position - index;
+
+  return values[index] + (values[index + 1] - values[index]) * ratio;
+}
+
+// Values of the scheduled value curve `event` between `startTime` and
+// `endTime`, with the same number of points as the curve
+function curveValuesBetween(event, startTime, endTime) {
+  return event.values.map((_, index) => {
+    const time = startTime + (endTime - startTime) * index / (event.values.length - 1);
+    return curveValueAtTime(event, time);
+  });
+}
+
+// Scheduled automation events of `param` describing its value from `time` on,
+// cf. AudioParam::getScheduledEvents. The list starts with the value of the
+// param at `time`, or with the remaining part of the value curve in progress
+// at `time` if any. The events scheduled after `time` are kept as is.
+function pendingAutomationEvents(param, time) {
+  const events = param.getScheduledEvents();
+  const pending = events.filter(event => event.time > time);
+  const previous = events.filter(event => event.time <= time).pop();
+
+  if (
+    previous !== undefined
+    && previous.type === 'setValueCurve'
+    && previous.time + previous.duration > time
+  ) {
+    const endTime = previous.time + previous.duration;
+    const values = curveValuesBetween(previous, time, endTime);
+
+    pending.unshift({ type: 'setValueCurve', time, values, duration: endTime - time });
+    return pending;
+  }
+
+  // a ramp scheduled after `time` starts from the value at `time`, otherwise
+  // a setTarget in progress goes on from it
+  if (
+    previous !== undefined
+    && previous.type === 'setTarget'
+    && (pending.length === 0 || !['linearRamp', 'expRamp'].includes(pending[0].type))
+  ) {
+    pending.unshift({ ...previous, time });
+  }
+
+  pending.unshift({ type: 'setValue', time, value: param.getValueAtTime(time) });
+
+  return pending;
+}
+
+// Set the value of `param` to `value` from `time` on, the automation events
+// in progress at `time` are truncated and the ones scheduled after are
+// cancelled. Returns the cancelled automation, cf. pendingAutomationEvents.
+//
+// The events in progress are truncated by hand rather than with
+// cancelAndHoldAtTime, which does not hold ramps ending after another event
+// is scheduled. A ramp cancelled while in progress restarts from its start
+// value, so that the ramp is first anchored at `currentTime`.
+function holdAutomation(param, currentTime, time, value) {
+  const events = param.getScheduledEvents();
+  const pending = pendingAutomationEvents(param, time);
+  const previous = events.filter(event => event.time <= time).pop();
+  // a ramp ending at `time` would be cancelled as well
+  const next = events.find(event => event.time >= time);
+
+  if (
+    previous !== undefined
+    && previous.type === 'setValueCurve'
+    && previous.time + previous.duration > time
+  ) {
+    param.cancelScheduledValues(previous.time);
+
+    if (time > previous.time) {
+      const values = curveValuesBetween(previous, previous.time, time);
+      param.setValueCurveAtTime(values, previous.time, time - previous.time);
+    }
+  } else if (next !== undefined && ['linearRamp', 'expRamp'].includes(next.type)) {
+    param.setValueAtTime(param.getValueAtTime(currentTime), currentTime);
+    param.cancelScheduledValues(time);
+
+    // the ramp in progress ends at the value it reaches at `time`
+    if (next.type === 'linearRamp') {
+      param.linearRampToValueAtTime(pending[0].value, time);
+    } else {
+      param.exponentialRampToValueAtTime(pending[0].value, time);
+    }
+  } else {
+    param.cancelScheduledValues(time);
+  }
+
+  param.setValueAtTime(value, time);
+
+  return pending;
+}
+
+// Schedule the automation returned by `holdAutomation` again on `param`,
+// delayed by `delay` seconds
+function scheduleAutomationEvents(param, events, delay) {
+  events.forEach(event => {
+    const time = event.time + delay;
+
+    switch (event.type) {
+      case 'setValue':
+        param.setValueAtTime(event.value, time);
+        break;
+      case 'linearRamp':
+        param.linearRampToValueAtTime(event.value, time);
+        break;
+      case 'expRamp':
+        param.exponentialRampToValueAtTime(event.value, time);
+        break;
+      case 'setTarget':
+        param.setTargetAtTime(event.value, time, event.timeConstant);
+        break;
+      case 'setValueCurve':
+        param.setValueCurveAtTime(event.values, time, event.duration);
+        break;
+      case 'cancelAndHold':
+        param.cancelAndHoldAtTime(time);
+        break;
+    }
+  });
+}
+
+module.exports = {
+  kPauseFadeQuanta,
+  holdAutomation,
+  scheduleAutomationEvents,
+};
@@ -0,0 +1,61 @@
+const conversions = require('webidl-conversions');
+
+// Non spec, bounds the number of OfflineAudioContext rendering in parallel,
+// cf. `setOfflineRenderConcurrency`. Each rendering occupies a thread of the
+// shared runtime until it is finished, renderings over the limit wait for a
+// free slot in the order `startRendering` was called.
+//
+// Note that a rendering keeps its slot while suspended, cf. `suspend(time)`
+let concurrency = Infinity;
+let running = 0;
+const pending = [];
+
+function next() {
+  while (running < concurrency && pending.length > 0) {
+    running += 1;
+    pending.shift()();
+  }
+}
+
+// Returns `null` if a slot is free, i.e. the rendering can start right away,
+// otherwise a promise that resolves when the rendering can start.
+// `releaseRenderSlot` must be called once it is finished
+exports.acquireRenderSlot = function acquireRenderSlot() {
+  if (running < concurrency && pending.length === 0) {
+    running += 1;
+    return null;
+  }
+
+  return new Promise(resolve => {
+    pending.push(resolve);
+    next();
+  });
+};
+
+exports.releaseRenderSlot = function releaseRenderSlot() {
+  running -= 1;
+  next();
+};
+
+// Maximum number of OfflineAudioContext rendering in parallel, `Infinity` (the
+// default) removes the limit. Applies to the renderings started afterward as
+// well as to the ones waiting for a slot.
+exports.setOfflineRenderConcurrency = function setOfflineRenderConcurrency(value) {
+  if (arguments.length < 1) {
+    throw new TypeError(`Failed to execute 'setOfflineRenderConcurrency': 1 argument required, but only ${arguments.length} present`);
+  }
+
+  if (value !== Infinity) {
+    value = conversions['unsigned long'](value, {
+      enforceRange: true,
+      context: `Failed to execute 'setOfflineRenderConcurrency': The provided value (${value})`,
+    });
+
+    if (value < 1) {
+      throw new RangeError(`Failed to execute 'setOfflineRenderConcurrency': The provided value (${value}) must be greater than or equal to 1`);
+    }
+  }
+
+  concurrency = value;
+  next();
+};
@@ -5,7 +5,24 @@ module.exports.kCreateProcessor = Symbol('node-web-audio-api:create-processor');
 module.exports.kProcessorRegistered = Symbol('node-web-audio-api:processor-registered');
 module.exports.kGetParameterDescriptors = Symbol('node-web-audio-api:get-parameter-descriptors');
 module.exports.kWorkletRelease = Symbol('node-web-audio-api:worklet-release');
+module.exports.kWorkletSetProcessBudget = Symbol('node-web-audio-api:worklet-set-process-budget');
 module.exports.kCheckProcessorsCreated = Symbol('node-web-audio-api:check-processor-created');
+module.exports.kMuteGain = Symbol('node-web-audio-api:mute-gain');
+module.exports.kScheduledSourceNodes = Symbol('node-web-audio-api:scheduled-source-nodes');
+module.exports.kGetUnderrunCount = Symbol('node-web-audio-api:get-underrun-count');
+module.exports.kBypassRouting = Symbol('node-web-audio-api:bypass-routing');
+module.exports.kFeedbackGuard = Symbol('node-web-audio-api:feedback-guard');
+module.exports.kChannelAnalysers = Symbol('node-web-audio-api:channel-analysers');
+module.exports.kOutputGate = Symbol('node-web-audio-api:output-gate');
+module.exports.kInsertOutputGate = Symbol('node-web-audio-api:insert-output-gate');
+module.exports.kRerouteInput = Symbol('node-web-audio-api:reroute-input');
+module.exports.kRerouteOutput = Symbol('node-web-audio-api:reroute-output');
+module.exports.kGetConnections = Symbol('node-web-audio-api:get-connections');
+module.exports.kGraphNodes = Symbol('node-web-audio-api:graph-nodes');
+module.exports.kAudioParamNames = Symbol('node-web-audio-api:audio-param-names');
+module.exports.kTeardown = Symbol('node-web-audio-api:teardown');
+module.exports.kStartTime = Symbol('node-web-audio-api:start-time');
+module.exports.kStopTime = Symbol('node-web-audio-api:stop-time');
 
 // semi-private keys for events listeners
 
@@ -13,6 +30,7 @@ module.exports.kCheckProcessorsCreated = Symbol('node-web-audio-api:check-proces
 module.exports.kOnStateChange = Symbol.for('node-web-audio-api:onstatechange');
 // AudioContext
 module.exports.kOnSinkChange = Symbol.for('node-web-audio-api:onsinkchange');
+module.exports.kOnRenderQuantum = Symbol.for('node-web-audio-api:onrenderquantum');
 // # OfflineAudioContext
 // > [The onstatechange] event is fired before the complete event is fired
 // cf. https://webaudio.github.io/web-audio-api/#dom-baseaudiocontext-onstatechange
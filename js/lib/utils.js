@@ -3,6 +3,15 @@ exports.isFunction = function isFunction(val) {
     Object.prototype.toString.call(val) == '[object AsyncFunction]';
 };
 
+exports.isIterable = function isIterable(obj) {
+  // checks for null and undefined
+  if (obj === null || obj === undefined) {
+    return false;
+  }
+
+  return typeof obj[Symbol.iterator] === 'function';
+};
+
 const kEnumerableProperty = { __proto__: null };
 kEnumerableProperty.enumerable = true;
 Object.freeze(kEnumerableProperty);
@@ -14,3 +23,30 @@ kHiddenProperty.enumerable = false;
 Object.freeze(kHiddenProperty);
 
 exports.kHiddenProperty = kHiddenProperty;
+
+// Non-spec: times in the past are clamped to `currentTime` on realtime
+// contexts, as browsers do, so that events scheduled slightly late because of
+// the jitter of the JS thread are not mis-scheduled. Negative times are left
+// untouched so that they still throw, and offline contexts keep exact times.
+exports.clampToCurrentTime = function clampToCurrentTime(context, time) {
+  if (context === null || time < 0 || context[Symbol.toStringTag] !== 'AudioContext') {
+    return time;
+  }
+
+  return Math.max(time, context.currentTime);
+};
+
+// Non-spec: with the `strictAutomation` option of OfflineAudioContext, an
+// automation event ending after the end of the rendering throws a RangeError
+// instead of being silently truncated.
+exports.assertWithinRenderDuration = function assertWithinRenderDuration(context, method, endTime) {
+  if (context === null || context[Symbol.toStringTag] !== 'OfflineAudioContext' || !context.strictAutomation) {
+    return;
+  }
+
+  const renderDuration = context.length / context.sampleRate;
+
+  if (endTime > renderDuration) {
+    throw new RangeError(`Failed to execute '${method}' on 'AudioParam': The automation ends at ${endTime} seconds, after the end of the rendering (${renderDuration} seconds)`);
+  }
+};
@@ -22,7 +22,7 @@ use napi::*;
 use napi_derive::js_function;
 use web_audio_api::node::*;
 
-pub(crate) struct NapiAnalyserNode(AnalyserNode);
+pub(crate) struct NapiAnalyserNode(AnalyserNode, Option<(*mut u8, usize)>);
 
 // for debug purpose
 // impl Drop for NapiAnalyserNode {
@@ -196,7 +196,7 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     ])?;
 
     // finalize instance creation
-    let napi_node = NapiAnalyserNode(native_node);
+    let napi_node = NapiAnalyserNode(native_node, Default::default());
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -328,10 +328,29 @@ fn get_float_frequency_data(ctx: CallContext) -> Result<JsUndefined> {
 fn get_byte_frequency_data(ctx: CallContext) -> Result<JsUndefined> {
     let js_this = ctx.this_unchecked::<JsObject>();
     let napi_node = ctx.env.unwrap::<NapiAnalyserNode>(&js_this)?;
-    let node = napi_node.unwrap();
-
-    let mut array_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
-    let array: &mut [u8] = array_js.as_mut();
+    let NapiAnalyserNode(node, byte_array) = napi_node;
+
+    // non spec, the JS facade calls the method without argument if the array
+    // is the one given to the last call, cf. AnalyserNode::getByteFrequencyData
+    let mut array_js;
+    let array: &mut [u8] = if ctx.length == 0 {
+        match *byte_array {
+            // SAFETY: the JS facade holds the array given to the last call, and
+            // checks that it is neither detached nor resized
+            Some((ptr, len)) => unsafe { std::slice::from_raw_parts_mut(ptr, len) },
+            None => &mut [],
+        }
+    } else {
+        array_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
+        // the data of a detached array is a null pointer
+        let array: &mut [u8] = if array_js.length == 0 {
+            &mut []
+        } else {
+            array_js.as_mut()
+        };
+        *byte_array = (!array.is_empty()).then_some((array.as_mut_ptr(), array.len()));
+        array
+    };
 
     node.get_byte_frequency_data(array);
 
@@ -356,10 +375,29 @@ fn get_float_time_domain_data(ctx: CallContext) -> Result<JsUndefined> {
 fn get_byte_time_domain_data(ctx: CallContext) -> Result<JsUndefined> {
     let js_this = ctx.this_unchecked::<JsObject>();
     let napi_node = ctx.env.unwrap::<NapiAnalyserNode>(&js_this)?;
-    let node = napi_node.unwrap();
-
-    let mut array_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
-    let array: &mut [u8] = array_js.as_mut();
+    let NapiAnalyserNode(node, byte_array) = napi_node;
+
+    // non spec, the JS facade calls the method without argument if the array
+    // is the one given to the last call, cf. AnalyserNode::getByteFrequencyData
+    let mut array_js;
+    let array: &mut [u8] = if ctx.length == 0 {
+        match *byte_array {
+            // SAFETY: the JS facade holds the array given to the last call, and
+            // checks that it is neither detached nor resized
+            Some((ptr, len)) => unsafe { std::slice::from_raw_parts_mut(ptr, len) },
+            None => &mut [],
+        }
+    } else {
+        array_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
+        // the data of a detached array is a null pointer
+        let array: &mut [u8] = if array_js.length == 0 {
+            &mut []
+        } else {
+            array_js.as_mut()
+        };
+        *byte_array = (!array.is_empty()).then_some((array.as_mut_ptr(), array.len()));
+        array
+    };
 
     node.get_byte_time_domain_data(array);
 
@@ -25,6 +25,11 @@ impl NapiAudioBuffer {
                 Property::new("getChannelData")?.with_method(get_channel_data),
                 Property::new("copyToChannel")?.with_method(copy_to_channel),
                 Property::new("copyFromChannel")?.with_method(copy_from_channel),
+                Property::new("slice")?.with_method(slice),
+                Property::new("resample")?.with_method(resample),
+                Property::new("concat")?
+                    .with_method(concat)
+                    .with_property_attributes(PropertyAttributes::Static),
             ],
         )
     }
@@ -160,10 +165,15 @@ fn copy_to_channel(ctx: CallContext) -> Result<JsUndefined> {
     let obj = napi_obj.unwrap_mut();
 
     let source_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
-    let source: &[f32] = source_js.as_ref();
-
     let channel_number = ctx.get::<JsNumber>(1)?.get_double()? as usize;
     let offset = ctx.get::<JsNumber>(2)?.get_double()? as usize;
+    // the data pointer of an empty typed array may be null, the channel number
+    // must still be checked though
+    let source: &[f32] = if source_js.length == 0 {
+        &[]
+    } else {
+        source_js.as_ref()
+    };
 
     obj.copy_to_channel_with_offset(source, channel_number, offset);
 
@@ -177,16 +187,122 @@ fn copy_from_channel(ctx: CallContext) -> Result<JsUndefined> {
     let obj = napi_obj.unwrap_mut();
 
     let mut dest_js = ctx.get::<JsTypedArray>(0)?.into_value()?;
-    let dest: &mut [f32] = dest_js.as_mut();
-
     let channel_number = ctx.get::<JsNumber>(1)?.get_double()? as usize;
     let offset = ctx.get::<JsNumber>(2)?.get_double()? as usize;
+    // the data pointer of an empty typed array may be null, the channel number
+    // must still be checked though
+    let dest: &mut [f32] = if dest_js.length == 0 {
+        &mut []
+    } else {
+        dest_js.as_mut()
+    };
 
     obj.copy_from_channel_with_offset(dest, channel_number, offset);
 
     ctx.env.get_undefined()
 }
 
+// Non-spec, `start` and `end` are clamped on JS side
+#[js_function(2)]
+fn slice(ctx: CallContext) -> Result<JsObject> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_obj = ctx.env.unwrap::<NapiAudioBuffer>(&js_this)?;
+    let obj = napi_obj.unwrap();
+
+    let start = ctx.get::<JsNumber>(0)?.get_double()? as usize;
+    let end = ctx.get::<JsNumber>(1)?.get_double()? as usize;
+
+    let samples = (0..obj.number_of_channels())
+        .map(|channel_number| obj.get_channel_data(channel_number)[start..end].to_vec())
+        .collect();
+    let audio_buffer = AudioBuffer::from(samples, obj.sample_rate());
+
+    let ctor = crate::utils::get_class_ctor(ctx.env, "AudioBuffer")?;
+    let js_audio_buffer = ctor.new_instance(&[ctx.env.get_null()?])?;
+    let napi_audio_buffer = ctx.env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
+    napi_audio_buffer.insert(audio_buffer);
+
+    Ok(js_audio_buffer)
+}
+
+// Non-spec, copy of the buffer resampled to the given sample rate, cf.
+// AudioBufferSourceNode `resampleBuffer` option
+#[js_function(1)]
+fn resample(ctx: CallContext) -> Result<JsObject> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_obj = ctx.env.unwrap::<NapiAudioBuffer>(&js_this)?;
+    let obj = napi_obj.unwrap();
+
+    let sample_rate = ctx.get::<JsNumber>(0)?.get_double()? as f32;
+
+    // sample `i` of the copy is at time `i / sample_rate` in the source, so
+    // that pitch and duration are preserved
+    let ratio = obj.sample_rate() as f64 / sample_rate as f64;
+    let source_length = obj.length();
+    let length = (source_length as f64 / ratio).ceil() as usize;
+
+    let samples = (0..obj.number_of_channels())
+        .map(|channel_number| {
+            let channel = obj.get_channel_data(channel_number);
+
+            (0..length)
+                .map(|i| {
+                    let playhead = i as f64 * ratio;
+                    let prev_index = playhead.floor() as usize;
+                    let next_index = prev_index + 1;
+                    let k = (playhead - playhead.floor()) as f32;
+
+                    let prev = channel[prev_index];
+                    let next = channel.get(next_index).copied().unwrap_or(0.);
+                    (1. - k) * prev + k * next
+                })
+                .collect()
+        })
+        .collect();
+    let audio_buffer = AudioBuffer::from(samples, sample_rate);
+
+    let ctor = crate::utils::get_class_ctor(ctx.env, "AudioBuffer")?;
+    let js_audio_buffer = ctor.new_instance(&[ctx.env.get_null()?])?;
+    let napi_audio_buffer = ctx.env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
+    napi_audio_buffer.insert(audio_buffer);
+
+    Ok(js_audio_buffer)
+}
+
+// Non-spec, static method, sample rates and number of channels are checked on JS side
+#[js_function(1)]
+fn concat(ctx: CallContext) -> Result<JsObject> {
+    let js_buffers = ctx.get::<JsObject>(0)?;
+    let number_of_buffers = js_buffers.get_array_length()?;
+
+    let mut samples: Vec<Vec<f32>> = vec![];
+    let mut sample_rate = 0.;
+
+    for i in 0..number_of_buffers {
+        let js_buffer = js_buffers.get_element::<JsObject>(i)?;
+        let napi_obj = ctx.env.unwrap::<NapiAudioBuffer>(&js_buffer)?;
+        let obj = napi_obj.unwrap();
+
+        sample_rate = obj.sample_rate();
+        samples.resize_with(obj.number_of_channels(), Vec::new);
+        samples
+            .iter_mut()
+            .enumerate()
+            .for_each(|(channel_number, channel)| {
+                channel.extend_from_slice(obj.get_channel_data(channel_number))
+            });
+    }
+
+    let audio_buffer = AudioBuffer::from(samples, sample_rate);
+
+    let ctor = crate::utils::get_class_ctor(ctx.env, "AudioBuffer")?;
+    let js_audio_buffer = ctor.new_instance(&[ctx.env.get_null()?])?;
+    let napi_audio_buffer = ctx.env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
+    napi_audio_buffer.insert(audio_buffer);
+
+    Ok(js_audio_buffer)
+}
+
 // @FIXME - cf. https://github.com/ircam-ismm/node-web-audio-api/issues/80
 #[js_function(1)]
 fn get_channel_data(ctx: CallContext) -> Result<JsTypedArray> {
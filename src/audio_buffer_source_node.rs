@@ -22,7 +22,7 @@ use napi::*;
 use napi_derive::js_function;
 use web_audio_api::node::*;
 
-pub(crate) struct NapiAudioBufferSourceNode(AudioBufferSourceNode);
+pub(crate) struct NapiAudioBufferSourceNode(AudioBufferSourceNode, crate::utils::RenderStartTime);
 
 // for debug purpose
 // impl Drop for NapiAudioBufferSourceNode {
@@ -47,7 +47,9 @@ impl NapiAudioBufferSourceNode {
                 .with_getter(get_loop_end)
                 .with_setter(set_loop_end),
             Property::new("start")?.with_method(start),
-            Property::new("stop")?.with_method(stop)
+            Property::new("stop")?.with_method(stop),
+            Property::new("actualStartTime")?.with_method(actual_start_time),
+            Property::new("position")?.with_method(position)
         ];
 
         env.define_class("AudioBufferSourceNode", constructor, &interface)
@@ -172,8 +174,15 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
             .with_property_attributes(PropertyAttributes::Static),
     ])?;
 
+    // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+    let track_actual_start_time = js_options
+        .get::<&str, JsBoolean>("trackActualStartTime")?
+        .unwrap()
+        .get_value()?;
+    let start_time = crate::utils::RenderStartTime::new(track_actual_start_time);
+
     // finalize instance creation
-    let napi_node = NapiAudioBufferSourceNode(native_node);
+    let napi_node = NapiAudioBufferSourceNode(native_node, start_time);
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -238,6 +247,8 @@ fn start(ctx: CallContext) -> Result<JsUndefined> {
     };
 
     node.start_at_with_offset_and_duration(when, offset, duration);
+    // cf. AudioScheduledSourceNode::getActualStartTime
+    napi_node.1.record(napi_node.0.context(), when);
 
     ctx.env.get_undefined()
 }
@@ -254,6 +265,28 @@ fn stop(ctx: CallContext) -> Result<JsUndefined> {
     ctx.env.get_undefined()
 }
 
+// non spec, cf. AudioScheduledSourceNode::getActualStartTime
+#[js_function]
+fn actual_start_time(ctx: CallContext) -> Result<JsUnknown> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioBufferSourceNode>(&js_this)?;
+
+    match napi_node.1.get() {
+        Some(time) => ctx.env.create_double(time).map(|v| v.into_unknown()),
+        None => ctx.env.get_null().map(|v| v.into_unknown()),
+    }
+}
+
+#[js_function(0)]
+fn position(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioBufferSourceNode>(&js_this)?;
+    let node = napi_node.unwrap();
+
+    let value = node.position();
+    ctx.env.create_double(value)
+}
+
 // -------------------------------------------------
 // Getters / Setters
 // -------------------------------------------------
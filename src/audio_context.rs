@@ -1,17 +1,58 @@
 use std::io::Cursor;
 use std::sync::Arc;
 
+use cpal::traits::{DeviceTrait, HostTrait};
 use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunctionCallMode};
 use napi::*;
 use napi_derive::js_function;
 use web_audio_api::context::*;
+use web_audio_api::media_devices::{enumerate_devices_sync, MediaDeviceInfoKind};
+use web_audio_api::node::AudioNode;
+use web_audio_api::worklet::{AudioWorkletNode, AudioWorkletNodeOptions};
 use web_audio_api::Event;
 
+use crate::utils::{RenderQuantum, RenderQuantumMeter, RenderQuantumMeterStop};
 use crate::*;
 
-/// Napi object wrapping the native AudioContext and the AudioWorklet ID
-#[derive(Clone)]
-pub(crate) struct NapiAudioContext(Arc<AudioContext>, usize);
+/// Napi object wrapping the native AudioContext, the AudioWorklet ID, the
+/// eventual render quantum meter, cf. `listen_to_render_quantum`, and the
+/// backend info of the current sink, cf. `get_backend_info`
+pub(crate) struct NapiAudioContext(
+    Arc<AudioContext>,
+    usize,
+    Option<AudioWorkletNode>,
+    Option<BackendInfo>,
+);
+
+/// Host API and name of the device opened by the backend, resolved on first
+/// access and kept until the sink is changed as listing the devices is costly
+struct BackendInfo {
+    host_api: String,
+    device_name: Option<String>,
+}
+
+impl BackendInfo {
+    fn resolve(sink_id: &str) -> Self {
+        if sink_id == "none" {
+            return Self {
+                host_api: "none".to_string(),
+                device_name: None,
+            };
+        }
+
+        let host = backend_host();
+        let device_name = output_device(&host, sink_id).and_then(|device| device.name().ok());
+
+        Self {
+            host_api: host.id().name().to_string(),
+            device_name,
+        }
+    }
+}
+
+// Maximum number of pending render quantum notifications, further notifications
+// are dropped if the JS thread lags behind
+const RENDER_QUANTUM_QUEUE_SIZE: usize = 16;
 
 // for debug purpose
 // impl Drop for NapiAudioContext {
@@ -27,6 +68,9 @@ impl NapiAudioContext {
             Property::new("outputLatency")?.with_getter(get_output_latency),
             Property::new("sinkId")?.with_getter(get_sink_id),
             Property::new("setSinkId")?.with_method(set_sink_id),
+            Property::new("getDeviceSampleRate")?.with_method(get_device_sample_rate),
+            Property::new("getBackendInfo")?.with_method(get_backend_info),
+            Property::new("listen_to_render_quantum")?.with_method(listen_to_render_quantum),
             Property::new("resume")?.with_method(resume),
             Property::new("suspend")?.with_method(suspend),
             Property::new("close")?.with_method(close),
@@ -91,6 +135,21 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     let sink_id_js = js_options.get::<&str, JsString>("sinkId")?.unwrap();
     let sink_id_utf8 = sink_id_js.into_utf8()?.into_owned()?;
     let sink_id = sink_id_utf8.as_str().to_string();
+    // upstream falls back to the default device if the id is unknown
+    let sink_device = resolve_sink_id(&sink_id)?;
+
+    // Non-spec, requested size of the system-level audio callbacks
+    let output_buffer_size_js = js_options
+        .get::<&str, JsUnknown>("outputBufferSize")?
+        .unwrap();
+    let output_buffer_size = match output_buffer_size_js.get_type()? {
+        ValueType::Number => {
+            let size = output_buffer_size_js.coerce_to_number()?.get_uint32()? as usize;
+            Some(size)
+        }
+        ValueType::Null => None,
+        _ => unreachable!(),
+    };
 
     let audio_context_options = AudioContextOptions {
         latency_hint,
@@ -99,13 +158,17 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
         ..Default::default()
     };
 
+    let audio_context_options = match output_buffer_size {
+        Some(size) => with_output_buffer_size(audio_context_options, sink_device, size)?,
+        None => audio_context_options,
+    };
     let audio_context = AudioContext::new(audio_context_options);
     let worklet_id = crate::audio_worklet_node::allocate_process_call_channel();
 
     // -------------------------------------------------
     // Wrap context
     // -------------------------------------------------
-    let napi_audio_context = NapiAudioContext(Arc::new(audio_context), worklet_id);
+    let napi_audio_context = NapiAudioContext(Arc::new(audio_context), worklet_id, None, None);
     ctx.env.wrap(&mut js_this, napi_audio_context)?;
 
     js_this.define_properties(&[Property::new("Symbol.toStringTag")?
@@ -134,6 +197,22 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
 
 base_audio_context_impl!(NapiAudioContext);
 
+// "" is the default device and "none" is the non-spec `{ type: 'none' }` sink,
+// the device of any other sink id is returned so that the devices are listed
+// only once when creating the context
+fn resolve_sink_id(sink_id: &str) -> Result<Option<SinkDevice>> {
+    if sink_id.is_empty() || sink_id == "none" {
+        return Ok(None);
+    }
+
+    match sink_device(sink_id) {
+        Some(device) => Ok(Some(device)),
+        None => Err(napi::Error::from_reason(format!(
+            "NotFoundError - Failed to construct 'AudioContext': Unknown sinkId '{sink_id}'"
+        ))),
+    }
+}
+
 #[js_function]
 fn get_base_latency(ctx: CallContext) -> Result<JsNumber> {
     let js_this = ctx.this_unchecked::<JsObject>();
@@ -179,9 +258,139 @@ fn set_sink_id(ctx: CallContext) -> Result<JsUndefined> {
         return Err(napi::Error::from_reason(msg.to_string()));
     }
 
+    napi_obj.3 = None;
+
     ctx.env.get_undefined()
 }
 
+// Non spec, sample rate the output device runs the stream at. The stream is
+// opened at the rate of the context, which the device either supports, or the
+// system resamples to the rate of its default configuration. The rate of the
+// context is returned for the null sink.
+#[js_function]
+fn get_device_sample_rate(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_obj = ctx.env.unwrap::<NapiAudioContext>(&js_this)?;
+    let obj = napi_obj.unwrap();
+
+    let stream_sample_rate = cpal::SampleRate(obj.sample_rate() as u32);
+    let device_sample_rate = output_device(&backend_host(), &obj.sink_id()).and_then(|device| {
+        let supported = device.supported_output_configs().ok()?.any(|range| {
+            range.min_sample_rate() <= stream_sample_rate
+                && stream_sample_rate <= range.max_sample_rate()
+        });
+
+        if supported {
+            Some(stream_sample_rate)
+        } else {
+            device
+                .default_output_config()
+                .ok()
+                .map(|config| config.sample_rate())
+        }
+    });
+
+    let sample_rate = device_sample_rate.unwrap_or(stream_sample_rate).0;
+    ctx.env.create_double(sample_rate as f64)
+}
+
+// Non spec, host API and device opened by the backend, the sample rate and
+// buffer size are retrieved from the JS facade
+#[js_function]
+fn get_backend_info(ctx: CallContext) -> Result<JsObject> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_obj = ctx.env.unwrap::<NapiAudioContext>(&js_this)?;
+    let sink_id = napi_obj.unwrap().sink_id();
+    let BackendInfo {
+        host_api,
+        device_name,
+    } = napi_obj
+        .3
+        .get_or_insert_with(|| BackendInfo::resolve(&sink_id));
+
+    let mut info = ctx.env.create_object()?;
+    info.set_named_property("hostApi", ctx.env.create_string(host_api)?)?;
+
+    match device_name {
+        Some(name) => info.set_named_property("deviceName", ctx.env.create_string(name)?)?,
+        None => info.set_named_property("deviceName", ctx.env.get_null()?)?,
+    }
+
+    Ok(info)
+}
+
+/// Name of an output device, and its rank among the output devices sharing
+/// the same name
+type SinkDevice = (String, usize);
+
+// Device of the given sink id among the output devices, `None` if the sink id
+// is unknown
+fn sink_device(sink_id: &str) -> Option<SinkDevice> {
+    let outputs: Vec<_> = enumerate_devices_sync()
+        .into_iter()
+        .filter(|device| device.kind() == MediaDeviceInfoKind::AudioOutput)
+        .collect();
+
+    let index = outputs
+        .iter()
+        .position(|device| device.device_id() == sink_id)?;
+    let name = outputs[index].label().to_string();
+    let rank = outputs[..index]
+        .iter()
+        .filter(|device| device.label() == name)
+        .count();
+
+    Some((name, rank))
+}
+
+// Output device opened by the backend for the given sink, i.e. fallback to the
+// default device if the sink id is unknown, `None` for the null sink. The sink
+// id is resolved to the name of the device, as the host may not list the
+// devices in the same order.
+fn output_device(host: &cpal::Host, sink_id: &str) -> Option<cpal::Device> {
+    match sink_id {
+        "none" => None,
+        "" => sink_output_device(host, None),
+        _ => sink_output_device(host, sink_device(sink_id)),
+    }
+}
+
+fn sink_output_device(host: &cpal::Host, sink_device: Option<SinkDevice>) -> Option<cpal::Device> {
+    sink_device
+        .and_then(|(name, rank)| {
+            host.output_devices()
+                .ok()?
+                .filter(|device| device.name().is_ok_and(|n| n == name))
+                .nth(rank)
+        })
+        .or_else(|| host.default_output_device())
+}
+
+// Host selected by the cpal backend of web-audio-api, i.e. JACK if enabled and
+// running, the default host of the platform otherwise
+fn backend_host() -> cpal::Host {
+    #[cfg(feature = "jack")]
+    {
+        let jack_host = cpal::available_hosts()
+            .into_iter()
+            .find(|id| *id == cpal::HostId::Jack)
+            .and_then(|id| cpal::host_from_id(id).ok());
+
+        if let Some(host) = jack_host {
+            // if jack is not running, the host can't access devices
+            if host
+                .devices()
+                .map(|devices| devices.count() > 0)
+                .unwrap_or(false)
+            {
+                return host;
+            }
+        }
+    }
+
+    cpal::default_host()
+}
+
 #[js_function]
 fn resume(ctx: CallContext) -> Result<JsObject> {
     let js_this = ctx.this_unchecked::<JsObject>();
@@ -286,3 +495,130 @@ fn listen_to_events(ctx: CallContext) -> Result<JsUndefined> {
 
     ctx.env.get_undefined()
 }
+
+// Upstream has no option to specify the buffer size, but derives it from the
+// latency hint, i.e. `latency * sample_rate` rounded up to the next power of
+// two, so we request the latency that maps exactly to `size` at the rate the
+// stream is opened with: the requested rate, or the rate of the device.
+fn with_output_buffer_size(
+    mut options: AudioContextOptions,
+    sink_device: Option<SinkDevice>,
+    size: usize,
+) -> Result<AudioContextOptions> {
+    if !size.is_power_of_two() {
+        return Err(napi::Error::from_reason(format!(
+            "NotSupportedError - Invalid output buffer size: {size:?}, should be a power of two"
+        )));
+    }
+
+    // range of buffer sizes the backend may open the stream with, the null
+    // sink always renders blocks of 128 frames, at 48kHz by default
+    let (supported_sizes, device_sample_rate) = if options.sink_id == "none" {
+        (Some((128, 128)), 48000.)
+    } else {
+        // if there is no device, the context fails to open anyway
+        let Some(config) = sink_output_device(&backend_host(), sink_device)
+            .and_then(|device| device.default_output_config().ok())
+        else {
+            return Ok(options);
+        };
+
+        let supported_sizes = match config.buffer_size() {
+            cpal::SupportedBufferSize::Range { min, max } => Some((*min as usize, *max as usize)),
+            cpal::SupportedBufferSize::Unknown => None,
+        };
+
+        (supported_sizes, config.sample_rate().0 as f32)
+    };
+
+    // the backend clamps the buffer size to the range supported by the device
+    if let Some((min, max)) = supported_sizes {
+        if size < min || size > max {
+            return Err(napi::Error::from_reason(format!(
+                "NotSupportedError - The audio backend does not support an output buffer size of {size} frames (supported range is {min}..={max})"
+            )));
+        }
+    }
+
+    let sample_rate = options.sample_rate.unwrap_or(device_sample_rate);
+    // target just below `size` to be robust to floating point errors
+    let latency = (size as f64 - 0.5) / sample_rate as f64;
+    options.latency_hint = AudioContextLatencyCategory::Custom(latency);
+
+    Ok(options)
+}
+
+// Non spec, notify the JS facade at each render quantum, cf. onrenderquantum
+//
+// Disabling the notifications drops the message handler of the meter, and
+// thus releases the threadsafe function, so that the process can exit and no
+// notification is sent afterward
+#[js_function(1)]
+fn listen_to_render_quantum(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_obj = ctx.env.unwrap::<NapiAudioContext>(&js_this)?;
+    let enabled = ctx.get::<JsBoolean>(0)?.get_value()?;
+
+    if let Some(meter) = napi_obj.2.take() {
+        meter.port().clear_onmessage();
+        meter.port().post_message(RenderQuantumMeterStop);
+    }
+
+    if !enabled || napi_obj.0.state() == AudioContextState::Closed {
+        return ctx.env.get_undefined();
+    }
+
+    let options = AudioWorkletNodeOptions {
+        number_of_inputs: 0,
+        number_of_outputs: 1,
+        output_channel_count: vec![1],
+        processor_options: (),
+        ..AudioWorkletNodeOptions::default()
+    };
+    let meter = AudioWorkletNode::new::<RenderQuantumMeter>(napi_obj.unwrap(), options);
+    // the render thread only orders the graph again when an edge is added, a
+    // node which is not connected might never be rendered
+    meter.connect(&napi_obj.unwrap().destination());
+
+    let k_onrenderquantum =
+        crate::utils::get_symbol_for(ctx.env, "node-web-audio-api:onrenderquantum");
+    let render_quantum_cb = js_this.get_property(k_onrenderquantum).unwrap();
+    let mut render_quantum_tsfn = ctx.env.create_threadsafe_function(
+        &render_quantum_cb,
+        RENDER_QUANTUM_QUEUE_SIZE,
+        |ctx: ThreadSafeCallContext<u64>| Ok(vec![ctx.env.create_double(ctx.value as f64)?]),
+    )?;
+
+    // unref tsfn so they do not prevent the process to exit
+    let _ = render_quantum_tsfn.unref(ctx.env);
+
+    meter.port().set_onmessage(move |msg| {
+        if let Some(quantum) = msg.downcast_ref::<RenderQuantum>() {
+            // throttle, i.e. drop the notification if the queue is full
+            render_quantum_tsfn.call(Ok(quantum.frame), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    });
+
+    napi_obj.2 = Some(meter);
+
+    ctx.env.get_undefined()
+}
+
+// ----------------------------------------------------
+// Non-spec, number of output channels of the default output device, i.e. the
+// `destination.maxChannelCount` of an AudioContext opened on the default sink,
+// clamped to the renderer limit. The renderer limit is returned if there is
+// no output device.
+// ----------------------------------------------------
+#[js_function]
+pub(crate) fn napi_get_max_channel_count(ctx: CallContext) -> Result<JsNumber> {
+    let number_of_channels = backend_host()
+        .default_output_device()
+        .and_then(|device| device.default_output_config().ok())
+        .map_or(web_audio_api::MAX_CHANNELS, |config| {
+            usize::from(config.channels())
+        })
+        .min(web_audio_api::MAX_CHANNELS);
+
+    ctx.env.create_uint32(number_of_channels as u32)
+}
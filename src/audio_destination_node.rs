@@ -1,10 +1,31 @@
 use crate::*;
 use napi::*;
 use napi_derive::js_function;
+use std::sync::Arc;
 use web_audio_api::context::*;
 use web_audio_api::node::*;
 
-pub(crate) struct NapiAudioDestinationNode(AudioDestinationNode);
+use web_audio_api::worklet::{AudioWorkletNode, AudioWorkletNodeOptions};
+
+use crate::utils::{OutputLevels, OutputMeter, PeakLimiter};
+
+// The second field is the fade out gain stage, only inserted after the mute
+// stage while a `panic` is running (cf. `start_panic`).
+// The third field is the optional limiter inserted between the last gain
+// stage and the destination.
+// The fourth field is the mute stage: every node connected to the destination
+// is actually connected to this `GainNode`, which gives us a hook just before
+// the device to implement non-spec features (e.g. `AudioContext.muted`).
+// The fifth field is the meter fed with the exact signal reaching the
+// destination and the levels it computes, only installed once the levels are
+// read (cf. `getOutputLevel`).
+pub(crate) struct NapiAudioDestinationNode(
+    AudioDestinationNode,
+    Option<GainNode>,
+    Option<AudioWorkletNode>,
+    GainNode,
+    Option<(AudioWorkletNode, Arc<OutputLevels>)>,
+);
 
 // https://webaudio.github.io/web-audio-api/#AudioDestinationNode
 //
@@ -13,7 +34,12 @@ pub(crate) struct NapiAudioDestinationNode(AudioDestinationNode);
 impl NapiAudioDestinationNode {
     pub fn create_js_class(env: &Env) -> Result<JsFunction> {
         let interface = audio_node_interface![
-            Property::new("maxChannelCount")?.with_getter(get_max_channel_count)
+            Property::new("maxChannelCount")?.with_getter(get_max_channel_count),
+            Property::new("syncChannelConfig")?.with_method(sync_channel_config),
+            Property::new("setLimiter")?.with_method(set_limiter),
+            Property::new("getOutputLevel")?.with_method(get_output_level),
+            Property::new("startPanic")?.with_method(start_panic),
+            Property::new("endPanic")?.with_method(end_panic)
         ];
 
         env.define_class("AudioDestinationNode", constructor, &interface)
@@ -22,6 +48,147 @@ impl NapiAudioDestinationNode {
     pub fn unwrap(&self) -> &AudioDestinationNode {
         &self.0
     }
+
+    /// Node on which sources should be connected to reach the destination
+    pub fn input(&self) -> &GainNode {
+        &self.3
+    }
+
+    // last gain stage, connected to the limiter or to the destination
+    fn output_stage(&self) -> &GainNode {
+        self.1.as_ref().unwrap_or(&self.3)
+    }
+
+    // make sure the gain stages and meter mix their input exactly as the
+    // destination would
+    fn sync_channel_config(&self) {
+        let mut nodes: Vec<&dyn AudioNode> = vec![&self.3];
+
+        if let Some(panic_gain) = &self.1 {
+            nodes.push(panic_gain);
+        }
+
+        if let Some((meter, _)) = &self.4 {
+            nodes.push(meter);
+        }
+
+        for node in nodes {
+            node.set_channel_count(self.0.channel_count());
+            node.set_channel_count_mode(self.0.channel_count_mode());
+            node.set_channel_interpretation(self.0.channel_interpretation());
+        }
+    }
+
+    // the meter is connected to the last stage before the destination
+    fn connect_meter(&self) {
+        let Some((meter, _)) = &self.4 else {
+            return;
+        };
+
+        match &self.2 {
+            Some(limiter) => limiter.connect(meter),
+            None => self.output_stage().connect(meter),
+        };
+    }
+
+    // levels since the last call, the meter is installed on first call
+    fn take_output_levels(&mut self) -> Vec<(f32, f32)> {
+        if self.4.is_none() {
+            let levels = Arc::new(OutputLevels::default());
+            let options = AudioWorkletNodeOptions {
+                number_of_inputs: 1,
+                number_of_outputs: 0,
+                output_channel_count: vec![],
+                processor_options: Arc::clone(&levels),
+                ..AudioWorkletNodeOptions::default()
+            };
+            let meter = AudioWorkletNode::new::<OutputMeter>(self.0.context(), options);
+
+            self.4 = Some((meter, levels));
+            self.sync_channel_config();
+            self.connect_meter();
+        }
+
+        self.4.as_ref().unwrap().1.take()
+    }
+
+    // insert or remove the limiter between the last gain stage and the
+    // destination
+    fn set_limiter(&mut self, enabled: bool) {
+        if enabled == self.2.is_some() {
+            return;
+        }
+
+        self.output_stage().disconnect();
+
+        if enabled {
+            let options = AudioWorkletNodeOptions::default();
+            let limiter = AudioWorkletNode::new::<PeakLimiter>(self.0.context(), options);
+            self.output_stage().connect(&limiter);
+            limiter.connect(&self.0);
+            self.2 = Some(limiter);
+        } else {
+            if let Some(limiter) = self.2.take() {
+                limiter.disconnect();
+            }
+
+            self.output_stage().connect(&self.0);
+        }
+
+        self.connect_meter();
+    }
+
+    // insert or remove the fade out gain stage after the mute stage
+    fn set_panic_gain(&mut self, panic_gain: Option<GainNode>) {
+        self.3.disconnect();
+
+        if let Some(previous) = self.1.take() {
+            previous.disconnect();
+        }
+
+        if let Some(panic_gain) = &panic_gain {
+            self.3.connect(panic_gain);
+        }
+
+        self.1 = panic_gain;
+        self.sync_channel_config();
+
+        match &self.2 {
+            Some(limiter) => {
+                self.output_stage().connect(limiter);
+            }
+            None => {
+                self.output_stage().connect(&self.0);
+                self.connect_meter();
+            }
+        }
+    }
+
+    // fade out the output from `now` to `stop_time`, the fade out gain stage
+    // is kept until `end_panic`, i.e. until the stopped sources have ended
+    fn start_panic(&mut self, now: f64, stop_time: f64) {
+        match &self.1 {
+            Some(panic_gain) => {
+                panic_gain.gain().cancel_and_hold_at_time(now);
+            }
+            None => {
+                let panic_gain = GainNode::new(self.0.context(), GainOptions::default());
+                panic_gain.gain().set_value_at_time(1., now);
+                self.set_panic_gain(Some(panic_gain));
+            }
+        }
+
+        let panic_gain = self.1.as_ref().unwrap();
+        panic_gain
+            .gain()
+            .linear_ramp_to_value_at_time(0., stop_time);
+    }
+
+    fn end_panic(&mut self) {
+        if self.1.is_some() {
+            self.set_panic_gain(None);
+        }
+    }
 }
 
 #[js_function(1)]
@@ -36,22 +203,33 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     let audio_context_utf8_name = audio_context_name.into_utf8()?.into_owned()?;
     let audio_context_str = &audio_context_utf8_name[..];
 
-    let native_node = match audio_context_str {
+    let (native_node, mute_gain) = match audio_context_str {
         "AudioContext" => {
             let napi_audio_context = ctx.env.unwrap::<NapiAudioContext>(&js_audio_context)?;
             let audio_context = napi_audio_context.unwrap();
-            audio_context.destination() // this is also different from other audio nodes
+            // this is also different from other audio nodes
+            (audio_context.destination(), audio_context.create_gain())
         }
         "OfflineAudioContext" => {
             let napi_audio_context = ctx
                 .env
                 .unwrap::<NapiOfflineAudioContext>(&js_audio_context)?;
             let audio_context = napi_audio_context.unwrap();
-            audio_context.destination() // this is also different from other audio nodes
+            // this is also different from other audio nodes
+            (audio_context.destination(), audio_context.create_gain())
         }
         &_ => panic!("not supported"),
     };
 
+    mute_gain.connect(&native_node);
+
+    // Bind mute gain AudioParam to JS object
+    let native_param = mute_gain.gain().clone();
+    let napi_param = NapiAudioParam::new(native_param);
+    let mut js_obj = NapiAudioParam::create_js_object(ctx.env)?;
+    ctx.env.wrap(&mut js_obj, napi_param)?;
+    js_this.set_named_property("muteGain", &js_obj)?;
+
     js_this.define_properties(&[
         Property::new("context")?
             .with_value(&js_audio_context)
@@ -63,7 +241,8 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     ])?;
 
     // finalize instance creation
-    let napi_node = NapiAudioDestinationNode(native_node);
+    let napi_node = NapiAudioDestinationNode(native_node, None, None, mute_gain, None);
+    napi_node.sync_channel_config();
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -85,3 +264,62 @@ fn get_max_channel_count(ctx: CallContext) -> Result<JsNumber> {
 
     ctx.env.create_double(max_channel_count)
 }
+
+#[js_function]
+fn sync_channel_config(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioDestinationNode>(&js_this)?;
+    napi_node.sync_channel_config();
+
+    ctx.env.get_undefined()
+}
+
+#[js_function(1)]
+fn set_limiter(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioDestinationNode>(&js_this)?;
+
+    let enabled = ctx.get::<JsBoolean>(0)?.get_value()?;
+    napi_node.set_limiter(enabled);
+
+    ctx.env.get_undefined()
+}
+
+#[js_function]
+fn get_output_level(ctx: CallContext) -> Result<JsObject> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioDestinationNode>(&js_this)?;
+
+    let levels = napi_node.take_output_levels();
+    let mut js_levels = ctx.env.create_array_with_length(levels.len())?;
+
+    for (index, (peak, rms)) in levels.into_iter().enumerate() {
+        let mut js_level = ctx.env.create_object()?;
+        js_level.set_named_property("peak", ctx.env.create_double(peak as f64)?)?;
+        js_level.set_named_property("rms", ctx.env.create_double(rms as f64)?)?;
+        js_levels.set_element(index as u32, js_level)?;
+    }
+
+    Ok(js_levels)
+}
+
+#[js_function(2)]
+fn start_panic(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioDestinationNode>(&js_this)?;
+
+    let now = ctx.get::<JsNumber>(0)?.get_double()?;
+    let stop_time = ctx.get::<JsNumber>(1)?.get_double()?;
+    napi_node.start_panic(now, stop_time);
+
+    ctx.env.get_undefined()
+}
+
+#[js_function]
+fn end_panic(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiAudioDestinationNode>(&js_this)?;
+    napi_node.end_panic();
+
+    ctx.env.get_undefined()
+}
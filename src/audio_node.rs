@@ -75,6 +75,10 @@ macro_rules! audio_node_impl {
             ctx.env.create_double(channel_count)
         }
 
+        // Nodes with a fixed channel configuration, e.g. ChannelMergerNode and
+        // ChannelSplitterNode, panic with an "InvalidStateError - ..." message
+        // when the channel setters try to change it, the panic is caught by
+        // napi and rethrown as an InvalidStateError on the JS side.
         #[js_function(1)]
         fn set_channel_count(ctx: CallContext) -> Result<JsUndefined> {
             let js_this = ctx.this_unchecked::<JsObject>();
@@ -186,6 +190,16 @@ macro_rules! audio_node_impl {
                         .unwrap::<$crate::audio_destination_node::NapiAudioDestinationNode>(
                         &js_dest,
                     )?;
+                    let native_dest = napi_dest.input();
+                    native_src.connect_from_output_to_input(native_dest, output, input);
+                    // proper return value is handled on JS side
+                    ctx.env.get_undefined()
+                }
+                // non-spec nodes
+                "ChannelMatrixNode" => {
+                    let napi_dest = ctx
+                        .env
+                        .unwrap::<$crate::channel_matrix_node::NapiChannelMatrixNode>(&js_dest)?;
                     let native_dest = napi_dest.unwrap();
                     native_src.connect_from_output_to_input(native_dest, output, input);
                     // proper return value is handled on JS side
@@ -413,6 +427,28 @@ macro_rules! audio_node_impl {
                         .unwrap::<$crate::audio_destination_node::NapiAudioDestinationNode>(
                         &js_dest,
                     )?;
+                    let native_dest = napi_dest.input();
+
+                    if ctx.length == 3 {
+                        let output = ctx.get::<JsNumber>(1)?.get_double()? as usize;
+                        let input = ctx.get::<JsNumber>(2)?.get_double()? as usize;
+                        native_src.disconnect_dest_from_output_to_input(
+                            native_dest,
+                            output,
+                            input
+                        );
+                    } else if ctx.length == 2 {
+                        let output = ctx.get::<JsNumber>(1)?.get_double()? as usize;
+                        native_src.disconnect_dest_from_output(native_dest, output);
+                    } else {
+                        native_src.disconnect_dest(native_dest);
+                    }
+                }
+                // non-spec nodes
+                "ChannelMatrixNode" => {
+                    let napi_dest = ctx
+                        .env
+                        .unwrap::<$crate::channel_matrix_node::NapiChannelMatrixNode>(&js_dest)?;
                     let native_dest = napi_dest.unwrap();
 
                     if ctx.length == 3 {
@@ -18,6 +18,7 @@ use std::collections::HashMap;
 use std::option::Option;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
 /// Unique ID generator for AudioWorkletProcessors
 static INCREMENTING_ID: AtomicU32 = AtomicU32::new(0);
@@ -131,6 +132,13 @@ thread_local! {
     static HAS_THREAD_PRIO: Cell<bool> = const { Cell::new(false) };
 }
 
+/// Non-spec CPU budget of the processors of a given Worker, processors exceeding
+/// `max_duration` for `max_consecutive_overruns` render quanta are disabled
+struct ProcessBudget {
+    max_duration: Duration,
+    max_consecutive_overruns: u32,
+}
+
 struct WorkletAbruptCompletionResult {
     cmd: String,
     err: Error,
@@ -207,6 +215,19 @@ fn rebuild_io_layout(
     Ok(new_js_io)
 }
 
+/// Output silence, e.g. when the processor is not callable anymore
+fn clear_outputs(outputs: &'static [&'static [&'static [f32]]]) {
+    for output in outputs.iter() {
+        for channel in output.iter() {
+            let dst = channel.as_ptr() as *mut f32;
+
+            unsafe {
+                std::ptr::write_bytes(dst, 0, channel.len());
+            }
+        }
+    }
+}
+
 /// Recycle all processor buffers on Drop
 fn recycle_processor(env: &Env, processor: JsObject) -> Result<()> {
     let global = env.get_global()?;
@@ -244,17 +265,30 @@ fn recycle_processor(env: &Env, processor: JsObject) -> Result<()> {
     let k_worklet_params_cache = env.symbol_for("node-web-audio-api:worklet-params-cache")?;
     let js_params_cache = processor.get_property::<JsSymbol, JsObject>(k_worklet_params_cache)?;
 
-    let param_cache_128 = js_params_cache.get_element::<JsTypedArray>(0)?;
-    let _ = recycle_buffer.call1::<JsTypedArray, JsUndefined>(param_cache_128)?;
+    // cache is a map of param name -> [Float32Array(128), Float32Array(1)]
+    let param_names = js_params_cache.get_property_names()?;
+
+    for i in 0..param_names.get_array_length_unchecked()? {
+        let name = param_names.get_element::<JsString>(i)?;
+        let float32_arr_cache = js_params_cache.get_property::<JsString, JsObject>(name)?;
 
-    let param_cache_1 = js_params_cache.get_element::<JsTypedArray>(1)?;
-    let _ = recycle_buffer_1.call1::<JsTypedArray, JsUndefined>(param_cache_1)?;
+        let param_cache_128 = float32_arr_cache.get_element::<JsTypedArray>(0)?;
+        let _ = recycle_buffer.call1::<JsTypedArray, JsUndefined>(param_cache_128)?;
+
+        let param_cache_1 = float32_arr_cache.get_element::<JsTypedArray>(1)?;
+        let _ = recycle_buffer_1.call1::<JsTypedArray, JsUndefined>(param_cache_1)?;
+    }
 
     Ok(())
 }
 
 /// Handle a AudioWorkletProcessor::process call in the Worker
-fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArguments) -> Result<()> {
+fn process_audio_worklet(
+    env: &Env,
+    processors: &JsObject,
+    args: ProcessorArguments,
+    budget: Option<&ProcessBudget>,
+) -> Result<()> {
     let ProcessorArguments {
         id,
         inputs,
@@ -290,6 +324,7 @@ fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArgume
         .get_value()?;
 
     if !callable_process {
+        clear_outputs(outputs);
         let _ = tail_time_sender.send(false);
         return Ok(());
     }
@@ -360,14 +395,17 @@ fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArgume
                 js_params.set_named_property(name, float32_arr)?;
             }
 
+            let start = Instant::now();
             let res: Result<JsUnknown> =
                 process_method.apply3(processor, js_inputs, js_outputs, js_params);
+            let elapsed = start.elapsed();
 
             match res {
                 Ok(js_ret) => {
                     // Grab back new owned value processor and js_ouputs, has been
                     // consumed by `apply` call
-                    let processor = processors.get_named_property::<JsObject>(&id.to_string())?;
+                    let mut processor =
+                        processors.get_named_property::<JsObject>(&id.to_string())?;
                     let js_outputs =
                         processor.get_property::<JsSymbol, JsObject>(k_worklet_outputs)?;
 
@@ -390,8 +428,55 @@ fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArgume
                         }
                     }
 
-                    let ret = js_ret.coerce_to_bool()?.get_value()?;
-                    let _ = tail_time_sender.send(ret); // allowed to fail
+                    if let Some(budget) = budget {
+                        let k_worklet_overruns =
+                            env.symbol_for("node-web-audio-api:worklet-overruns")?;
+                        let overruns = if elapsed > budget.max_duration {
+                            processor
+                                .get_property::<JsSymbol, JsNumber>(k_worklet_overruns)?
+                                .get_uint32()?
+                                + 1
+                        } else {
+                            0
+                        };
+
+                        processor.set_property(k_worklet_overruns, env.create_uint32(overruns)?)?;
+
+                        if overruns >= budget.max_consecutive_overruns {
+                            completion = Some(WorkletAbruptCompletionResult {
+                                cmd: "node-web-audio-api:worklet:process-error".to_string(),
+                                err: Error::from_reason(format!(
+                                    "processor has been disabled as it exceeded its CPU budget ({}s) for {} consecutive render quanta",
+                                    budget.max_duration.as_secs_f64(),
+                                    overruns,
+                                )),
+                            });
+                        }
+                    }
+
+                    if completion.is_none() {
+                        let ret = js_ret.coerce_to_bool()?.get_value()?;
+
+                        // Non-spec, a processor without input requested its termination,
+                        // nothing can make it active again: tear it down, i.e. never call
+                        // it again, and notify the node with an `ended` event. Processors
+                        // with inputs are kept alive as they can be revived by their inputs
+                        if !ret && inputs.is_empty() {
+                            let value = env.get_boolean(false)?;
+                            processor.set_property(k_worklet_callable_process, value)?;
+
+                            let k_worklet_queue_task =
+                                env.symbol_for("node-web-audio-api:worklet-queue-task")?;
+                            let queue_task = processor
+                                .get_property::<JsSymbol, JsFunction>(k_worklet_queue_task)?;
+                            let js_cmd =
+                                env.create_string("node-web-audio-api:worklet:process-ended")?;
+                            let _: Result<JsUnknown> =
+                                queue_task.apply2(processor, js_cmd, env.get_null()?);
+                        }
+
+                        let _ = tail_time_sender.send(ret); // allowed to fail
+                    }
                 }
                 Err(err) => {
                     completion = Some(WorkletAbruptCompletionResult {
@@ -419,6 +504,7 @@ fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArgume
         // processor can be removed from graph (?)
         let value = env.get_boolean(false)?;
         processor.set_property(k_worklet_callable_process, value)?;
+        clear_outputs(outputs);
         // set active source flag to false, same semantic as tail time
         // https://webaudio.github.io/web-audio-api/#active-source
         let _ = tail_time_sender.send(false);
@@ -433,7 +519,7 @@ fn process_audio_worklet(env: &Env, processors: &JsObject, args: ProcessorArgume
 }
 
 /// The entry point into Rust from the Worker
-#[js_function(2)]
+#[js_function(4)]
 pub(crate) fn run_audio_worklet_global_scope(ctx: CallContext) -> Result<JsUndefined> {
     // Set thread priority to highest, if not done already
     if !HAS_THREAD_PRIO.replace(true) {
@@ -445,6 +531,16 @@ pub(crate) fn run_audio_worklet_global_scope(ctx: CallContext) -> Result<JsUndef
     let worklet_id = ctx.get::<JsNumber>(0)?.get_uint32()? as usize;
     // List of registered processors
     let processors = ctx.get::<JsObject>(1)?;
+    // Non-spec CPU budget of the processors, disabled if max duration is zero
+    let max_duration = ctx.get::<JsNumber>(2)?.get_double()?;
+    let budget = if max_duration > 0. {
+        Some(ProcessBudget {
+            max_duration: Duration::from_secs_f64(max_duration),
+            max_consecutive_overruns: ctx.get::<JsNumber>(3)?.get_uint32()?,
+        })
+    } else {
+        None
+    };
 
     // Poll for incoming commands and yield back to the event loop if there are none.
     // recv_timeout is not an option due to realtime safety, see discussion of
@@ -460,7 +556,7 @@ pub(crate) fn run_audio_worklet_global_scope(ctx: CallContext) -> Result<JsUndef
                 processors.delete_named_property(&id.to_string())?;
             }
             WorkletCommand::Process(args) => {
-                process_audio_worklet(ctx.env, &processors, args)?;
+                process_audio_worklet(ctx.env, &processors, args, budget.as_ref())?;
             }
         }
     }
@@ -522,11 +618,17 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
         .get::<&str, JsTypedArray>("outputChannelCount")?
         .unwrap();
     let output_channel_count_value = output_channel_count_js.into_value()?;
-    let output_channel_count_u32: &[u32] = output_channel_count_value.as_ref();
-    let output_channel_count: Vec<usize> = output_channel_count_u32
-        .iter()
-        .map(|&v| v as usize)
-        .collect();
+    // the underlying data pointer of an empty typed array may be null, which
+    // is not a valid pointer to build a slice from
+    let output_channel_count: Vec<usize> = if output_channel_count_value.length == 0 {
+        vec![]
+    } else {
+        let output_channel_count_u32: &[u32] = output_channel_count_value.as_ref();
+        output_channel_count_u32
+            .iter()
+            .map(|&v| v as usize)
+            .collect()
+    };
 
     let mut parameter_data = HashMap::<String, f64>::new();
     let parameter_data_js = options_js.get_named_property::<JsObject>("parameterData")?;
@@ -7,6 +7,7 @@ macro_rules! base_audio_context_interface {
             Property::new("listener")?.with_getter(get_listener),
             Property::new("state")?.with_getter(get_state),
             Property::new("decodeAudioData")?.with_method(decode_audio_data),
+            Property::new("abortDecoding")?.with_method(abort_decoding),
             $($e,)*
         ]
     }
@@ -74,36 +75,173 @@ macro_rules! base_audio_context_impl {
         // METHODS
         // ----------------------------------------------------
 
-        #[js_function(1)]
+        #[js_function(6)]
         fn decode_audio_data(ctx: CallContext) -> Result<JsObject> {
             let js_this = ctx.this_unchecked::<JsObject>();
             let napi_obj = ctx.env.unwrap::<$napi_struct>(&js_this)?;
             let clone = Arc::clone(&napi_obj.0);
 
             let js_buffer = ctx.get::<JsArrayBuffer>(0)?.into_value()?;
-            let cursor = Cursor::new(js_buffer.to_vec());
+            let data = js_buffer.to_vec();
+            // non spec, kept to name the format of the data in decoding errors
+            let header = data[..data.len().min($crate::decoding::SNIFF_LENGTH)].to_vec();
+            // non spec, decode a given track of the container
+            let track_index_js = ctx.get::<JsUnknown>(1)?;
+            let track_index = match track_index_js.get_type()? {
+                ValueType::Number => {
+                    Some(track_index_js.coerce_to_number()?.get_uint32()? as usize)
+                }
+                _ => None,
+            };
+            // non spec, id of the decoding if started with an `AbortSignal`
+            let decoding_id_js = ctx.get::<JsUnknown>(2)?;
+            let decoding_id = match decoding_id_js.get_type()? {
+                ValueType::Number => Some(decoding_id_js.coerce_to_number()?.get_uint32()?),
+                _ => None,
+            };
+            // non spec, resolve with the description of the decoded track
+            let with_metadata = ctx.get::<JsBoolean>(3)?.get_value()?;
+            // non spec, rate of the decoded buffer, differs from the rate of
+            // the context when the graph is oversampled, cf. renderOversample
+            let sample_rate = ctx.get::<JsNumber>(4)?.get_double()? as f32;
+            // non spec, decode into the given buffer, the data is decoded into a
+            // scratch copy so that the buffer can still be used meanwhile, and
+            // copied into the buffer once decoded
+            let js_target = ctx.get::<JsUnknown>(5)?;
+            let (target, target_ref) = match js_target.get_type()? {
+                ValueType::Object => {
+                    let js_target = js_target.coerce_to_object()?;
+                    let target = ctx
+                        .env
+                        .unwrap::<NapiAudioBuffer>(&js_target)?
+                        .unwrap()
+                        .clone();
+                    (Some(target), Some(ctx.env.create_reference(js_target)?))
+                }
+                _ => (None, None),
+            };
+            // register synchronously so that the decoding can be aborted before
+            // it is actually started
+            let aborted = decoding_id.map($crate::decoding::register_decoding);
 
             ctx.env.execute_tokio_future(
-                async move { Ok(clone.decode_audio_data_sync(cursor)) },
-                |&mut env, result| {
+                async move {
+                    let not_aborted = std::sync::atomic::AtomicBool::new(false);
+                    let aborted_flag = aborted.as_deref().unwrap_or(&not_aborted);
+
+                    // `None` if the data has been decoded into the target
+                    let (result, target) = match target {
+                        Some(mut target) => {
+                            let result = $crate::decoding::decode_track_into(
+                                data,
+                                track_index,
+                                &mut target,
+                                aborted_flag,
+                            )
+                            .map(|info| (None, Some(info).filter(|_| with_metadata)));
+
+                            (result, Some(target))
+                        }
+                        None => {
+                            let result = match (track_index, &aborted, with_metadata) {
+                                (None, None, false) if sample_rate == clone.sample_rate() => clone
+                                    .decode_audio_data_sync(Cursor::new(data))
+                                    .map(|audio_buffer| (Some(audio_buffer), None)),
+                                (track_index, _, _) => $crate::decoding::decode_track(
+                                    data,
+                                    track_index,
+                                    sample_rate,
+                                    aborted_flag,
+                                )
+                                .map(|(audio_buffer, info)| {
+                                    (Some(audio_buffer), Some(info).filter(|_| with_metadata))
+                                }),
+                            };
+
+                            (result, None)
+                        }
+                    };
+
+                    if let Some(decoding_id) = decoding_id {
+                        $crate::decoding::unregister_decoding(decoding_id);
+                    }
+
+                    Ok((result, target))
+                },
+                move |&mut env, (result, target)| {
+                    // copy the decoded data into the target, which is left
+                    // untouched if the decoding failed
+                    let js_target = match (target_ref, target) {
+                        (Some(mut target_ref), Some(decoded)) => {
+                            let js_target: JsObject = env.get_reference_value(&target_ref)?;
+                            target_ref.unref(env)?;
+
+                            if result.is_ok() {
+                                let target =
+                                    env.unwrap::<NapiAudioBuffer>(&js_target)?.unwrap_mut();
+
+                                for channel_number in 0..decoded.number_of_channels() {
+                                    target.copy_to_channel(
+                                        decoded.get_channel_data(channel_number),
+                                        channel_number,
+                                    );
+                                }
+                            }
+
+                            Some(js_target)
+                        }
+                        _ => None,
+                    };
+
                     match result {
-                        Ok(audio_buffer) => {
-                            // create js audio buffer instance
-                            let store_ref: &mut napi::Ref<()> = env.get_instance_data()?.unwrap();
-                            let store: JsObject = env.get_reference_value(store_ref)?;
-                            let ctor: JsFunction = store.get_named_property("AudioBuffer")?;
-                            let js_audio_buffer = ctor.new_instance(&[env.get_null()?])?;
-                            // populate with native audio buffer
-                            let napi_audio_buffer =
-                                env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
-                            napi_audio_buffer.insert(audio_buffer);
-
-                            Ok(js_audio_buffer)
+                        Ok((audio_buffer, info)) => {
+                            let js_audio_buffer = match audio_buffer {
+                                Some(audio_buffer) => {
+                                    // create js audio buffer instance
+                                    let store_ref: &mut napi::Ref<()> =
+                                        env.get_instance_data()?.unwrap();
+                                    let store: JsObject = env.get_reference_value(store_ref)?;
+                                    let ctor: JsFunction =
+                                        store.get_named_property("AudioBuffer")?;
+                                    let js_audio_buffer = ctor.new_instance(&[env.get_null()?])?;
+                                    // populate with native audio buffer
+                                    let napi_audio_buffer =
+                                        env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
+                                    napi_audio_buffer.insert(audio_buffer);
+
+                                    js_audio_buffer
+                                }
+                                // decoded in place
+                                None => js_target.unwrap(),
+                            };
+
+                            match info {
+                                Some(info) => {
+                                    let mut js_result = env.create_object()?;
+                                    js_result.set_named_property("audioBuffer", js_audio_buffer)?;
+                                    js_result
+                                        .set_named_property("metadata", info.to_js_object(&env)?)?;
+
+                                    Ok(js_result)
+                                }
+                                None => Ok(js_audio_buffer),
+                            }
                         }
-                        Err(e) => Err(napi::Error::from_reason(e.to_string())),
+                        Err(e) => Err(napi::Error::from_reason($crate::decoding::describe_error(
+                            &header, e,
+                        ))),
                     }
                 },
             )
         }
+
+        // non spec, abort a decoding started with an `AbortSignal`
+        #[js_function(1)]
+        fn abort_decoding(ctx: CallContext) -> Result<JsUndefined> {
+            let decoding_id = ctx.get::<JsNumber>(0)?.get_uint32()?;
+            $crate::decoding::abort_decoding(decoding_id);
+
+            ctx.env.get_undefined()
+        }
     };
 }
@@ -0,0 +1,138 @@
+use crate::*;
+use napi::*;
+use napi_derive::js_function;
+use web_audio_api::node::*;
+use web_audio_api::worklet::{AudioWorkletNode, AudioWorkletNodeOptions};
+
+use crate::utils::{ChannelMatrix, ChannelMatrixOptions};
+
+// Non-spec node mixing its input channels into its output channels with an
+// explicit gain matrix
+//
+// The node is backed by an AudioWorkletNode running a `ChannelMatrix` on the
+// render thread. Its input is configured with an explicit channel count and a
+// discrete interpretation so that the matrix sees the input channels as is.
+pub(crate) struct NapiChannelMatrixNode {
+    node: AudioWorkletNode,
+}
+
+impl NapiChannelMatrixNode {
+    pub fn create_js_class(env: &Env) -> Result<JsFunction> {
+        let interface = audio_node_interface![];
+
+        env.define_class("ChannelMatrixNode", constructor, &interface)
+    }
+
+    // @note: this is used in audio_node.rs for the connect / disconnect macros
+    pub fn unwrap(&mut self) -> &mut AudioWorkletNode {
+        &mut self.node
+    }
+}
+
+fn assert_valid_channel_count(name: &str, value: usize) -> Result<()> {
+    if value == 0 || value > web_audio_api::MAX_CHANNELS {
+        return Err(napi::Error::from_reason(format!(
+            "NotSupportedError - {name} ({value}) is outside of the range [1, {}]",
+            web_audio_api::MAX_CHANNELS
+        )));
+    }
+
+    Ok(())
+}
+
+#[js_function(2)]
+fn constructor(ctx: CallContext) -> Result<JsUndefined> {
+    let mut js_this = ctx.this_unchecked::<JsObject>();
+
+    let js_audio_context = ctx.get::<JsObject>(0)?;
+
+    // --------------------------------------------------------
+    // Parse ChannelMatrixOptions
+    // by bindings construction all fields are populated on the JS side
+    // --------------------------------------------------------
+    let js_options = ctx.get::<JsObject>(1)?;
+
+    let input_channel_count = js_options
+        .get::<&str, JsNumber>("inputChannelCount")?
+        .unwrap()
+        .get_double()? as usize;
+    assert_valid_channel_count("inputChannelCount", input_channel_count)?;
+
+    let output_channel_count = js_options
+        .get::<&str, JsNumber>("outputChannelCount")?
+        .unwrap()
+        .get_double()? as usize;
+    assert_valid_channel_count("outputChannelCount", output_channel_count)?;
+
+    let matrix_js = js_options.get::<&str, JsTypedArray>("matrix")?.unwrap();
+    let matrix_value = matrix_js.into_value()?;
+    let matrix: &[f32] = matrix_value.as_ref();
+
+    if matrix.len() != input_channel_count * output_channel_count {
+        return Err(napi::Error::from_reason(format!(
+            "NotSupportedError - The length of the matrix ({}) should be outputChannelCount * inputChannelCount ({})",
+            matrix.len(),
+            input_channel_count * output_channel_count,
+        )));
+    }
+
+    let options = AudioWorkletNodeOptions {
+        number_of_inputs: 1,
+        number_of_outputs: 1,
+        output_channel_count: vec![output_channel_count],
+        processor_options: ChannelMatrixOptions {
+            input_channel_count,
+            matrix: matrix.to_vec(),
+        },
+        audio_node_options: AudioNodeOptions {
+            channel_count: input_channel_count,
+            channel_count_mode: ChannelCountMode::Explicit,
+            channel_interpretation: ChannelInterpretation::Discrete,
+        },
+        ..AudioWorkletNodeOptions::default()
+    };
+
+    // --------------------------------------------------------
+    // Create native ChannelMatrixNode
+    // --------------------------------------------------------
+    let audio_context_name =
+        js_audio_context.get_named_property::<JsString>("Symbol.toStringTag")?;
+    let audio_context_utf8_name = audio_context_name.into_utf8()?.into_owned()?;
+    let audio_context_str = &audio_context_utf8_name[..];
+
+    let native_node = match audio_context_str {
+        "AudioContext" => {
+            let napi_audio_context = ctx.env.unwrap::<NapiAudioContext>(&js_audio_context)?;
+            let audio_context = napi_audio_context.unwrap();
+            AudioWorkletNode::new::<ChannelMatrix>(audio_context, options)
+        }
+        "OfflineAudioContext" => {
+            let napi_audio_context = ctx
+                .env
+                .unwrap::<NapiOfflineAudioContext>(&js_audio_context)?;
+            let audio_context = napi_audio_context.unwrap();
+            AudioWorkletNode::new::<ChannelMatrix>(audio_context, options)
+        }
+        &_ => unreachable!(),
+    };
+
+    // --------------------------------------------------------
+    // Finalize instance creation
+    // --------------------------------------------------------
+    js_this.define_properties(&[
+        Property::new("context")?
+            .with_value(&js_audio_context)
+            .with_property_attributes(PropertyAttributes::Enumerable),
+        // this must be put on the instance and not in the prototype to be reachable
+        Property::new("Symbol.toStringTag")?
+            .with_value(&ctx.env.create_string("ChannelMatrixNode")?)
+            .with_property_attributes(PropertyAttributes::Static),
+    ])?;
+
+    let napi_node = NapiChannelMatrixNode { node: native_node };
+    ctx.env.wrap(&mut js_this, napi_node)?;
+
+    ctx.env.get_undefined()
+}
+
+audio_node_impl!(NapiChannelMatrixNode);
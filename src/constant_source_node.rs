@@ -22,7 +22,7 @@ use napi::*;
 use napi_derive::js_function;
 use web_audio_api::node::*;
 
-pub(crate) struct NapiConstantSourceNode(ConstantSourceNode);
+pub(crate) struct NapiConstantSourceNode(ConstantSourceNode, crate::utils::RenderStartTime);
 
 // for debug purpose
 // impl Drop for NapiConstantSourceNode {
@@ -35,7 +35,8 @@ impl NapiConstantSourceNode {
     pub fn create_js_class(env: &Env) -> Result<JsFunction> {
         let interface = audio_node_interface![
             Property::new("start")?.with_method(start),
-            Property::new("stop")?.with_method(stop)
+            Property::new("stop")?.with_method(stop),
+            Property::new("actualStartTime")?.with_method(actual_start_time)
         ];
 
         env.define_class("ConstantSourceNode", constructor, &interface)
@@ -116,8 +117,15 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
             .with_property_attributes(PropertyAttributes::Static),
     ])?;
 
+    // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+    let track_actual_start_time = js_options
+        .get::<&str, JsBoolean>("trackActualStartTime")?
+        .unwrap()
+        .get_value()?;
+    let start_time = crate::utils::RenderStartTime::new(track_actual_start_time);
+
     // finalize instance creation
-    let napi_node = NapiConstantSourceNode(native_node);
+    let napi_node = NapiConstantSourceNode(native_node, start_time);
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -167,6 +175,8 @@ fn start(ctx: CallContext) -> Result<JsUndefined> {
 
     let when = ctx.get::<JsNumber>(0)?.get_double()?;
     node.start_at(when);
+    // cf. AudioScheduledSourceNode::getActualStartTime
+    napi_node.1.record(napi_node.0.context(), when);
 
     ctx.env.get_undefined()
 }
@@ -182,3 +192,15 @@ fn stop(ctx: CallContext) -> Result<JsUndefined> {
 
     ctx.env.get_undefined()
 }
+
+// non spec, cf. AudioScheduledSourceNode::getActualStartTime
+#[js_function]
+fn actual_start_time(ctx: CallContext) -> Result<JsUnknown> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiConstantSourceNode>(&js_this)?;
+
+    match napi_node.1.get() {
+        Some(time) => ctx.env.create_double(time).map(|v| v.into_unknown()),
+        None => ctx.env.get_null().map(|v| v.into_unknown()),
+    }
+}
@@ -0,0 +1,464 @@
+use std::error::Error;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use napi::*;
+use napi_derive::js_function;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, Track};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use web_audio_api::AudioBuffer;
+
+type DecodingError = Box<dyn Error + Send + Sync>;
+
+/// Description of a track found in a media container, cf. `probe` and the
+/// `metadata` option of `decodeAudioData`
+pub(crate) struct TrackInfo {
+    index: usize,
+    codec: &'static str,
+    number_of_channels: Option<usize>,
+    sample_rate: Option<u32>,
+    bit_depth: Option<u32>,
+    duration: Option<f64>,
+}
+
+impl TrackInfo {
+    fn new(index: usize, track: &Track) -> Self {
+        let params = &track.codec_params;
+        let codec = symphonia::default::get_codecs()
+            .get_codec(params.codec)
+            .map_or("unknown", |descriptor| descriptor.short_name);
+        let duration = match (params.n_frames, params.sample_rate) {
+            (Some(n_frames), Some(sample_rate)) => Some(n_frames as f64 / sample_rate as f64),
+            _ => None,
+        };
+
+        Self {
+            index,
+            codec,
+            number_of_channels: params.channels.map(|channels| channels.count()),
+            sample_rate: params.sample_rate,
+            bit_depth: params.bits_per_sample,
+            duration,
+        }
+    }
+
+    pub(crate) fn to_js_object(&self, env: &Env) -> Result<JsObject> {
+        let mut js_track = env.create_object()?;
+        js_track.set_named_property("index", env.create_uint32(self.index as u32)?)?;
+        js_track.set_named_property("codec", env.create_string(self.codec)?)?;
+
+        match self.number_of_channels {
+            Some(value) => {
+                js_track.set_named_property("numberOfChannels", env.create_uint32(value as u32)?)?
+            }
+            None => js_track.set_named_property("numberOfChannels", env.get_null()?)?,
+        }
+
+        match self.sample_rate {
+            Some(value) => js_track.set_named_property("sampleRate", env.create_uint32(value)?)?,
+            None => js_track.set_named_property("sampleRate", env.get_null()?)?,
+        }
+
+        match self.bit_depth {
+            Some(value) => js_track.set_named_property("bitDepth", env.create_uint32(value)?)?,
+            None => js_track.set_named_property("bitDepth", env.get_null()?)?,
+        }
+
+        match self.duration {
+            Some(value) => js_track.set_named_property("duration", env.create_double(value)?)?,
+            None => js_track.set_named_property("duration", env.get_null()?)?,
+        }
+
+        Ok(js_track)
+    }
+}
+
+// Non-spec, decodings started with an `AbortSignal`, cf. `abort_decoding`
+static ABORT_FLAGS: Mutex<Vec<(u32, Arc<AtomicBool>)>> = Mutex::new(Vec::new());
+
+/// Register the decoding `id` so that it can be aborted, the flag is checked by
+/// `decode_track` between each packet
+pub(crate) fn register_decoding(id: u32) -> Arc<AtomicBool> {
+    let aborted = Arc::new(AtomicBool::new(false));
+    ABORT_FLAGS.lock().unwrap().push((id, Arc::clone(&aborted)));
+
+    aborted
+}
+
+pub(crate) fn unregister_decoding(id: u32) {
+    ABORT_FLAGS
+        .lock()
+        .unwrap()
+        .retain(|(other, _)| *other != id);
+}
+
+/// Abort the decoding `id`, no-op if it is already finished
+pub(crate) fn abort_decoding(id: u32) {
+    ABORT_FLAGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(other, _)| *other == id)
+        .for_each(|(_, aborted)| aborted.store(true, Ordering::Relaxed));
+}
+
+fn open_format(data: Vec<u8>) -> std::result::Result<Box<dyn FormatReader>, DecodingError> {
+    let stream = MediaSourceStream::new(Box::new(Cursor::new(data)), Default::default());
+    let probed = symphonia::default::get_probe().format(
+        &Hint::new(),
+        stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+
+    Ok(probed.format)
+}
+
+fn probe_tracks(data: Vec<u8>) -> std::result::Result<Vec<TrackInfo>, DecodingError> {
+    let format = open_format(data)?;
+
+    let tracks = format
+        .tracks()
+        .iter()
+        .enumerate()
+        .map(|(index, track)| TrackInfo::new(index, track))
+        .collect();
+
+    Ok(tracks)
+}
+
+/// Decode the track at index `track_index` of the given container, or its
+/// default track if `None`, and resample it to `sample_rate`. This mimics
+/// `BaseAudioContext::decode_audio_data_sync` which always decodes the default
+/// track, but can be interrupted by setting `aborted`. The description of the
+/// decoded track is returned along with the buffer.
+pub(crate) fn decode_track(
+    data: Vec<u8>,
+    track_index: Option<usize>,
+    sample_rate: f32,
+    aborted: &AtomicBool,
+) -> std::result::Result<(AudioBuffer, TrackInfo), DecodingError> {
+    let mut channels: Vec<Vec<f32>> = vec![];
+
+    let (info, file_sample_rate) = decode_packets(
+        data,
+        track_index,
+        aborted,
+        |samples, number_of_frames, _| {
+            channels.resize_with(samples.len() / number_of_frames, Vec::new);
+            channels
+                .iter_mut()
+                .zip(samples.chunks(number_of_frames))
+                .for_each(|(channel, data)| channel.extend_from_slice(data));
+
+            Ok(())
+        },
+    )?;
+
+    if channels.is_empty() {
+        return Ok((AudioBuffer::from(vec![vec![]], sample_rate), info));
+    }
+
+    let channels = resample(
+        channels,
+        file_sample_rate.unwrap_or(sample_rate),
+        sample_rate,
+    );
+    Ok((AudioBuffer::from(channels, sample_rate), info))
+}
+
+/// Same as `decode_track` but the decoded data is written into `target`, at
+/// its sample rate. The frames are written as they are decoded if no
+/// resampling is needed, otherwise the resampled data is copied once decoded.
+/// Fails with a NotSupportedError if the decoded data does not match the
+/// number of channels or the length of `target`.
+pub(crate) fn decode_track_into(
+    data: Vec<u8>,
+    track_index: Option<usize>,
+    target: &mut AudioBuffer,
+    aborted: &AtomicBool,
+) -> std::result::Result<TrackInfo, DecodingError> {
+    let sample_rate = target.sample_rate();
+    let number_of_channels = target.number_of_channels();
+    let length = target.length();
+    let mismatch = || -> DecodingError {
+        format!(
+            "NotSupportedError - the decoded data does not match the provided AudioBuffer ({number_of_channels} channels, {length} frames at {sample_rate}Hz)"
+        )
+        .into()
+    };
+
+    let mut offset = 0;
+    // only used if the data must be resampled
+    let mut channels: Vec<Vec<f32>> = vec![];
+
+    let (info, file_sample_rate) = decode_packets(
+        data,
+        track_index,
+        aborted,
+        |samples, number_of_frames, rate| {
+            if samples.len() / number_of_frames != number_of_channels {
+                return Err(mismatch());
+            }
+
+            if needs_resampling(rate, sample_rate) {
+                channels.resize_with(number_of_channels, Vec::new);
+                channels
+                    .iter_mut()
+                    .zip(samples.chunks(number_of_frames))
+                    .for_each(|(channel, data)| channel.extend_from_slice(data));
+            } else {
+                if offset + number_of_frames > length {
+                    return Err(mismatch());
+                }
+
+                samples
+                    .chunks(number_of_frames)
+                    .enumerate()
+                    .for_each(|(channel_number, data)| {
+                        target.get_channel_data_mut(channel_number)[offset..][..number_of_frames]
+                            .copy_from_slice(data);
+                    });
+                offset += number_of_frames;
+            }
+
+            Ok(())
+        },
+    )?;
+
+    if !channels.is_empty() {
+        let channels = resample(
+            channels,
+            file_sample_rate.unwrap_or(sample_rate),
+            sample_rate,
+        );
+
+        if channels[0].len() != length {
+            return Err(mismatch());
+        }
+
+        channels
+            .iter()
+            .enumerate()
+            .for_each(|(channel_number, data)| target.copy_to_channel(data, channel_number));
+        offset = length;
+    }
+
+    if offset != length {
+        return Err(mismatch());
+    }
+
+    Ok(info)
+}
+
+// Decode the packets of the track at index `track_index`, or of the default
+// track, and give the planar samples of each decoded packet to `write` along
+// with its number of frames and its sample rate. Returns the description of
+// the track and the sample rate of the decoded data, if any.
+fn decode_packets(
+    data: Vec<u8>,
+    track_index: Option<usize>,
+    aborted: &AtomicBool,
+    mut write: impl FnMut(&[f32], usize, f32) -> std::result::Result<(), DecodingError>,
+) -> std::result::Result<(TrackInfo, Option<f32>), DecodingError> {
+    let mut format = open_format(data)?;
+    let number_of_tracks = format.tracks().len();
+
+    let track_index = match track_index {
+        Some(track_index) if track_index >= number_of_tracks => {
+            return Err(format!(
+                "IndexSizeError - track index {track_index} is out of range (number of tracks: {number_of_tracks})"
+            )
+            .into())
+        }
+        Some(track_index) => track_index,
+        None => {
+            let default_track = format
+                .default_track()
+                .ok_or("no track found in the container")?;
+            format
+                .tracks()
+                .iter()
+                .position(|track| track.id == default_track.id)
+                .unwrap()
+        }
+    };
+    let track = &format.tracks()[track_index];
+    let mut info = TrackInfo::new(track_index, track);
+    let track_id = track.id;
+    let decoder_opts = DecoderOptions { verify: true };
+    let mut decoder = symphonia::default::get_codecs().make(&track.codec_params, &decoder_opts)?;
+
+    let mut file_sample_rate = None;
+
+    loop {
+        // drop the partially decoded data
+        if aborted.load(Ordering::Relaxed) {
+            return Err("AbortError - the decoding was aborted".into());
+        }
+
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break
+            }
+            Err(err) => return Err(Box::new(err)),
+        };
+
+        // skip packets from other tracks
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // recoverable errors, continue with the next packet
+            Err(SymphoniaError::DecodeError(_)) | Err(SymphoniaError::IoError(_)) => continue,
+            Err(err) => return Err(Box::new(err)),
+        };
+
+        let spec = *decoded.spec();
+        let number_of_frames = decoded.frames();
+        file_sample_rate = Some(spec.rate as f32);
+        // the codec parameters are not always known before decoding
+        info.sample_rate = Some(spec.rate);
+        info.number_of_channels = Some(spec.channels.count());
+
+        if number_of_frames == 0 {
+            continue;
+        }
+
+        let mut samples = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        samples.copy_planar_ref(decoded);
+
+        write(
+            &samples.samples()[..number_of_frames * spec.channels.count()],
+            number_of_frames,
+            spec.rate as f32,
+        )?;
+    }
+
+    Ok((info, file_sample_rate))
+}
+
+// Same tolerance as the resampling applied by `decode_audio_data_sync`
+fn needs_resampling(source_sample_rate: f32, target_sample_rate: f32) -> bool {
+    (source_sample_rate - target_sample_rate).abs() > 0.1
+}
+
+// Linear interpolation keeping the first and last samples intact, same as the
+// resampling applied by `decode_audio_data_sync`
+fn resample(
+    channels: Vec<Vec<f32>>,
+    source_sample_rate: f32,
+    target_sample_rate: f32,
+) -> Vec<Vec<f32>> {
+    let source_length = channels[0].len();
+
+    if !needs_resampling(source_sample_rate, target_sample_rate) || source_length == 0 {
+        return channels;
+    }
+
+    let ratio = target_sample_rate as f64 / source_sample_rate as f64;
+    let target_length = (source_length as f64 * ratio).ceil() as usize;
+
+    channels
+        .iter()
+        .map(|channel| {
+            (0..target_length)
+                .map(|i| {
+                    let position = i as f64 / (target_length - 1).max(1) as f64;
+                    let playhead = position * (source_length - 1) as f64;
+                    let prev_index = playhead.floor() as usize;
+                    let next_index = (prev_index + 1).min(source_length - 1);
+                    let k = (playhead - playhead.floor()) as f32;
+
+                    (1. - k) * channel[prev_index] + k * channel[next_index]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Formats decoded by the default features of web-audio-api, cf. `describe_error`
+const SUPPORTED_FORMATS: &str = "wav, mp3, flac, ogg (vorbis), m4a (aac, alac)";
+
+/// Number of bytes kept from the encoded data to sniff its format on error,
+/// enough to reach the codec id in the track headers of webm files
+pub(crate) const SNIFF_LENGTH: usize = 4096;
+
+fn contains(data: &[u8], pattern: &[u8]) -> bool {
+    data.windows(pattern.len()).any(|window| window == pattern)
+}
+
+/// Best effort guess of a format that can't be decoded from the first bytes of
+/// the encoded data. The codecs are looked up first as their containers (e.g.
+/// ogg or webm) may be supported with other codecs.
+fn sniff_unsupported_format(header: &[u8]) -> Option<&'static str> {
+    if contains(header, b"OpusHead") || contains(header, b"A_OPUS") {
+        Some("opus")
+    } else if contains(header, b"Speex   ") {
+        Some("speex")
+    } else if header.starts_with(b"FORM") && header.len() >= 12 && &header[8..11] == b"AIF" {
+        Some("aiff")
+    } else if header.starts_with(b"caff") {
+        Some("caf")
+    } else if header.starts_with(b"#!AMR") {
+        Some("amr")
+    } else if header.starts_with(b"MThd") {
+        Some("midi")
+    } else {
+        None
+    }
+}
+
+/// Message of a decoding error, naming the detected format and listing the
+/// supported ones if the format can't be decoded
+pub(crate) fn describe_error(header: &[u8], err: DecodingError) -> String {
+    let unsupported = matches!(
+        err.downcast_ref::<SymphoniaError>(),
+        Some(SymphoniaError::Unsupported(_))
+    );
+
+    match sniff_unsupported_format(header) {
+        Some(format) => format!("unsupported format '{format}'; supported: {SUPPORTED_FORMATS}"),
+        None if unsupported => {
+            format!("unsupported format ({err}); supported: {SUPPORTED_FORMATS}")
+        }
+        None => err.to_string(),
+    }
+}
+
+// ----------------------------------------------------
+// Non-spec, list the tracks of a media container
+// ----------------------------------------------------
+#[js_function(1)]
+pub(crate) fn napi_probe(ctx: CallContext) -> Result<JsObject> {
+    let js_buffer = ctx.get::<JsArrayBuffer>(0)?.into_value()?;
+    let data = js_buffer.to_vec();
+
+    ctx.env
+        .execute_tokio_future(
+            async move { Ok(probe_tracks(data)) },
+            |&mut env, result| match result {
+                Ok(tracks) => {
+                    let mut js_tracks = env.create_array_with_length(tracks.len())?;
+
+                    for (i, track) in tracks.iter().enumerate() {
+                        js_tracks.set_element(i as u32, track.to_js_object(&env)?)?;
+                    }
+
+                    Ok(js_tracks)
+                }
+                Err(e) => Err(napi::Error::from_reason(e.to_string())),
+            },
+        )
+}
@@ -22,7 +22,7 @@ use napi::*;
 use napi_derive::js_function;
 use web_audio_api::node::*;
 
-pub(crate) struct NapiDelayNode(DelayNode);
+pub(crate) struct NapiDelayNode(DelayNode, f64);
 
 // for debug purpose
 // impl Drop for NapiDelayNode {
@@ -33,7 +33,8 @@ pub(crate) struct NapiDelayNode(DelayNode);
 
 impl NapiDelayNode {
     pub fn create_js_class(env: &Env) -> Result<JsFunction> {
-        let interface = audio_node_interface![];
+        let interface =
+            audio_node_interface![Property::new("allocatedFrames")?.with_method(allocated_frames)];
 
         env.define_class("DelayNode", constructor, &interface)
     }
@@ -176,10 +177,27 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     ])?;
 
     // finalize instance creation
-    let napi_node = NapiDelayNode(native_node);
+    let napi_node = NapiDelayNode(native_node, max_delay_time);
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
 }
 
 audio_node_impl!(NapiDelayNode);
+
+// non spec, number of frames (per channel) of the delay line, computed as
+// upstream does at the rate of the render graph, i.e. `maxDelayTime` rounded
+// up to a render quantum, plus the render quantum being written
+#[js_function(0)]
+fn allocated_frames(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiDelayNode>(&js_this)?;
+
+    let render_quantum_size = 128.;
+    let sample_rate =
+        web_audio_api::context::BaseAudioContext::sample_rate(napi_node.0.context()) as f64;
+    let num_quanta = (napi_node.1 * sample_rate / render_quantum_size).ceil();
+    let frames = (num_quanta + 1.) * render_quantum_size;
+
+    ctx.env.create_uint32(frames as u32)
+}
@@ -33,7 +33,7 @@ mod audio_node;
 mod utils;
 // Web Audio API
 mod audio_context;
-use crate::audio_context::NapiAudioContext;
+use crate::audio_context::{napi_get_max_channel_count, NapiAudioContext};
 mod audio_destination_node;
 use crate::audio_destination_node::NapiAudioDestinationNode;
 mod audio_param;
@@ -87,6 +87,12 @@ use crate::stereo_panner_node::NapiStereoPannerNode;
 mod wave_shaper_node;
 use crate::wave_shaper_node::NapiWaveShaperNode;
 
+// Non-spec audio nodes
+mod channel_matrix_node;
+use crate::channel_matrix_node::NapiChannelMatrixNode;
+mod noise_source_node;
+use crate::noise_source_node::NapiNoiseSourceNode;
+
 // AudioWorklet internals
 use crate::audio_worklet_node::{exit_audio_worklet_global_scope, run_audio_worklet_global_scope};
 
@@ -97,6 +103,10 @@ mod media_devices;
 use crate::media_devices::napi_enumerate_devices;
 use crate::media_devices::napi_get_user_media;
 
+// Non-spec decoding utils
+mod decoding;
+use crate::decoding::napi_probe;
+
 #[cfg(all(
     any(windows, unix),
     target_arch = "x86_64",
@@ -184,6 +194,15 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
     let napi_class = NapiWaveShaperNode::create_js_class(&env)?;
     exports.set_named_property("WaveShaperNode", napi_class)?;
 
+    // ----------------------------------------------------------------
+    // Non-spec audio nodes
+    // ----------------------------------------------------------------
+    let napi_class = NapiChannelMatrixNode::create_js_class(&env)?;
+    exports.set_named_property("ChannelMatrixNode", napi_class)?;
+
+    let napi_class = NapiNoiseSourceNode::create_js_class(&env)?;
+    exports.set_named_property("NoiseSourceNode", napi_class)?;
+
     // ----------------------------------------------------------------
     // AudioWorklet utils (internal)
     // ----------------------------------------------------------------
@@ -209,6 +228,17 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
     // expose media devices
     exports.set_named_property("mediaDevices", media_devices)?;
 
+    // ----------------------------------------------------------------
+    // Non-spec decoding utils
+    // ----------------------------------------------------------------
+    exports.create_named_method("probe", napi_probe)?;
+
+    // ----------------------------------------------------------------
+    // Non-spec output device limits
+    // ----------------------------------------------------------------
+    // maximum number of channels of the default output device
+    exports.create_named_method("getMaxChannelCount", napi_get_max_channel_count)?;
+
     // ----------------------------------------------------------------
     // Store constructors for classes that need to be created from within Rust code
     // ----------------------------------------------------------------
@@ -0,0 +1,268 @@
+use crate::*;
+use napi::*;
+use napi_derive::js_function;
+use web_audio_api::node::*;
+use web_audio_api::worklet::{AudioWorkletNode, AudioWorkletNodeOptions};
+
+use crate::utils::{NoiseEnded, NoiseGenerator, NoiseMessage, NoiseType, RenderStartTime};
+
+// Non-spec source node generating white, pink or brown noise
+//
+// The node is backed by an AudioWorkletNode running a `NoiseGenerator` on the
+// render thread, the AudioScheduledSourceNode interface is implemented here
+// on top of its message port.
+pub(crate) struct NapiNoiseSourceNode {
+    node: AudioWorkletNode,
+    noise_type: NoiseType,
+    // number of calls to `start` and `stop`, mimics upstream scheduled sources
+    start_stop_count: u8,
+    // cf. AudioScheduledSourceNode::getActualStartTime
+    start_time: RenderStartTime,
+}
+
+impl NapiNoiseSourceNode {
+    pub fn create_js_class(env: &Env) -> Result<JsFunction> {
+        let interface = audio_node_interface![
+            Property::new("type")?
+                .with_getter(get_type)
+                .with_setter(set_type),
+            Property::new("start")?.with_method(start),
+            Property::new("stop")?.with_method(stop),
+            Property::new("actualStartTime")?.with_method(actual_start_time)
+        ];
+
+        env.define_class("NoiseSourceNode", constructor, &interface)
+    }
+
+    // @note: this is used in audio_node.rs for the connect / disconnect macros
+    pub fn unwrap(&mut self) -> &mut AudioWorkletNode {
+        &mut self.node
+    }
+}
+
+fn parse_noise_type(value: &str) -> NoiseType {
+    match value {
+        "white" => NoiseType::White,
+        "pink" => NoiseType::Pink,
+        "brown" => NoiseType::Brown,
+        _ => unreachable!(),
+    }
+}
+
+fn assert_valid_time_value(value: f64) -> Result<()> {
+    if !value.is_finite() {
+        return Err(napi::Error::from_reason(
+            "TypeError - The provided time value is non-finite.",
+        ));
+    }
+
+    if value < 0. {
+        return Err(napi::Error::from_reason(format!(
+            "RangeError - The provided time value ({value:?}) cannot be negative"
+        )));
+    }
+
+    Ok(())
+}
+
+#[js_function(2)]
+fn constructor(ctx: CallContext) -> Result<JsUndefined> {
+    let mut js_this = ctx.this_unchecked::<JsObject>();
+
+    let js_audio_context = ctx.get::<JsObject>(0)?;
+
+    // --------------------------------------------------------
+    // Parse NoiseSourceOptions
+    // by bindings construction all fields are populated on the JS side
+    // --------------------------------------------------------
+    let js_options = ctx.get::<JsObject>(1)?;
+
+    let js_type = js_options.get::<&str, JsString>("type")?.unwrap();
+    let type_str = js_type.into_utf8()?.into_owned()?;
+    let noise_type = parse_noise_type(&type_str);
+
+    let options = AudioWorkletNodeOptions {
+        number_of_inputs: 0,
+        number_of_outputs: 1,
+        output_channel_count: vec![1],
+        processor_options: noise_type,
+        ..AudioWorkletNodeOptions::default()
+    };
+
+    // --------------------------------------------------------
+    // Create native NoiseSourceNode
+    // --------------------------------------------------------
+    let audio_context_name =
+        js_audio_context.get_named_property::<JsString>("Symbol.toStringTag")?;
+    let audio_context_utf8_name = audio_context_name.into_utf8()?.into_owned()?;
+    let audio_context_str = &audio_context_utf8_name[..];
+
+    let native_node = match audio_context_str {
+        "AudioContext" => {
+            let napi_audio_context = ctx.env.unwrap::<NapiAudioContext>(&js_audio_context)?;
+            let audio_context = napi_audio_context.unwrap();
+            AudioWorkletNode::new::<NoiseGenerator>(audio_context, options)
+        }
+        "OfflineAudioContext" => {
+            let napi_audio_context = ctx
+                .env
+                .unwrap::<NapiOfflineAudioContext>(&js_audio_context)?;
+            let audio_context = napi_audio_context.unwrap();
+            AudioWorkletNode::new::<NoiseGenerator>(audio_context, options)
+        }
+        &_ => unreachable!(),
+    };
+
+    // --------------------------------------------------------
+    // Finalize instance creation
+    // --------------------------------------------------------
+    js_this.define_properties(&[
+        Property::new("context")?
+            .with_value(&js_audio_context)
+            .with_property_attributes(PropertyAttributes::Enumerable),
+        // this must be put on the instance and not in the prototype to be reachable
+        Property::new("Symbol.toStringTag")?
+            .with_value(&ctx.env.create_string("NoiseSourceNode")?)
+            .with_property_attributes(PropertyAttributes::Static),
+    ])?;
+
+    // non spec, cf. AudioScheduledSourceNode::getActualStartTime
+    let track_actual_start_time = js_options
+        .get::<&str, JsBoolean>("trackActualStartTime")?
+        .unwrap()
+        .get_value()?;
+
+    let napi_node = NapiNoiseSourceNode {
+        node: native_node,
+        noise_type,
+        start_stop_count: 0,
+        start_time: RenderStartTime::new(track_actual_start_time),
+    };
+    ctx.env.wrap(&mut js_this, napi_node)?;
+
+    ctx.env.get_undefined()
+}
+
+audio_node_impl!(NapiNoiseSourceNode);
+
+// -------------------------------------------------
+// Getters / Setters
+// -------------------------------------------------
+
+#[js_function(0)]
+fn get_type(ctx: CallContext) -> Result<JsString> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiNoiseSourceNode>(&js_this)?;
+
+    let js_value = match napi_node.noise_type {
+        NoiseType::White => "white",
+        NoiseType::Pink => "pink",
+        NoiseType::Brown => "brown",
+    };
+
+    ctx.env.create_string(js_value)
+}
+
+#[js_function(1)]
+fn set_type(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiNoiseSourceNode>(&js_this)?;
+
+    let js_str = ctx.get::<JsObject>(0)?.coerce_to_string()?;
+    let utf8_str = js_str.into_utf8()?.into_owned()?;
+    let value = parse_noise_type(&utf8_str);
+
+    napi_node.noise_type = value;
+    napi_node
+        .node
+        .port()
+        .post_message(NoiseMessage::Type(value));
+
+    ctx.env.get_undefined()
+}
+
+// -------------------------------------------------
+// AudioScheduledSourceNode Interface
+// -------------------------------------------------
+fn listen_to_ended_event(env: &Env, js_this: &JsObject, node: &AudioWorkletNode) -> Result<()> {
+    use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunctionCallMode};
+
+    let k_onended = crate::utils::get_symbol_for(env, "node-web-audio-api:onended");
+    let ended_cb = js_this.get_property(k_onended).unwrap();
+    let mut ended_tsfn =
+        env.create_threadsafe_function(&ended_cb, 0, |ctx: ThreadSafeCallContext<()>| {
+            let mut event = ctx.env.create_object()?;
+            let event_type = ctx.env.create_string("ended")?;
+            event.set_named_property("type", event_type)?;
+
+            Ok(vec![event])
+        })?;
+
+    // unref tsfn so they do not prevent the process to exit
+    let _ = ended_tsfn.unref(env);
+
+    node.port().set_onmessage(move |msg| {
+        if msg.downcast_ref::<NoiseEnded>().is_some() {
+            ended_tsfn.call(Ok(()), ThreadsafeFunctionCallMode::Blocking);
+        }
+    });
+
+    Ok(())
+}
+
+#[js_function(1)]
+fn start(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiNoiseSourceNode>(&js_this)?;
+
+    let when = ctx.get::<JsNumber>(0)?.get_double()?;
+    assert_valid_time_value(when)?;
+
+    if napi_node.start_stop_count != 0 {
+        return Err(napi::Error::from_reason(
+            "InvalidStateError - Cannot call `start` twice",
+        ));
+    }
+
+    listen_to_ended_event(ctx.env, &js_this, &napi_node.node)?;
+
+    napi_node.start_stop_count += 1;
+    napi_node
+        .node
+        .port()
+        .post_message(NoiseMessage::Start(when));
+    napi_node.start_time.record(napi_node.node.context(), when);
+
+    ctx.env.get_undefined()
+}
+
+#[js_function(1)]
+fn stop(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiNoiseSourceNode>(&js_this)?;
+
+    let when = ctx.get::<JsNumber>(0)?.get_double()?;
+    assert_valid_time_value(when)?;
+
+    if napi_node.start_stop_count != 1 {
+        return Err(napi::Error::from_reason(
+            "InvalidStateError - Cannot stop before start",
+        ));
+    }
+
+    napi_node.start_stop_count += 1;
+    napi_node.node.port().post_message(NoiseMessage::Stop(when));
+
+    ctx.env.get_undefined()
+}
+
+#[js_function]
+fn actual_start_time(ctx: CallContext) -> Result<JsUnknown> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiNoiseSourceNode>(&js_this)?;
+
+    match napi_node.start_time.get() {
+        Some(time) => ctx.env.create_double(time).map(|v| v.into_unknown()),
+        None => ctx.env.get_null().map(|v| v.into_unknown()),
+    }
+}
@@ -9,9 +9,12 @@ use web_audio_api::{Event, OfflineAudioCompletionEvent};
 
 use crate::*;
 
-/// Napi object wrapping the native OfflineAudioContext and the AudioWorklet ID
+use crate::utils::{Cancellable, Cancellation};
+
+/// Napi object wrapping the native OfflineAudioContext, the AudioWorklet ID
+/// and the cancellation of the rendering, cf. `cancelRendering`
 #[derive(Clone)]
-pub(crate) struct NapiOfflineAudioContext(Arc<OfflineAudioContext>, usize);
+pub(crate) struct NapiOfflineAudioContext(Arc<OfflineAudioContext>, usize, Arc<Cancellation>);
 
 // // for debug purpose
 // impl Drop for NapiOfflineAudioContext {
@@ -26,7 +29,8 @@ impl NapiOfflineAudioContext {
             Property::new("length")?.with_getter(get_length),
             Property::new("startRendering")?.with_method(start_rendering),
             Property::new("resume")?.with_method(resume),
-            Property::new("suspend")?.with_method(suspend)
+            Property::new("suspend")?.with_method(suspend),
+            Property::new("cancelRendering")?.with_method(cancel_rendering)
         ];
 
         env.define_class("OfflineAudioContext", constructor, &interface)
@@ -58,7 +62,11 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     // -------------------------------------------------
     // Wrap context
     // -------------------------------------------------
-    let napi_audio_context = NapiOfflineAudioContext(Arc::new(audio_context), worklet_id);
+    let napi_audio_context = NapiOfflineAudioContext(
+        Arc::new(audio_context),
+        worklet_id,
+        Arc::new(Cancellation::default()),
+    );
     ctx.env.wrap(&mut js_this, napi_audio_context)?;
 
     js_this.define_properties(&[
@@ -163,24 +171,47 @@ fn start_rendering(ctx: CallContext) -> Result<JsObject> {
 
     // everything is setup, do "real" rendering job
     let context_clone = Arc::clone(&napi_context.0);
+    let rendering = async move { context_clone.start_rendering().await };
+    let cancellable = Cancellable::new(rendering, Arc::clone(&napi_context.2));
 
     ctx.env.execute_tokio_future(
-        async move {
-            let audio_buffer = context_clone.start_rendering().await;
-            Ok(audio_buffer)
-        },
+        async move { Ok(cancellable.await) },
         |&mut env, audio_buffer| {
+            // the rendering has been cancelled, cf. `cancelRendering`
+            let Some(audio_buffer) = audio_buffer else {
+                return Ok(env.get_null()?.into_unknown());
+            };
+
             // create Napi audio buffer from native audio buffer
             let ctor = crate::utils::get_class_ctor(&env, "AudioBuffer")?;
             let js_audio_buffer = ctor.new_instance(&[env.get_null()?])?;
             let napi_audio_buffer = env.unwrap::<NapiAudioBuffer>(&js_audio_buffer)?;
             napi_audio_buffer.insert(audio_buffer);
 
-            Ok(js_audio_buffer)
+            Ok(js_audio_buffer.into_unknown())
         },
     )
 }
 
+// Non-spec, drop the rendering while it is suspended, the `startRendering`
+// promise then resolves with null and the pending `suspend` promises with
+// false. The rendering can only be dropped while it is suspended: upstream
+// renders the quanta between two suspensions at once.
+#[js_function]
+fn cancel_rendering(ctx: CallContext) -> Result<JsUndefined> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_context = ctx.env.unwrap::<NapiOfflineAudioContext>(&js_this)?;
+
+    // no more events are dispatched by the rendering, release the callbacks
+    let context = napi_context.unwrap();
+    context.clear_onstatechange();
+    context.clear_oncomplete();
+
+    napi_context.2.cancel();
+
+    ctx.env.get_undefined()
+}
+
 #[js_function]
 fn resume(ctx: CallContext) -> Result<JsObject> {
     let js_this = ctx.this_unchecked::<JsObject>();
@@ -203,12 +234,12 @@ fn suspend(ctx: CallContext) -> Result<JsObject> {
     let context_clone = Arc::clone(&napi_context.0);
 
     let when = ctx.get::<JsNumber>(0)?.get_double()?;
+    // the suspension is never reached if the rendering is cancelled
+    let suspension = async move { context_clone.suspend(when).await };
+    let cancellable = Cancellable::new(suspension, Arc::clone(&napi_context.2));
 
     ctx.env.execute_tokio_future(
-        async move {
-            context_clone.suspend(when).await;
-            Ok(())
-        },
-        |&mut env, _val| env.get_undefined(),
+        async move { Ok(cancellable.await.is_some()) },
+        |&mut env, suspended| env.get_boolean(suspended),
     )
 }
@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use napi::*;
 use napi_derive::js_function;
 use web_audio_api::node::*;
@@ -10,12 +13,16 @@ use crate::utils::{
 };
 use crate::*;
 
-pub(crate) struct NapiScriptProcessorNode(ScriptProcessorNode);
+// The second field is the maximum number of audioprocess events waiting to be
+// dispatched to JS (0 means unbounded), the third one counts the events
+// dropped because the queue was full, cf. ScriptProcessorNode::droppedEvents
+pub(crate) struct NapiScriptProcessorNode(ScriptProcessorNode, usize, Arc<AtomicU64>);
 
 impl NapiScriptProcessorNode {
     pub fn create_js_class(env: &Env) -> Result<JsFunction> {
         let interface = audio_node_interface![
             Property::new("bufferSize")?.with_getter(get_buffer_size),
+            Property::new("droppedEvents")?.with_getter(get_dropped_events),
             Property::new("listen_to_events")?.with_method(listen_to_events)
         ];
 
@@ -54,6 +61,11 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
         .unwrap()
         .get_double()? as usize;
 
+    let event_queue_size = js_options
+        .get::<&str, JsNumber>("eventQueueSize")?
+        .unwrap()
+        .get_double()? as usize;
+
     // --------------------------------------------------------
     // Create AudioBufferSourceOptions object
     // --------------------------------------------------------
@@ -101,7 +113,8 @@ fn constructor(ctx: CallContext) -> Result<JsUndefined> {
     ])?;
 
     // finalize instance creation
-    let napi_node = NapiScriptProcessorNode(native_node);
+    let napi_node =
+        NapiScriptProcessorNode(native_node, event_queue_size, Arc::new(AtomicU64::new(0)));
     ctx.env.wrap(&mut js_this, napi_node)?;
 
     ctx.env.get_undefined()
@@ -124,11 +137,23 @@ fn get_buffer_size(ctx: CallContext) -> Result<JsNumber> {
     ctx.env.create_double(buffer_size)
 }
 
+#[js_function]
+fn get_dropped_events(ctx: CallContext) -> Result<JsNumber> {
+    let js_this = ctx.this_unchecked::<JsObject>();
+    let napi_node = ctx.env.unwrap::<NapiScriptProcessorNode>(&js_this)?;
+
+    let dropped_events = napi_node.2.load(Ordering::Relaxed) as f64;
+
+    ctx.env.create_double(dropped_events)
+}
+
 #[js_function]
 fn listen_to_events(ctx: CallContext) -> Result<JsUndefined> {
     let js_this = ctx.this_unchecked::<JsObject>();
     let napi_node = ctx.env.unwrap::<NapiScriptProcessorNode>(&js_this)?;
     let node = napi_node.unwrap();
+    let event_queue_size = napi_node.1;
+    let dropped_events = Arc::clone(&napi_node.2);
 
     let k_onaudioprocess =
         crate::utils::get_symbol_for(ctx.env, "node-web-audio-api:onaudioprocess");
@@ -137,7 +162,7 @@ fn listen_to_events(ctx: CallContext) -> Result<JsUndefined> {
     let audioprocess_tsfn = ThreadsafeFunctionPatched::create(
         ctx.env.raw(),
         unsafe { audioprocess_cb.raw() },
-        0,
+        event_queue_size,
         move |ctx: ThreadSafeCallContextPatched<AudioProcessingEvent>| {
             let mut event = ctx.value;
 
@@ -190,8 +215,17 @@ fn listen_to_events(ctx: CallContext) -> Result<JsUndefined> {
     // not implemented in threadsafe patched version
     // let _ = audioprocess_tsfn.unref(ctx.env);
 
+    // With a bounded queue, the events that don't fit in the queue are dropped
+    // rather than blocking the thread dispatching them. A dropped event gives
+    // its output buffer back to the render thread as is.
     node.set_onaudioprocess(move |e| {
-        audioprocess_tsfn.call(e, ThreadsafeFunctionCallModePatched::Blocking);
+        if event_queue_size == 0 {
+            audioprocess_tsfn.call(e, ThreadsafeFunctionCallModePatched::Blocking);
+        } else if audioprocess_tsfn.call(e, ThreadsafeFunctionCallModePatched::NonBlocking)
+            == Status::QueueFull
+        {
+            dropped_events.fetch_add(1, Ordering::Relaxed);
+        }
     });
 
     ctx.env.get_undefined()
@@ -0,0 +1,63 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Shared flag to cancel one or several `Cancellable` futures from another thread
+#[derive(Default)]
+pub(crate) struct Cancellation {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+
+        for waker in self.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Future resolving with `None` as soon as it is cancelled, the wrapped future
+/// is then dropped without being polled again
+pub(crate) struct Cancellable<F> {
+    future: Pin<Box<F>>,
+    cancellation: Arc<Cancellation>,
+}
+
+impl<F> Cancellable<F> {
+    pub fn new(future: F, cancellation: Arc<Cancellation>) -> Self {
+        Self {
+            future: Box::pin(future),
+            cancellation,
+        }
+    }
+}
+
+impl<F: Future> Future for Cancellable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        {
+            let mut wakers = self.cancellation.wakers.lock().unwrap();
+
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+        } // lock is dropped
+
+        // checked after the waker is registered so that a cancellation can't be missed
+        if self.cancellation.is_cancelled() {
+            return Poll::Ready(None);
+        }
+
+        self.future.as_mut().poll(cx).map(Some)
+    }
+}
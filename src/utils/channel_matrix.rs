@@ -0,0 +1,60 @@
+use web_audio_api::worklet::{AudioParamValues, AudioWorkletGlobalScope, AudioWorkletProcessor};
+
+/// Options of the processor, cf. ChannelMatrixNode
+#[derive(Default)]
+pub(crate) struct ChannelMatrixOptions {
+    pub input_channel_count: usize,
+    /// gains of the matrix, one row of `input_channel_count` gains per output
+    /// channel
+    pub matrix: Vec<f32>,
+}
+
+/// Processor running on the render thread, cf. ChannelMatrixNode
+///
+/// Each output channel is the sum of the input channels weighted by the gains
+/// of its row in the matrix.
+pub(crate) struct ChannelMatrix {
+    input_channel_count: usize,
+    matrix: Vec<f32>,
+}
+
+impl AudioWorkletProcessor for ChannelMatrix {
+    type ProcessorOptions = ChannelMatrixOptions;
+
+    fn constructor(options: Self::ProcessorOptions) -> Self {
+        Self {
+            input_channel_count: options.input_channel_count,
+            matrix: options.matrix,
+        }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        inputs: &'b [&'a [&'a [f32]]],
+        outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        _scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        let input = inputs[0];
+
+        for (row, output) in self
+            .matrix
+            .chunks(self.input_channel_count)
+            .zip(outputs[0].iter_mut())
+        {
+            output.fill(0.);
+
+            // missing input channels are silent
+            for (gain, channel) in row.iter().zip(input.iter()) {
+                if *gain != 0. {
+                    output
+                        .iter_mut()
+                        .zip(channel.iter())
+                        .for_each(|(o, i)| *o += gain * i);
+                }
+            }
+        }
+
+        false
+    }
+}
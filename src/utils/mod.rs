@@ -5,6 +5,34 @@ use napi::{Env, JsFunction, JsObject, JsSymbol, Result};
 mod thread_safe_function;
 pub(crate) use thread_safe_function::*;
 
+// futures that can be dropped from another thread, cf. OfflineAudioContext::renderProgress
+mod cancellable;
+pub(crate) use cancellable::*;
+
+// brickwall limiter applied on the destination, cf. AudioDestinationNode::setLimiter
+mod peak_limiter;
+pub(crate) use peak_limiter::PeakLimiter;
+
+// mixes the input channels with a gain matrix, cf. ChannelMatrixNode
+mod channel_matrix;
+pub(crate) use channel_matrix::*;
+
+// noise generator running on the render thread, cf. NoiseSourceNode
+mod noise_generator;
+pub(crate) use noise_generator::*;
+
+// levels of the signal reaching the device, cf. AudioDestinationNode::getOutputLevel
+mod output_meter;
+pub(crate) use output_meter::*;
+
+// notifies the control thread of each render quantum, cf. AudioContext::onrenderquantum
+mod render_quantum_meter;
+pub(crate) use render_quantum_meter::*;
+
+// time at which a source is started by the render thread, cf. AudioScheduledSourceNode::getActualStartTime
+mod start_probe;
+pub(crate) use start_probe::*;
+
 // cf. https://users.rust-lang.org/t/vec-f32-to-u8/21522/7
 #[allow(clippy::needless_lifetimes)]
 pub(crate) fn to_byte_slice<'a>(floats: &'a [f32]) -> &'a [u8] {
@@ -0,0 +1,168 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use web_audio_api::worklet::{AudioParamValues, AudioWorkletGlobalScope, AudioWorkletProcessor};
+
+// Each generator is seeded differently so that several noise sources are not
+// correlated, the seed is never zero as required by xorshift
+static NEXT_SEED: AtomicU32 = AtomicU32::new(0x9E37_79B9);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum NoiseType {
+    #[default]
+    White,
+    Pink,
+    Brown,
+}
+
+/// Messages sent from a NoiseSourceNode to its generator
+#[derive(Debug)]
+pub(crate) enum NoiseMessage {
+    Type(NoiseType),
+    Start(f64),
+    Stop(f64),
+}
+
+/// Message sent from the generator to its NoiseSourceNode once it has ended
+pub(crate) struct NoiseEnded;
+
+/// Noise generator running on the render thread, cf. NoiseSourceNode
+///
+/// Start and stop are handled the same way as the upstream scheduled source
+/// nodes (e.g. ConstantSourceNode), i.e. with sample accuracy:
+/// - white noise is uniformly distributed in [-1, 1[
+/// - pink noise (-3dB/octave) uses Paul Kellet's refined filter
+/// - brown noise (-6dB/octave) is a leaky integration of white noise
+pub(crate) struct NoiseGenerator {
+    noise_type: NoiseType,
+    start_time: f64,
+    stop_time: f64,
+    ended_triggered: bool,
+    // xorshift32 state
+    seed: u32,
+    // pink noise filter state
+    pink: [f32; 7],
+    // brown noise integrator state
+    brown: f32,
+}
+
+// Position of `time` in frames, snapped to the closest frame if very close
+fn time_to_frame(time: f64, sample_rate: f64) -> f64 {
+    let frame = time * sample_rate;
+    let rounded = frame.round();
+
+    if (frame - rounded).abs() < 1e-6 {
+        rounded
+    } else {
+        frame
+    }
+}
+
+impl NoiseGenerator {
+    fn next_white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+
+        (self.seed as f64 / u32::MAX as f64 * 2. - 1.) as f32
+    }
+
+    fn next_sample(&mut self) -> f32 {
+        let white = self.next_white();
+
+        match self.noise_type {
+            NoiseType::White => white,
+            NoiseType::Pink => {
+                let b = &mut self.pink;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.96900 * b[2] + white * 0.153852;
+                b[3] = 0.86650 * b[3] + white * 0.3104856;
+                b[4] = 0.55000 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.0168980;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+                b[6] = white * 0.115926;
+                // bring output back to roughly [-1, 1]
+                pink * 0.11
+            }
+            NoiseType::Brown => {
+                self.brown = (self.brown + 0.02 * white) / 1.02;
+                // bring output back to roughly [-1, 1]
+                self.brown * 3.5
+            }
+        }
+    }
+}
+
+impl AudioWorkletProcessor for NoiseGenerator {
+    type ProcessorOptions = NoiseType;
+
+    fn constructor(noise_type: Self::ProcessorOptions) -> Self {
+        let seed = NEXT_SEED.fetch_add(0x9E37_79B9, Ordering::Relaxed).max(1);
+
+        Self {
+            noise_type,
+            start_time: f64::MAX,
+            stop_time: f64::MAX,
+            ended_triggered: false,
+            seed,
+            pink: [0.; 7],
+            brown: 0.,
+        }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        _inputs: &'b [&'a [&'a [f32]]],
+        outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        let output = &mut outputs[0][0];
+
+        let dt = 1. / scope.sample_rate as f64;
+        let next_block_time = scope.current_time + dt * output.len() as f64;
+
+        if self.start_time >= next_block_time {
+            output.fill(0.);
+            // sources that have not been scheduled to start can be collected
+            return self.start_time != f64::MAX;
+        }
+
+        // compare positions in frames rather than accumulated time to be robust
+        // to rounding errors, e.g. when `stop` is given as `frame / sampleRate`
+        let sample_rate = scope.sample_rate as f64;
+        let start_frame = time_to_frame(self.start_time, sample_rate);
+        let stop_frame = time_to_frame(self.stop_time, sample_rate);
+        let mut current_frame = scope.current_frame as f64;
+
+        for sample in output.iter_mut() {
+            *sample = if current_frame < start_frame || current_frame >= stop_frame {
+                0.
+            } else {
+                self.next_sample()
+            };
+
+            current_frame += 1.;
+        }
+
+        let still_running = self.stop_time >= next_block_time;
+
+        if !still_running && !self.ended_triggered {
+            scope.post_message(Box::new(NoiseEnded));
+            self.ended_triggered = true;
+        }
+
+        still_running
+    }
+
+    fn onmessage(&mut self, msg: &mut dyn Any) {
+        if let Some(message) = msg.downcast_ref::<NoiseMessage>() {
+            match *message {
+                NoiseMessage::Type(noise_type) => self.noise_type = noise_type,
+                NoiseMessage::Start(when) => self.start_time = when,
+                NoiseMessage::Stop(when) => self.stop_time = when,
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use web_audio_api::worklet::{AudioParamValues, AudioWorkletGlobalScope, AudioWorkletProcessor};
+use web_audio_api::MAX_CHANNELS;
+
+/// Levels of the render quanta rendered since the last read, written by the
+/// render thread and read by the control thread, cf.
+/// AudioDestinationNode::getOutputLevel
+///
+/// The levels are stored as the bits of the f32 values so that they can be
+/// shared without locking the render thread.
+pub(crate) struct OutputLevels {
+    number_of_channels: AtomicUsize,
+    peaks: [AtomicU32; MAX_CHANNELS],
+    rms: [AtomicU32; MAX_CHANNELS],
+    // set by the control thread on read, the render thread then starts a new window
+    read: AtomicBool,
+}
+
+impl Default for OutputLevels {
+    fn default() -> Self {
+        Self {
+            number_of_channels: AtomicUsize::new(0),
+            peaks: std::array::from_fn(|_| AtomicU32::new(0)),
+            rms: std::array::from_fn(|_| AtomicU32::new(0)),
+            read: AtomicBool::new(false),
+        }
+    }
+}
+
+impl OutputLevels {
+    /// (peak, rms) of each channel, the levels of the next call are computed
+    /// over the render quanta rendered after this call
+    pub fn take(&self) -> Vec<(f32, f32)> {
+        let number_of_channels = self.number_of_channels.load(Ordering::Relaxed);
+
+        let levels = (0..number_of_channels)
+            .map(|channel| {
+                (
+                    f32::from_bits(self.peaks[channel].load(Ordering::Relaxed)),
+                    f32::from_bits(self.rms[channel].load(Ordering::Relaxed)),
+                )
+            })
+            .collect();
+
+        self.read.store(true, Ordering::Relaxed);
+
+        levels
+    }
+}
+
+/// Processor running on the render thread, cf. AudioDestinationNode::getOutputLevel
+///
+/// The processor has no output, it computes the peak and RMS levels of each
+/// channel of its input over the render quanta rendered since the last read,
+/// i.e. the peak is held until the levels are read.
+pub(crate) struct OutputMeter {
+    levels: Arc<OutputLevels>,
+    // (peak, sum of squares) of each channel in the current window
+    window: Vec<(f32, f64)>,
+    window_length: usize,
+}
+
+impl AudioWorkletProcessor for OutputMeter {
+    type ProcessorOptions = Arc<OutputLevels>;
+
+    fn constructor(levels: Self::ProcessorOptions) -> Self {
+        Self {
+            levels,
+            window: Vec::with_capacity(MAX_CHANNELS),
+            window_length: 0,
+        }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        inputs: &'b [&'a [&'a [f32]]],
+        _outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        _scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        let input = inputs[0];
+
+        if self.levels.read.swap(false, Ordering::Relaxed) || self.window.len() != input.len() {
+            self.window.clear();
+            self.window.resize(input.len(), (0., 0.));
+            self.window_length = 0;
+        }
+
+        self.window_length += input.first().map_or(0, |samples| samples.len());
+
+        for (channel, samples) in input.iter().enumerate() {
+            let (peak, sum) = &mut self.window[channel];
+
+            for s in samples.iter() {
+                *peak = peak.max(s.abs());
+                *sum += (s * s) as f64;
+            }
+
+            let rms = (*sum / self.window_length as f64).sqrt() as f32;
+
+            self.levels.peaks[channel].store(peak.to_bits(), Ordering::Relaxed);
+            self.levels.rms[channel].store(rms.to_bits(), Ordering::Relaxed);
+        }
+
+        self.levels
+            .number_of_channels
+            .store(input.len(), Ordering::Relaxed);
+
+        true
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use web_audio_api::worklet::{AudioParamValues, AudioWorkletGlobalScope, AudioWorkletProcessor};
+
+// Output never exceeds this value, i.e. 0 dBFS
+const CEILING: f32 = 1.;
+// Gain reduction starts this amount of time before a peak is output
+const LOOKAHEAD: f64 = 0.0015;
+// Time constant of the gain recovery once the peak has been output
+const RELEASE: f64 = 0.05;
+// Oversampling factor of the true peak detection, cf. ITU-R BS.1770
+const OVERSAMPLING: usize = 4;
+// Number of input samples used to interpolate each oversampled value
+const TAPS_PER_PHASE: usize = 12;
+// The interpolated values lie between the input samples delayed by
+// `DETECTION_DELAY` and `DETECTION_DELAY - 1`
+const DETECTION_DELAY: usize = TAPS_PER_PHASE / 2;
+
+/// Lookahead brickwall limiter running on the render thread, cf.
+/// AudioDestinationNode::setLimiter
+///
+/// The audio is delayed by `LOOKAHEAD`, so that the gain can be smoothly
+/// reduced before a peak is reached. The applied gain is guaranteed to be
+/// lower than `CEILING / peak` for every sample:
+/// - the peak of each frame is its true peak, i.e. the peak of the signal
+///   oversampled by `OVERSAMPLING`, so that the peaks between two samples are
+///   limited too
+/// - `required` is the gain required by the last frame, with a release
+///   applied when the gain goes back up
+/// - `minimum` holds the minimum required gain over the lookahead window,
+///   i.e. the front of a monotonic deque of the required gains
+/// - the gain applied to the delayed frame is the output of two moving
+///   averages of `minimum` whose lengths sum up to the window length plus
+///   one, i.e. a S-shaped ramp toward the next peak
+pub(crate) struct PeakLimiter {
+    // lookahead in number of frames, computed from the scope sample rate
+    lookahead: usize,
+    release_coef: f32,
+    // polyphase interpolation filter, `OVERSAMPLING` phases of `TAPS_PER_PHASE`
+    coefs: Vec<[f64; TAPS_PER_PHASE]>,
+    // last input samples of each channel, most recent first
+    histories: Vec<[f32; TAPS_PER_PHASE]>,
+    // ring buffers of `lookahead + DETECTION_DELAY + 1` length
+    delay_lines: Vec<Vec<f32>>,
+    delay_index: usize,
+    required: f32,
+    // frame number and required gain, increasing gains from front to back
+    window: VecDeque<(u64, f32)>,
+    frame: u64,
+    // ring buffers of the moving averages and their sums
+    minimum: Vec<f32>,
+    minimum_index: usize,
+    minimum_sum: f64,
+    smoothed: Vec<f32>,
+    smoothed_index: usize,
+    smoothed_sum: f64,
+}
+
+// Windowed sinc interpolating between the input samples, each phase is
+// normalized so that a constant signal is left untouched
+fn interpolation_coefs() -> Vec<[f64; TAPS_PER_PHASE]> {
+    let length = OVERSAMPLING * TAPS_PER_PHASE;
+    let center = (length - 1) as f64 / 2.;
+
+    (0..OVERSAMPLING)
+        .map(|phase| {
+            let mut coefs = [0.; TAPS_PER_PHASE];
+
+            for (tap, coef) in coefs.iter_mut().enumerate() {
+                let n = (tap * OVERSAMPLING + phase) as f64;
+                let x = (n - center) / OVERSAMPLING as f64;
+                let sinc = if x == 0. {
+                    1.
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                // Blackman window
+                let t = n / (length - 1) as f64;
+                let window = 0.42 - 0.5 * (2. * PI * t).cos() + 0.08 * (4. * PI * t).cos();
+
+                *coef = sinc * window;
+            }
+
+            let sum: f64 = coefs.iter().sum();
+            coefs.iter_mut().for_each(|coef| *coef /= sum);
+
+            coefs
+        })
+        .collect()
+}
+
+impl PeakLimiter {
+    fn init(&mut self, sample_rate: f32, number_of_channels: usize) {
+        if self.lookahead == 0 {
+            let sample_rate = sample_rate as f64;
+            self.lookahead = (LOOKAHEAD * sample_rate).ceil() as usize;
+            self.release_coef = (1. - (-1. / (RELEASE * sample_rate)).exp()) as f32;
+            self.coefs = interpolation_coefs();
+            self.window = VecDeque::with_capacity(self.lookahead + 1);
+
+            // the supports of the moving averages must fit into the window
+            let window = self.lookahead + 1;
+            let minimum_length = window.div_ceil(2);
+            let smoothed_length = window + 1 - minimum_length;
+
+            self.minimum = vec![1.; minimum_length];
+            self.minimum_sum = minimum_length as f64;
+            self.smoothed = vec![1.; smoothed_length];
+            self.smoothed_sum = smoothed_length as f64;
+        }
+
+        if self.delay_lines.len() != number_of_channels {
+            let length = self.lookahead + DETECTION_DELAY + 1;
+            self.delay_lines
+                .resize_with(number_of_channels, || vec![0.; length]);
+            self.histories
+                .resize(number_of_channels, [0.; TAPS_PER_PHASE]);
+        }
+    }
+
+    // Push the frame into the interpolation histories and return the true peak
+    // between the samples delayed by `DETECTION_DELAY` and `DETECTION_DELAY - 1`
+    fn true_peak(&mut self, input: &[&[f32]], i: usize) -> f32 {
+        let mut peak = 0_f32;
+
+        for (channel, history) in input.iter().zip(self.histories.iter_mut()) {
+            history.copy_within(0..TAPS_PER_PHASE - 1, 1);
+            history[0] = channel[i];
+
+            peak = peak.max(history[DETECTION_DELAY].abs());
+
+            for coefs in self.coefs.iter() {
+                let value: f64 = coefs
+                    .iter()
+                    .zip(history.iter())
+                    .map(|(&coef, &sample)| coef * sample as f64)
+                    .sum();
+
+                peak = peak.max(value.abs() as f32);
+            }
+        }
+
+        peak
+    }
+
+    // Minimum of the required gains over the lookahead window
+    fn window_minimum(&mut self, required: f32) -> f32 {
+        while self
+            .window
+            .back()
+            .is_some_and(|&(_, gain)| gain >= required)
+        {
+            self.window.pop_back();
+        }
+
+        self.window.push_back((self.frame, required));
+
+        let window = (self.lookahead + 1) as u64;
+
+        while self
+            .window
+            .front()
+            .is_some_and(|&(frame, _)| frame + window <= self.frame)
+        {
+            self.window.pop_front();
+        }
+
+        self.frame += 1;
+        // the deque holds at least the gain just pushed
+        self.window.front().map_or(required, |&(_, gain)| gain)
+    }
+
+    // Output of the moving averages of the window minimum
+    fn smooth(&mut self, minimum: f32) -> f32 {
+        self.minimum_sum += minimum as f64 - self.minimum[self.minimum_index] as f64;
+        self.minimum[self.minimum_index] = minimum;
+        self.minimum_index = (self.minimum_index + 1) % self.minimum.len();
+
+        let average = (self.minimum_sum / self.minimum.len() as f64) as f32;
+
+        self.smoothed_sum += average as f64 - self.smoothed[self.smoothed_index] as f64;
+        self.smoothed[self.smoothed_index] = average;
+        self.smoothed_index = (self.smoothed_index + 1) % self.smoothed.len();
+
+        (self.smoothed_sum / self.smoothed.len() as f64) as f32
+    }
+}
+
+impl AudioWorkletProcessor for PeakLimiter {
+    type ProcessorOptions = ();
+
+    fn constructor(_opts: Self::ProcessorOptions) -> Self {
+        Self {
+            lookahead: 0,
+            release_coef: 0.,
+            coefs: vec![],
+            histories: vec![],
+            delay_lines: vec![],
+            delay_index: 0,
+            required: 1.,
+            window: VecDeque::new(),
+            frame: 0,
+            minimum: vec![],
+            minimum_index: 0,
+            minimum_sum: 0.,
+            smoothed: vec![],
+            smoothed_index: 0,
+            smoothed_sum: 0.,
+        }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        inputs: &'b [&'a [&'a [f32]]],
+        outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        let input = inputs[0];
+        let output = &mut outputs[0];
+
+        self.init(scope.sample_rate, input.len());
+
+        let delay_length = self.lookahead + DETECTION_DELAY + 1;
+        let number_of_frames = output.first().map_or(0, |channel| channel.len());
+
+        for i in 0..number_of_frames {
+            let index = self.delay_index;
+
+            for (channel, delay_line) in input.iter().zip(self.delay_lines.iter_mut()) {
+                delay_line[index] = channel[i];
+            }
+
+            let peak = self.true_peak(input, i);
+            let released = self.required + (1. - self.required) * self.release_coef;
+            self.required = if peak > CEILING {
+                released.min(CEILING / peak)
+            } else {
+                released.min(1.)
+            };
+
+            let minimum = self.window_minimum(self.required);
+            let gain = self.smooth(minimum);
+            // oldest frame of the ring buffer, i.e. delayed by the lookahead
+            // and the true peak detection
+            let read_index = (index + 1) % delay_length;
+
+            for (channel_number, channel) in output.iter_mut().enumerate() {
+                channel[i] = match self.delay_lines.get(channel_number) {
+                    // clamp to guard against rounding errors
+                    Some(delay_line) => (delay_line[read_index] * gain).clamp(-CEILING, CEILING),
+                    None => 0.,
+                };
+            }
+
+            self.delay_index = read_index;
+        }
+
+        true
+    }
+}
@@ -0,0 +1,52 @@
+use std::any::Any;
+
+use web_audio_api::worklet::{AudioParamValues, AudioWorkletGlobalScope, AudioWorkletProcessor};
+
+/// Message sent from the meter to the AudioContext at each render quantum
+pub(crate) struct RenderQuantum {
+    pub frame: u64,
+}
+
+/// Message sent from the AudioContext to stop the meter, which can then be
+/// collected by the render thread
+pub(crate) struct RenderQuantumMeterStop;
+
+/// Processor running on the render thread, cf. AudioContext::onrenderquantum
+///
+/// The processor is not connected to the graph, it only notifies the control
+/// thread each time a render quantum is processed.
+pub(crate) struct RenderQuantumMeter {
+    running: bool,
+}
+
+impl AudioWorkletProcessor for RenderQuantumMeter {
+    type ProcessorOptions = ();
+
+    fn constructor(_options: Self::ProcessorOptions) -> Self {
+        Self { running: true }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        _inputs: &'b [&'a [&'a [f32]]],
+        outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        outputs[0][0].fill(0.);
+
+        if self.running {
+            scope.post_message(Box::new(RenderQuantum {
+                frame: scope.current_frame,
+            }));
+        }
+
+        self.running
+    }
+
+    fn onmessage(&mut self, msg: &mut dyn Any) {
+        if msg.downcast_ref::<RenderQuantumMeterStop>().is_some() {
+            self.running = false;
+        }
+    }
+}
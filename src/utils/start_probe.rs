@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use web_audio_api::context::BaseAudioContext;
+use web_audio_api::node::AudioNode;
+use web_audio_api::worklet::{
+    AudioParamValues, AudioWorkletGlobalScope, AudioWorkletNode, AudioWorkletNodeOptions,
+    AudioWorkletProcessor,
+};
+
+// bits of the time while the probe has not been rendered, i.e. a NaN
+const NOT_RENDERED: u64 = u64::MAX;
+
+/// Time of the render quantum at which the render thread applied the `start`
+/// of a scheduled source, cf. AudioScheduledSourceNode::getActualStartTime
+///
+/// The time is stored as the bits of the f64 value so that it can be shared
+/// without locking the render thread. The time is only probed on the render
+/// thread if the source is tracked, cf. `trackActualStartTime` option.
+#[derive(Clone)]
+pub(crate) struct RenderStartTime(Arc<AtomicU64>, bool);
+
+// required by the options of the probe, cf. AudioWorkletNodeOptions
+impl Default for RenderStartTime {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl RenderStartTime {
+    pub fn new(tracked: bool) -> Self {
+        Self(Arc::new(AtomicU64::new(NOT_RENDERED)), tracked)
+    }
+
+    /// Called right after the `start` message of the source has been sent.
+    /// If the source is tracked, a probe is created in the graph: the control
+    /// messages are handled in order, so the probe is first rendered in the
+    /// render quantum the source is started. Otherwise, the `start` is assumed
+    /// to be applied at `when`, or at the current time if `when` is passed.
+    pub fn record(&self, context: &impl BaseAudioContext, when: f64) {
+        if !self.1 {
+            let time = when.max(context.current_time());
+            self.0.store(time.to_bits(), Ordering::Relaxed);
+            return;
+        }
+
+        let options = AudioWorkletNodeOptions {
+            number_of_inputs: 0,
+            number_of_outputs: 1,
+            output_channel_count: vec![1],
+            processor_options: self.clone(),
+            ..AudioWorkletNodeOptions::default()
+        };
+
+        let probe = AudioWorkletNode::new::<StartProbe>(context, options);
+        // the render thread only orders the graph again when an edge is added,
+        // a node which is not connected might never be rendered
+        probe.connect(&context.destination());
+        // the processor is dropped by the render thread once it has run
+    }
+
+    /// `None` until the render thread has applied the `start` of the source
+    pub fn get(&self) -> Option<f64> {
+        match self.0.load(Ordering::Relaxed) {
+            NOT_RENDERED => None,
+            bits => Some(f64::from_bits(bits)),
+        }
+    }
+}
+
+/// Processor running on the render thread for a single render quantum
+///
+/// The processor outputs silence to the destination, it only records the time
+/// of the first render quantum it is processed in.
+pub(crate) struct StartProbe {
+    start_time: RenderStartTime,
+}
+
+impl AudioWorkletProcessor for StartProbe {
+    type ProcessorOptions = RenderStartTime;
+
+    fn constructor(start_time: Self::ProcessorOptions) -> Self {
+        Self { start_time }
+    }
+
+    fn process<'a, 'b>(
+        &mut self,
+        _inputs: &'b [&'a [&'a [f32]]],
+        outputs: &'b mut [&'a mut [&'a mut [f32]]],
+        _params: AudioParamValues<'b>,
+        scope: &'b AudioWorkletGlobalScope,
+    ) -> bool {
+        outputs[0][0].fill(0.);
+
+        self.start_time
+            .0
+            .store(scope.current_time.to_bits(), Ordering::Relaxed);
+
+        false
+    }
+}
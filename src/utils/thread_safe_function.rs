@@ -175,14 +175,17 @@ impl<T: 'static> ThreadsafeFunction<T> {
         if self.aborted.load(Ordering::Acquire) {
             return Status::Closing;
         }
-        unsafe {
-            sys::napi_call_threadsafe_function(
-                self.raw_tsfn,
-                Box::into_raw(Box::new(value)) as *mut _,
-                mode.into(),
-            )
+        let data = Box::into_raw(Box::new(value));
+        let status = unsafe {
+            sys::napi_call_threadsafe_function(self.raw_tsfn, data as *mut _, mode.into())
+        };
+
+        // the value is not queued, e.g. the queue is full, drop it here
+        if status != sys::Status::napi_ok {
+            drop(unsafe { Box::from_raw(data) });
         }
-        .into()
+
+        status.into()
     }
 }
 
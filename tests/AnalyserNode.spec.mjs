@@ -0,0 +1,213 @@
+import { assert } from 'chai';
+import { AnalyserNode, OfflineAudioContext } from '../index.mjs';
+
+async function analyse(windowFunction) {
+  const sampleRate = 48000;
+  const fftSize = 2048;
+  const audioContext = new OfflineAudioContext(1, fftSize * 2, sampleRate);
+
+  const osc = audioContext.createOscillator();
+  // frequency between bin 100 and 101
+  osc.frequency.value = sampleRate / fftSize * 100.5;
+
+  const analyser = audioContext.createAnalyser();
+  analyser.fftSize = fftSize;
+  analyser.smoothingTimeConstant = 0;
+  analyser.windowFunction = windowFunction;
+
+  osc.connect(analyser).connect(audioContext.destination);
+  osc.start();
+
+  await audioContext.startRendering();
+
+  const data = new Float32Array(analyser.frequencyBinCount);
+  analyser.getFloatFrequencyData(data);
+
+  return data;
+}
+
+describe('# AnalyserNode', () => {
+  describe('## windowFunction (non spec)', () => {
+    it('should default to blackman', () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const analyser = audioContext.createAnalyser();
+
+      assert.equal(analyser.windowFunction, 'blackman');
+    });
+
+    it('should throw TypeError on invalid window function', () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const analyser = audioContext.createAnalyser();
+
+      assert.throws(() => analyser.windowFunction = 'triangle', TypeError);
+      assert.equal(analyser.windowFunction, 'blackman');
+    });
+
+    it('should have narrower skirts with blackman than with none', async () => {
+      const none = await analyse('none');
+      const blackman = await analyse('blackman');
+
+      // peak should be found in the same bins
+      assert.isAbove(none[100], -20);
+      assert.isAbove(blackman[100], -20);
+
+      // compare the leakage 10 bins away from the peak
+      for (let i = 10; i < 50; i++) {
+        assert.isBelow(blackman[100 - i], none[100 - i]);
+        assert.isBelow(blackman[101 + i], none[101 + i]);
+      }
+    });
+
+    it(`should apply 'hann' and 'hamming' windows`, async () => {
+      const none = await analyse('none');
+      const hann = await analyse('hann');
+      const hamming = await analyse('hamming');
+
+      assert.isBelow(hann[150], none[150]);
+      assert.isBelow(hamming[150], none[150]);
+    });
+  });
+
+  describe('## getByteFrequencyData() / getByteTimeDomainData() with a reused array', () => {
+    it('should return the same data as with a new array on each call', async () => {
+      const audioContext = new OfflineAudioContext(1, 4096, 48000);
+      const osc = audioContext.createOscillator();
+      const analyser = audioContext.createAnalyser();
+      osc.connect(analyser).connect(audioContext.destination);
+      osc.start();
+
+      await audioContext.startRendering();
+
+      const expectedFrequency = new Uint8Array(analyser.frequencyBinCount);
+      analyser.getByteFrequencyData(expectedFrequency);
+      const expectedTime = new Uint8Array(analyser.fftSize);
+      analyser.getByteTimeDomainData(expectedTime);
+
+      const frequency = new Uint8Array(analyser.frequencyBinCount);
+      for (let i = 0; i < 10000; i++) {
+        frequency.fill(0);
+        analyser.getByteFrequencyData(frequency);
+        // do not go through chai on each iteration
+        if (frequency.some((value, index) => value !== expectedFrequency[index])) {
+          assert.fail(`wrong frequency data at call ${i}`);
+        }
+      }
+
+      const time = new Uint8Array(analyser.fftSize);
+      for (let i = 0; i < 10000; i++) {
+        time.fill(0);
+        analyser.getByteTimeDomainData(time);
+        if (time.some((value, index) => value !== expectedTime[index])) {
+          assert.fail(`wrong time domain data at call ${i}`);
+        }
+      }
+
+      assert.isAbove(Math.max(...expectedFrequency), 0);
+      // the cache does not bypass the type check of other arguments
+      assert.throws(() => analyser.getByteFrequencyData(new Float32Array(10)), TypeError);
+    });
+
+    it('should resolve a detached or resized array again', async () => {
+      const audioContext = new OfflineAudioContext(1, 4096, 48000);
+      const osc = audioContext.createOscillator();
+      const analyser = audioContext.createAnalyser();
+      osc.connect(analyser).connect(audioContext.destination);
+      osc.start();
+
+      await audioContext.startRendering();
+
+      const expected = new Uint8Array(analyser.fftSize);
+      analyser.getByteTimeDomainData(expected);
+
+      const buffer = new ArrayBuffer(analyser.fftSize, { maxByteLength: analyser.fftSize });
+      const array = new Uint8Array(buffer);
+      analyser.getByteTimeDomainData(array);
+      assert.deepEqual(array, expected);
+
+      // the length tracking array is shrunk in place
+      buffer.resize(8);
+      array.fill(0);
+      analyser.getByteTimeDomainData(array);
+
+      const expectedShort = new Uint8Array(8);
+      analyser.getByteTimeDomainData(expectedShort);
+      assert.deepEqual(array, expectedShort);
+
+      // the memory of the array is moved to another buffer
+      const moved = new Uint8Array(structuredClone(buffer, { transfer: [buffer] }));
+      moved.fill(0);
+      assert.equal(array.length, 0);
+      analyser.getByteTimeDomainData(array);
+
+      assert.isTrue(moved.every(value => value === 0));
+    });
+  });
+
+  describe('## channelMode & getFloatFrequencyDataForChannel() (non spec)', () => {
+    function peakBin(data) {
+      let peak = 0;
+      for (let i = 1; i < data.length; i++) {
+        if (data[i] > data[peak]) {
+          peak = i;
+        }
+      }
+      return peak;
+    }
+
+    it(`should default to 'downmix'`, () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const analyser = audioContext.createAnalyser();
+
+      assert.equal(analyser.channelMode, 'downmix');
+      assert.throws(() => {
+        analyser.getFloatFrequencyDataForChannel(0, new Float32Array(1024));
+      }, DOMException, /perChannel/);
+
+      assert.throws(() => new AnalyserNode(audioContext, { channelMode: 'stereo' }), TypeError);
+    });
+
+    it('should analyse each input channel separately', async () => {
+      const sampleRate = 48000;
+      const fftSize = 2048;
+      const audioContext = new OfflineAudioContext(2, fftSize * 2, sampleRate);
+
+      const analyser = new AnalyserNode(audioContext, {
+        fftSize,
+        smoothingTimeConstant: 0,
+        channelMode: 'perChannel',
+      });
+      assert.equal(analyser.channelMode, 'perChannel');
+
+      // distinct tones on the left and right channels
+      const merger = audioContext.createChannelMerger(2);
+      [100, 200].forEach((bin, channel) => {
+        const osc = audioContext.createOscillator();
+        osc.frequency.value = sampleRate / fftSize * bin;
+        osc.connect(merger, 0, channel);
+        osc.start();
+      });
+
+      merger.connect(analyser).connect(audioContext.destination);
+
+      const output = await audioContext.startRendering();
+      // the node itself is unchanged
+      assert.isAbove(Math.max(...output.getChannelData(0)), 0.9);
+      assert.isAbove(Math.max(...output.getChannelData(1)), 0.9);
+
+      const left = new Float32Array(analyser.frequencyBinCount);
+      const right = new Float32Array(analyser.frequencyBinCount);
+      analyser.getFloatFrequencyDataForChannel(0, left);
+      analyser.getFloatFrequencyDataForChannel(1, right);
+
+      assert.equal(peakBin(left), 100);
+      assert.equal(peakBin(right), 200);
+      // each channel only contains its own tone
+      assert.isBelow(left[200], left[100] - 60);
+      assert.isBelow(right[100], right[200] - 60);
+
+      assert.throws(() => {
+        analyser.getFloatFrequencyDataForChannel(2, left);
+      }, DOMException, /outside the range/);
+    });
+  });
+});
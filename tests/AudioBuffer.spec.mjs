@@ -201,6 +201,136 @@ describe('# AudioBuffer', () => {
       await audioContext.close();
     });
   });
+
+  describe(`## slice(startFrame, endFrame)`, () => {
+    function createRampBuffer(numberOfChannels, length) {
+      const buffer = new AudioBuffer({ numberOfChannels, length, sampleRate: 48000 });
+
+      for (let channel = 0; channel < numberOfChannels; channel++) {
+        const ramp = new Float32Array(length).map((_, i) => i + channel * length);
+        buffer.copyToChannel(ramp, channel);
+      }
+
+      return buffer;
+    }
+
+    it('should return the given frame range for all channels', () => {
+      const source = createRampBuffer(2, 100);
+      const slice = source.slice(25, 75);
+
+      assert.isTrue(slice instanceof AudioBuffer);
+      assert.equal(slice.length, 50);
+      assert.equal(slice.numberOfChannels, 2);
+      assert.equal(slice.sampleRate, source.sampleRate);
+
+      for (let channel = 0; channel < 2; channel++) {
+        assert.equal(slice.getChannelData(channel)[0], source.getChannelData(channel)[25]);
+        assert.deepEqual(slice.getChannelData(channel), source.getChannelData(channel).slice(25, 75));
+      }
+    });
+
+    it('should not share data with the source buffer', () => {
+      const source = createRampBuffer(1, 100);
+      const slice = source.slice(10);
+
+      slice.getChannelData(0)[0] = -1;
+      assert.equal(source.getChannelData(0)[10], 10);
+    });
+
+    it('should clamp out of range indices, negative indices count from the end', () => {
+      const source = createRampBuffer(1, 100);
+
+      assert.equal(source.slice().length, 100);
+      assert.equal(source.slice(90, 1000).length, 10);
+      assert.equal(source.slice(-20).getChannelData(0)[0], 80);
+      assert.equal(source.slice(-1000, 10).length, 10);
+    });
+
+    it('should throw IndexSizeError if the resulting range is empty', () => {
+      const source = createRampBuffer(1, 100);
+
+      [[50, 50], [60, 40], [100, 200]].forEach(([start, end]) => {
+        let errored = false;
+
+        try {
+          source.slice(start, end);
+        } catch (err) {
+          errored = true;
+          assert.equal(err.name, 'IndexSizeError');
+        }
+
+        assert.isTrue(errored);
+      });
+    });
+  });
+
+  describe(`## AudioBuffer.concat(buffers)`, () => {
+    function createToneBuffer(frequency, length, numberOfChannels = 1, sampleRate = 48000) {
+      const buffer = new AudioBuffer({ numberOfChannels, length, sampleRate });
+
+      for (let channel = 0; channel < numberOfChannels; channel++) {
+        const data = buffer.getChannelData(channel);
+
+        for (let i = 0; i < length; i++) {
+          data[i] = Math.cos(2 * Math.PI * frequency * i / sampleRate);
+        }
+      }
+
+      return buffer;
+    }
+
+    it('should concatenate buffers sequentially', () => {
+      const first = createToneBuffer(440, 1000, 2);
+      const second = createToneBuffer(880, 500, 2);
+      // make sure first sample of second tone is recognizable
+      second.getChannelData(0)[0] = 0.25;
+      second.getChannelData(1)[0] = -0.25;
+
+      const result = AudioBuffer.concat([first, second]);
+
+      assert.isTrue(result instanceof AudioBuffer);
+      assert.equal(result.length, first.length + second.length);
+      assert.equal(result.numberOfChannels, 2);
+      assert.equal(result.sampleRate, 48000);
+
+      for (let channel = 0; channel < 2; channel++) {
+        const data = result.getChannelData(channel);
+        // join sample carries the second tone's first sample
+        assert.equal(data[first.length - 1], first.getChannelData(channel)[first.length - 1]);
+        assert.equal(data[first.length], second.getChannelData(channel)[0]);
+        assert.deepEqual(data.subarray(first.length), second.getChannelData(channel));
+      }
+    });
+
+    it('should accept any iterable', () => {
+      const buffers = new Set([createToneBuffer(440, 10), createToneBuffer(440, 20)]);
+      assert.equal(AudioBuffer.concat(buffers).length, 30);
+    });
+
+    it('should throw NotSupportedError on mismatched sample rates or channel counts', () => {
+      [
+        [createToneBuffer(440, 10), createToneBuffer(440, 10, 1, 44100)],
+        [createToneBuffer(440, 10), createToneBuffer(440, 10, 2)],
+      ].forEach(buffers => {
+        let errored = false;
+
+        try {
+          AudioBuffer.concat(buffers);
+        } catch (err) {
+          errored = true;
+          assert.equal(err.name, 'NotSupportedError');
+        }
+
+        assert.isTrue(errored);
+      });
+    });
+
+    it('should throw TypeError on invalid arguments', () => {
+      assert.throws(() => AudioBuffer.concat(), TypeError);
+      assert.throws(() => AudioBuffer.concat([]), TypeError);
+      assert.throws(() => AudioBuffer.concat([createToneBuffer(440, 10), {}]), TypeError);
+    });
+  });
 });
 
 
@@ -0,0 +1,337 @@
+import { assert } from 'chai';
+import {
+  AudioBuffer,
+  AudioBufferSourceNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+import { createWav } from './helpers.mjs';
+
+const sampleRate = 48000;
+
+async function renderEndedSource(buffer, setup = () => {}) {
+  const context = new OfflineAudioContext(1, 1024, sampleRate);
+  const src = context.createBufferSource();
+  let ended = false;
+
+  if (buffer !== null) {
+    src.buffer = buffer;
+  }
+  setup(src);
+  src.onended = () => ended = true;
+  src.connect(context.destination);
+  src.start();
+
+  const output = await context.startRendering();
+  // ended event is dispatched asynchronously from the render thread
+  await new Promise(resolve => setTimeout(resolve, 100));
+
+  return { ended, output: output.getChannelData(0) };
+}
+
+describe('# AudioBufferSourceNode', () => {
+  describe('## loopStartFrame / loopEndFrame (non spec)', () => {
+    it('should stay consistent with loopStart and loopEnd', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const src = context.createBufferSource();
+
+      // no buffer, use the rate of the context
+      src.loopEndFrame = 480;
+      assert.equal(src.loopEnd, 0.01);
+      assert.equal(src.loopEndFrame, 480);
+
+      src.buffer = new AudioBuffer({ length: 1000, sampleRate: 44100 });
+      src.loopStartFrame = 441;
+      assert.equal(src.loopStart, 0.01);
+      assert.equal(src.loopStartFrame, 441);
+
+      src.loopEnd = 0.02;
+      assert.equal(src.loopEndFrame, 882);
+
+      assert.throws(() => src.loopStartFrame = -1, TypeError);
+    });
+
+    it('should loop seamlessly over one cycle of a sine', async () => {
+      const period = 100;
+      const length = 10 * period;
+      const context = new OfflineAudioContext(1, length, sampleRate);
+
+      // buffer contains one cycle of the sine followed by garbage that should
+      // never be played
+      const buffer = new AudioBuffer({ length: 2 * period, sampleRate });
+      const data = buffer.getChannelData(0);
+      for (let i = 0; i < period; i++) {
+        data[i] = Math.sin(2 * Math.PI * i / period);
+      }
+      data.fill(1, period);
+
+      const src = context.createBufferSource();
+      src.buffer = buffer;
+      src.loop = true;
+      src.loopEndFrame = period;
+      src.connect(context.destination);
+      src.start();
+
+      const output = (await context.startRendering()).getChannelData(0);
+
+      for (let i = 0; i < length; i++) {
+        assert.approximately(output[i], data[i % period], 1e-6);
+      }
+    });
+  });
+
+  describe('## zero-length buffer', () => {
+    it('should expose empty channel data', async () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const buffer = await context.decodeAudioData(createWav([[]], sampleRate));
+
+      assert.equal(buffer.length, 0);
+      assert.equal(buffer.duration, 0);
+
+      const data = buffer.getChannelData(0);
+      assert.isTrue(data instanceof Float32Array);
+      assert.equal(data.length, 0);
+
+      // copying from / to an empty array is a no-op
+      buffer.copyFromChannel(new Float32Array(0), 0);
+      buffer.copyToChannel(new Float32Array(0), 0);
+      buffer.copyFromChannel(new Float32Array(4), 0);
+    });
+
+    it('should check the channel number when copying an empty array', () => {
+      const buffer = new AudioBuffer({ length: 4, sampleRate });
+
+      assert.throws(() => {
+        buffer.copyFromChannel(new Float32Array(0), 1);
+      }, DOMException, /channel/);
+
+      assert.throws(() => {
+        buffer.copyToChannel(new Float32Array(0), 1);
+      }, DOMException, /channel/);
+
+      try {
+        buffer.copyToChannel(new Float32Array(0), 1);
+      } catch (err) {
+        assert.equal(err.name, 'IndexSizeError');
+      }
+    });
+
+    it('should render silence and dispatch ended', async () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const buffer = await context.decodeAudioData(createWav([[]], sampleRate));
+
+      const setups = [
+        () => {},
+        src => src.loop = true,
+        src => src.playbackRate.value = -1,
+      ];
+
+      // looping is kept on the JS side
+      const src = new AudioBufferSourceNode(context, { buffer, loop: true });
+      assert.isTrue(src.loop);
+
+      for (const setup of setups) {
+        const { ended, output } = await renderEndedSource(buffer, setup);
+        assert.isTrue(ended);
+        assert.isTrue(output.every(value => value === 0));
+      }
+    });
+
+    it('should dispatch ended if buffer is null', async () => {
+      const { ended, output } = await renderEndedSource(null);
+      assert.isTrue(ended);
+      assert.isTrue(output.every(value => value === 0));
+    });
+  });
+
+  describe('## resampleBuffer (non spec)', () => {
+    // one second of a 441Hz sine at 44100Hz, i.e. 100 samples per period
+    function createSine() {
+      const buffer = new AudioBuffer({ length: 44100, sampleRate: 44100 });
+      const data = buffer.getChannelData(0);
+      for (let i = 0; i < data.length; i++) {
+        data[i] = Math.sin(2 * Math.PI * 441 * i / 44100);
+      }
+      return buffer;
+    }
+
+    async function render(createSource) {
+      const context = new OfflineAudioContext(1, sampleRate, sampleRate);
+      const src = createSource(context);
+      src.connect(context.destination);
+      src.start();
+
+      return (await context.startRendering()).getChannelData(0);
+    }
+
+    it('should be disabled by default', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      assert.isFalse(context.createBufferSource().resampleBuffer);
+      assert.isTrue(new AudioBufferSourceNode(context, { resampleBuffer: true }).resampleBuffer);
+    });
+
+    it('should resample the buffer to the rate of the context', async () => {
+      const buffer = createSine();
+
+      const outputs = [
+        await render(context => new AudioBufferSourceNode(context, { buffer, resampleBuffer: true })),
+        await render(context => {
+          const src = new AudioBufferSourceNode(context, { resampleBuffer: true });
+          src.buffer = buffer;
+          return src;
+        }),
+      ];
+
+      for (const output of outputs) {
+        // pitch and duration are preserved
+        for (let i = 0; i < output.length; i++) {
+          assert.approximately(output[i], Math.sin(2 * Math.PI * 441 * i / sampleRate), 1e-3);
+        }
+      }
+    });
+
+    it('should keep the assigned buffer', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const buffer = createSine();
+      const src = new AudioBufferSourceNode(context, { buffer, resampleBuffer: true });
+
+      assert.equal(src.buffer, buffer);
+      assert.equal(src.loopEndFrame, 0);
+      src.loopEnd = 0.5;
+      assert.equal(src.loopEndFrame, 22050);
+    });
+  });
+
+  describe('## pause() / resume() (non spec)', () => {
+    // 0.1 second ramp so that each frame of the buffer is unique
+    function createRamp(context) {
+      const buffer = new AudioBuffer({ length: 4800, sampleRate });
+      const data = buffer.getChannelData(0);
+      for (let i = 0; i < data.length; i++) {
+        data[i] = (i + 1) / data.length;
+      }
+      return buffer;
+    }
+
+    // pause at ~0.05s for 10 render quanta, pause and resume are effective
+    // one render quantum after they are called
+    const pauseFrame = 19 * 128;
+    const pauseLength = 10 * 128;
+    // the output is faded out and in over 2 render quanta
+    const fadeLength = 2 * 128;
+
+    async function render(setup, paused) {
+      const context = new OfflineAudioContext(1, 8192, sampleRate);
+      const src = context.createBufferSource();
+      let endedCount = 0;
+      src.buffer = createRamp(context);
+      src.onended = () => endedCount += 1;
+      src.connect(context.destination);
+      setup(src);
+      src.start();
+
+      if (paused) {
+        context.suspendAtFrame(pauseFrame - 128).then(() => {
+          src.pause();
+          // is a no-op
+          src.pause();
+          context.resume();
+        });
+
+        context.suspendAtFrame(pauseFrame + pauseLength - 128).then(() => {
+          src.resume();
+          context.resume();
+        });
+      }
+
+      const output = (await context.startRendering()).getChannelData(0);
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      return { src, output, endedCount };
+    }
+
+    it('should resume the playback from the position it was paused at', async () => {
+      const { src, output, endedCount } = await render(() => {}, true);
+      const data = src.buffer.getChannelData(0);
+      // the playhead is held at the end of the fade out
+      const holdFrame = pauseFrame + fadeLength;
+      const resumeFrame = pauseFrame + pauseLength;
+
+      for (let i = 0; i < output.length; i++) {
+        let expected;
+
+        if (i < pauseFrame) {
+          expected = data[i];
+        } else if (i < holdFrame) {
+          expected = data[i] * (1 - (i - pauseFrame) / fadeLength);
+        } else if (i < resumeFrame) {
+          expected = 0;
+        } else {
+          const position = i - (resumeFrame - holdFrame);
+          const gain = Math.min(1, (i - resumeFrame) / fadeLength);
+          expected = position < data.length ? data[position] * gain : 0;
+        }
+
+        assert.approximately(output[i], expected, 1e-6, `frame ${i}`);
+      }
+
+      assert.equal(endedCount, 1);
+      assert.equal(src.playbackRate.value, 1);
+    });
+
+    it('should preserve the automation of the playbackRate', async () => {
+      const setup = src => {
+        // in progress on pause, and scheduled while paused
+        src.playbackRate.setValueAtTime(1, 0);
+        src.playbackRate.linearRampToValueAtTime(0.5, 24 * 128 / sampleRate);
+        src.playbackRate.setValueAtTime(0.75, 26 * 128 / sampleRate);
+      };
+
+      const reference = (await render(setup, false)).output;
+      const { output, endedCount } = await render(setup, true);
+      // the playback is delayed by the time spent paused
+      const delay = pauseLength - fadeLength;
+
+      for (let i = 0; i < pauseFrame; i++) {
+        assert.approximately(output[i], reference[i], 1e-6, `frame ${i}`);
+      }
+
+      for (let i = pauseFrame + pauseLength + fadeLength; i < output.length; i++) {
+        assert.approximately(output[i], reference[i - delay], 1e-4, `frame ${i}`);
+      }
+
+      assert.equal(endedCount, 1);
+    });
+  });
+
+  describe('## getPlaybackPosition() (non spec)', () => {
+    it('should return the position of the playhead in the buffer', async () => {
+      const context = new OfflineAudioContext(1, sampleRate, sampleRate);
+      const src = context.createBufferSource();
+      src.buffer = new AudioBuffer({ length: 0.5 * sampleRate, sampleRate });
+      src.connect(context.destination);
+
+      assert.equal(src.getPlaybackPosition(), -1);
+
+      const startTime = 0.1;
+      src.start(startTime);
+      const positions = {};
+
+      for (const time of [0.05, 0.3, 0.5]) {
+        context.suspend(time).then(() => {
+          positions[time] = src.getPlaybackPosition();
+          context.resume();
+        });
+      }
+
+      await context.startRendering();
+      // ended event is dispatched asynchronously from the render thread
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      const quantum = 128 / sampleRate;
+      assert.equal(positions[0.05], -1);
+      assert.approximately(positions[0.3], 0.3 - startTime, quantum);
+      assert.approximately(positions[0.5], 0.5 - startTime, quantum);
+      assert.equal(src.getPlaybackPosition(), -1);
+    });
+  });
+});
@@ -0,0 +1,524 @@
+import { assert } from 'chai';
+import { sleep } from '@ircam/sc-utils';
+import { AudioContext, OfflineAudioContext, mediaDevices } from '../index.mjs';
+import { kMuteGain } from '../js/lib/symbols.js';
+
+describe('# AudioContext', () => {
+  describe('## getUnderrunCount()', () => {
+    it('should be 0 on a new context', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      assert.equal(audioContext.getUnderrunCount(), 0);
+      await audioContext.close();
+    });
+
+    it('should increase when the render thread cannot keep up', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      // monitored even if renderCapacity is not started
+      let updates = 0;
+      audioContext.renderCapacity.onupdate = () => updates += 1;
+
+      // long impulse response, too heavy to be rendered in real time
+      const impulse = audioContext.createBuffer(1, audioContext.sampleRate * 2, audioContext.sampleRate);
+      impulse.getChannelData(0).fill(0.001);
+
+      const src = audioContext.createOscillator();
+      const convolver = audioContext.createConvolver();
+      convolver.buffer = impulse;
+      src.connect(convolver).connect(audioContext.destination);
+      src.start();
+
+      await sleep(0.5);
+
+      const count = audioContext.getUnderrunCount();
+      await audioContext.close();
+
+      assert.isAbove(count, 0);
+      assert.equal(updates, 0);
+    });
+  });
+
+  describe('## getDeviceSampleRate()', () => {
+    it('should return the sample rate of the backend', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+
+      const sampleRate = audioContext.getDeviceSampleRate();
+      // the null sink has no device, the context sample rate is used
+      assert.isAbove(sampleRate, 0);
+      assert.equal(sampleRate, audioContext.sampleRate);
+
+      await audioContext.close();
+    });
+
+    it('should fallback to the context sample rate when suspended', async () => {
+      const audioContext = new AudioContext({
+        sampleRate: 22050,
+        sinkId: { type: 'none' },
+      });
+      await audioContext.suspend();
+
+      assert.equal(audioContext.getDeviceSampleRate(), 22050);
+
+      await audioContext.close();
+    });
+  });
+
+  describe('## outputBufferSize option & getOutputBufferSize() (non spec)', () => {
+    it('should open the device with the requested buffer size', async () => {
+      const audioContext = new AudioContext({ outputBufferSize: 256 });
+      await sleep(0.1);
+
+      assert.equal(audioContext.getOutputBufferSize(), 256);
+
+      await audioContext.close();
+    });
+
+    it('should reflect the buffer size of the "none" backend', async () => {
+      const audioContext = new AudioContext({
+        sinkId: { type: 'none' },
+        outputBufferSize: 128,
+      });
+      await sleep(0.1);
+
+      assert.equal(audioContext.getOutputBufferSize(), 128);
+
+      await audioContext.close();
+    });
+
+    it('should throw NotSupportedError if the backend rejects the size', () => {
+      // the "none" backend always renders blocks of 128 frames
+      assert.throws(() => {
+        new AudioContext({ sinkId: { type: 'none' }, outputBufferSize: 256 });
+      }, DOMException, /256/);
+
+      assert.throws(() => {
+        new AudioContext({ sinkId: { type: 'none' }, outputBufferSize: 200 });
+      }, DOMException, /power of two/);
+    });
+  });
+
+  describe('## sinkId option', () => {
+    it('should open the context on the given device', async () => {
+      const devices = await mediaDevices.enumerateDevices();
+      const output = devices.find(device => device.kind === 'audiooutput' && device.deviceId !== '');
+
+      // no output device available
+      if (!output) {
+        return;
+      }
+
+      const audioContext = new AudioContext({ sinkId: output.deviceId });
+      const sinkId = audioContext.sinkId;
+      await audioContext.close();
+
+      assert.equal(sinkId, output.deviceId);
+    });
+
+    it('should throw NotFoundError if the device id is unknown', () => {
+      let error = null;
+
+      try {
+        new AudioContext({ sinkId: 'unknown-device-id' });
+      } catch (err) {
+        error = err;
+      }
+
+      assert.isTrue(error instanceof DOMException);
+      assert.equal(error.name, 'NotFoundError');
+    });
+  });
+
+  describe('## AudioContext.getMaxChannelCount() (non spec)', () => {
+    it('should return the maximum number of channels of the output device', async () => {
+      const maxChannelCount = AudioContext.getMaxChannelCount();
+      assert.isAtLeast(maxChannelCount, 2);
+
+      const context = new OfflineAudioContext(maxChannelCount, 128, 48000);
+      assert.equal(context.destination.maxChannelCount, maxChannelCount);
+
+      const buffer = await context.startRendering();
+      assert.equal(buffer.numberOfChannels, maxChannelCount);
+    });
+  });
+
+  describe('## getSinkType() (non spec)', () => {
+    it('should return the type of the current sink', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      const sinkType = audioContext.getSinkType();
+
+      const devices = await mediaDevices.enumerateDevices();
+      const output = devices.find(device => device.kind === 'audiooutput' && device.deviceId !== '');
+      let updatedSinkType = null;
+
+      if (output) {
+        await audioContext.setSinkId(output.deviceId);
+        updatedSinkType = audioContext.getSinkType();
+      }
+
+      await audioContext.close();
+
+      assert.equal(sinkType, 'none');
+
+      if (output) {
+        assert.equal(updatedSinkType, 'device');
+      }
+    });
+
+    it('should return none for the "none" sink id string', async () => {
+      const audioContext = new AudioContext({ sinkId: 'none' });
+      const sinkType = audioContext.getSinkType();
+
+      await audioContext.setSinkId({ type: 'none' });
+      const updatedSinkType = audioContext.getSinkType();
+
+      await audioContext.setSinkId('none');
+      const stringSinkType = audioContext.getSinkType();
+
+      await audioContext.close();
+
+      assert.equal(sinkType, 'none');
+      assert.equal(updatedSinkType, 'none');
+      assert.equal(stringSinkType, 'none');
+    });
+
+    it('should reject a null sink id and keep the current sink', async () => {
+      assert.throws(() => new AudioContext({ sinkId: null }), TypeError, /AudioSinkType/);
+
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+
+      try {
+        await audioContext.setSinkId(null);
+        assert.fail('should reject');
+      } catch (err) {
+        assert.instanceOf(err, TypeError);
+      }
+
+      const sinkType = audioContext.getSinkType();
+      await audioContext.close();
+
+      assert.equal(sinkType, 'none');
+    });
+  });
+
+  describe('## denormals', () => {
+    // The upstream crate renders the graph with flush-to-zero enabled (on x86,
+    // x86_64 and aarch64), so that decaying feedback loops and filter tails
+    // do not hit the CPU penalty of denormal numbers
+    const tiny = 1e-30;
+    // tiny * gain is a denormal float32
+    const gain = 1e-10;
+
+    function createGraph(context) {
+      const src = context.createConstantSource();
+      src.offset.value = tiny;
+
+      const denormal = context.createGain();
+      denormal.gain.value = gain;
+      src.connect(denormal);
+      src.start();
+
+      return { src, denormal };
+    }
+
+    it('should flush denormals to zero on the render thread', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      const { src, denormal } = createGraph(audioContext);
+
+      const direct = audioContext.createAnalyser();
+      const flushed = audioContext.createAnalyser();
+      src.connect(direct);
+      denormal.connect(flushed);
+
+      await sleep(0.2);
+
+      const directData = new Float32Array(direct.fftSize);
+      const flushedData = new Float32Array(flushed.fftSize);
+      direct.getFloatTimeDomainData(directData);
+      flushed.getFloatTimeDomainData(flushedData);
+      await audioContext.close();
+
+      assert.equal(directData[directData.length - 1], Math.fround(tiny));
+      assert.isTrue(flushedData.every(value => value === 0));
+    });
+
+    it('should flush denormals to zero when rendering offline', async () => {
+      const offlineContext = new OfflineAudioContext(2, 128, 48000);
+      const merger = offlineContext.createChannelMerger(2);
+      const { src, denormal } = createGraph(offlineContext);
+
+      src.connect(merger, 0, 0);
+      denormal.connect(merger, 0, 1);
+      merger.connect(offlineContext.destination);
+
+      const output = await offlineContext.startRendering();
+
+      assert.isTrue(output.getChannelData(0).every(value => value === Math.fround(tiny)));
+      assert.isTrue(output.getChannelData(1).every(value => value === 0));
+    });
+  });
+
+  describe('## getBackendInfo() (non spec)', () => {
+    it('should describe the backend of the context', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      const info = audioContext.getBackendInfo();
+      await audioContext.close();
+
+      assert.isString(info.hostApi);
+      assert.isAbove(info.hostApi.length, 0);
+      assert.equal(info.sampleRate, audioContext.sampleRate);
+      assert.equal(info.bufferSize, 128);
+
+      const offlineContext = new OfflineAudioContext(1, 128, 48000);
+      assert.deepEqual(offlineContext.getBackendInfo(), {
+        hostApi: 'offline',
+        deviceName: null,
+        sampleRate: 48000,
+        bufferSize: 128,
+      });
+    });
+  });
+
+  describe('## schedulingLookAhead option & getSchedulingLookAhead() (non spec)', () => {
+    it('should return the duration of the system-level audio callbacks', async () => {
+      // the "none" backend always renders blocks of 128 frames
+      const audioContext = new AudioContext({ sinkId: { type: 'none' }, schedulingLookAhead: 0.05 });
+      const lookAhead = audioContext.getSchedulingLookAhead();
+      const latencyCategory = audioContext.getLatencyCategory();
+      await audioContext.close();
+
+      assert.equal(lookAhead, 128 / audioContext.sampleRate);
+      assert.equal(latencyCategory, 'custom');
+
+      assert.throws(() => new AudioContext({ sinkId: { type: 'none' }, schedulingLookAhead: 0 }), RangeError);
+    });
+
+    it('should throw TypeError if latencyHint is also given', () => {
+      assert.throws(() => {
+        new AudioContext({
+          sinkId: { type: 'none' },
+          latencyHint: 'playback',
+          schedulingLookAhead: 0.05,
+        });
+      }, TypeError, /mutually exclusive/);
+    });
+
+    it('should render events scheduled a look-ahead in advance despite jitter', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' }, schedulingLookAhead: 0.05 });
+      const lookAhead = audioContext.getSchedulingLookAhead();
+      const numberOfEvents = 20;
+      let endedCount = 0;
+
+      for (let i = 0; i < numberOfEvents; i++) {
+        // block the main thread for up to 5ms
+        const jitter = Date.now() + Math.random() * 5;
+        while (Date.now() < jitter) {}
+
+        const time = audioContext.currentTime + lookAhead;
+        const src = audioContext.createConstantSource();
+        src.connect(audioContext.destination);
+        src.onended = () => endedCount += 1;
+        src.start(time);
+        src.stop(time + 0.001);
+
+        await sleep(0.005);
+      }
+
+      await sleep(0.2);
+      await audioContext.close();
+
+      assert.equal(endedCount, numberOfEvents);
+    });
+  });
+
+  describe('## getLatencyCategory() (non spec)', () => {
+    it('should return the latency category the context was created with', async () => {
+      const hints = [
+        [undefined, 'interactive'],
+        ['balanced', 'balanced'],
+        ['playback', 'playback'],
+        [0.05, 'custom'],
+      ];
+
+      for (const [latencyHint, category] of hints) {
+        const audioContext = new AudioContext({ latencyHint, sinkId: { type: 'none' } });
+        const latencyCategory = audioContext.getLatencyCategory();
+        await audioContext.close();
+
+        assert.equal(latencyCategory, category);
+      }
+
+      const audioContext = new AudioContext({ outputBufferSize: 128, sinkId: { type: 'none' } });
+      const latencyCategory = audioContext.getLatencyCategory();
+      await audioContext.close();
+
+      assert.equal(latencyCategory, 'custom');
+    });
+  });
+
+  describe('## scheduling in the past', () => {
+    it('should clamp times in the past to currentTime', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      await sleep(0.1);
+
+      const src = audioContext.createConstantSource();
+      const analyser = audioContext.createAnalyser();
+      src.connect(analyser);
+
+      const now = audioContext.currentTime;
+      assert.isAbove(now, 0.01);
+
+      src.start(now - 0.01);
+      analyser.fftSize = 128;
+      const gain = audioContext.createGain();
+      gain.gain.setValueAtTime(0.5, now - 0.01);
+      gain.gain.linearRampToValueAtTime(1, now - 0.005);
+
+      const events = gain.gain.getScheduledEvents();
+
+      await sleep(0.1);
+      const data = new Float32Array(analyser.fftSize);
+      analyser.getFloatTimeDomainData(data);
+      await audioContext.close();
+
+      events.forEach(event => assert.isAtLeast(event.time, now));
+      // source started immediately
+      assert.deepEqual(data, new Float32Array(analyser.fftSize).fill(1));
+    });
+
+    it('should keep exact times on offline contexts', async () => {
+      const audioContext = new OfflineAudioContext(1, 48000, 48000);
+      const gain = audioContext.createGain();
+
+      audioContext.suspend(0.5).then(() => {
+        gain.gain.setValueAtTime(0.5, 0.25);
+        audioContext.resume();
+      });
+
+      await audioContext.startRendering();
+
+      assert.equal(gain.gain.getScheduledEvents()[0].time, 0.25);
+    });
+  });
+
+  describe('## onrenderquantum (non spec)', () => {
+    it('should be called at each render quantum', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      const frames = [];
+
+      audioContext.onrenderquantum = frame => frames.push(frame);
+      await sleep(1);
+      audioContext.onrenderquantum = null;
+
+      const count = frames.length;
+      // notifications are not delivered after the callback is removed
+      await sleep(0.1);
+      await audioContext.close();
+
+      assert.equal(frames.length, count);
+
+      const duration = (frames[frames.length - 1] - frames[0]) / audioContext.sampleRate;
+      assert.approximately(duration, 1, 0.2);
+      assert.approximately(count, audioContext.sampleRate / 128 * duration, audioContext.sampleRate / 128 * 0.1);
+
+      for (let i = 1; i < frames.length; i++) {
+        assert.equal(frames[i] % 128, 0);
+        assert.isAbove(frames[i], frames[i - 1]);
+      }
+    });
+
+    it('should stop the notifications on close', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      let count = 0;
+
+      audioContext.onrenderquantum = () => count += 1;
+      await sleep(0.1);
+      await audioContext.close();
+
+      const countAfterClose = count;
+      await sleep(0.1);
+
+      assert.isAbove(countAfterClose, 0);
+      assert.equal(count, countAfterClose);
+      assert.isNull(audioContext.onrenderquantum);
+    });
+
+    it('should be called if set once the graph is rendered', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      await sleep(0.1);
+
+      let count = 0;
+      audioContext.onrenderquantum = () => count += 1;
+      await sleep(0.1);
+      await audioContext.close();
+
+      assert.isAbove(count, 0);
+    });
+  });
+
+  describe('## muted (non spec)', () => {
+    it('should silence the device output while keeping the graph processed', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      assert.isFalse(audioContext.muted);
+
+      const src = audioContext.createOscillator();
+      const analyser = audioContext.createAnalyser();
+      src.connect(audioContext.destination);
+      src.connect(analyser);
+      src.start();
+
+      audioContext.muted = true;
+      assert.isTrue(audioContext.muted);
+      await sleep(0.2);
+
+      const data = new Float32Array(analyser.fftSize);
+      analyser.getFloatTimeDomainData(data);
+      const peak = data.reduce((acc, value) => Math.max(acc, Math.abs(value)), 0);
+      // gain applied to the signal sent to the device
+      const outputGain = audioContext.destination[kMuteGain].value;
+
+      audioContext.muted = false;
+      await sleep(0.1);
+      const restoredGain = audioContext.destination[kMuteGain].value;
+
+      await audioContext.close();
+
+      assert.isAbove(peak, 0.9);
+      assert.equal(outputGain, 0);
+      assert.equal(restoredGain, 1);
+    });
+  });
+
+  describe('## close({ flush })', () => {
+    it('should wait for the buffered audio to be played out', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+      await sleep(0.1);
+
+      const src = audioContext.createOscillator();
+      src.connect(audioContext.destination);
+      src.start();
+
+      const stopTime = audioContext.currentTime + 0.2;
+      src.stop(stopTime);
+
+      await audioContext.close({ flush: true });
+
+      assert.equal(audioContext.state, 'closed');
+      // the source has been rendered until its end
+      assert.isAtLeast(audioContext.currentTime, stopTime);
+    });
+
+    it('should throw if options is not an object', async () => {
+      const audioContext = new AudioContext({ sinkId: { type: 'none' } });
+
+      let errored = false;
+
+      try {
+        await audioContext.close(true);
+      } catch (err) {
+        errored = err instanceof TypeError;
+      }
+
+      assert.isTrue(errored);
+      await audioContext.close();
+    });
+  });
+});
@@ -0,0 +1,76 @@
+import { assert } from 'chai';
+import {
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# AudioDestinationNode', () => {
+  describe('## getOutputLevel() (non spec)', () => {
+    it('should report the levels of the signal reaching the device', async () => {
+      const context = new OfflineAudioContext(2, sampleRate, sampleRate);
+      // exactly one period per render quantum
+      const osc = context.createOscillator();
+      osc.frequency.value = sampleRate / 128;
+      const gain = context.createGain();
+      gain.gain.value = 0.8;
+      // mono source, upmixed to the 2 channels of the destination
+      osc.connect(gain).connect(context.destination);
+      osc.start(0);
+      osc.stop(0.5);
+
+      // installs the meter
+      assert.deepEqual(context.destination.getOutputLevel(), []);
+
+      let levels = null;
+      context.suspend(0.25).then(() => {
+        levels = context.destination.getOutputLevel();
+        context.resume();
+      });
+      // the levels of the next call are computed from there
+      context.suspend(0.5).then(() => {
+        context.destination.getOutputLevel();
+        context.resume();
+      });
+
+      await context.startRendering();
+
+      assert.equal(levels.length, 2);
+
+      for (const { peak, rms } of levels) {
+        assert.approximately(peak, 0.8, 1e-4);
+        assert.approximately(rms, 0.8 * Math.SQRT1_2, 1e-4);
+      }
+
+      // silent after the oscillator is stopped
+      for (const { peak, rms } of context.destination.getOutputLevel()) {
+        assert.equal(peak, 0);
+        assert.equal(rms, 0);
+      }
+    });
+
+    it('should hold the peak between two calls', async () => {
+      const context = new OfflineAudioContext(1, sampleRate, sampleRate);
+      // a single render quantum of signal
+      const src = context.createConstantSource();
+      src.offset.value = 0.5;
+      src.connect(context.destination);
+      src.start(0.1);
+      src.stop(0.1 + 128 / sampleRate);
+
+      context.destination.getOutputLevel();
+
+      let levels = null;
+      context.suspend(0.5).then(() => {
+        levels = context.destination.getOutputLevel();
+        context.resume();
+      });
+
+      await context.startRendering();
+
+      assert.equal(levels.length, 1);
+      assert.approximately(levels[0].peak, 0.5, 1e-6);
+      assert.isAbove(levels[0].rms, 0);
+    });
+  });
+});
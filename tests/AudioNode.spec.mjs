@@ -0,0 +1,69 @@
+import { assert } from 'chai';
+import {
+  AudioParam,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# AudioNode', () => {
+  describe('## connect(destination, output, input, { gain }) (non spec)', () => {
+    it('should apply the gain on the connection', async () => {
+      const context = new OfflineAudioContext(1, 256, sampleRate);
+      const src = context.createConstantSource();
+      src.offset.value = 0.8;
+
+      const gain = src.connect(context.destination, 0, 0, { gain: 0.5 });
+      assert.isTrue(gain instanceof AudioParam);
+      assert.equal(gain.value, 0.5);
+      // the gain of the connection can be changed later
+      gain.setValueAtTime(0.25, 128 / sampleRate);
+      src.start();
+
+      const output = (await context.startRendering()).getChannelData(0);
+
+      for (let i = 0; i < 128; i++) {
+        assert.approximately(output[i], 0.4, 1e-6);
+        assert.approximately(output[128 + i], 0.2, 1e-6);
+      }
+    });
+
+    it('should remove the connection on disconnect', async () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const src = context.createConstantSource();
+      const other = context.createConstantSource();
+      other.offset.value = 0.5;
+
+      src.connect(context.destination, 0, 0, { gain: 0.5 });
+      other.connect(context.destination);
+      // connecting again updates the gain of the connection
+      src.connect(context.destination, 0, 0, { gain: 0.25 });
+      src.disconnect(context.destination);
+      src.start();
+      other.start();
+
+      assert.throws(() => src.disconnect(context.destination), DOMException);
+
+      const output = (await context.startRendering()).getChannelData(0);
+      assert.deepEqual(output, new Float32Array(128).fill(0.5));
+    });
+
+    it('should keep the connection if the native disconnect throws', async () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const src = context.createConstantSource();
+      src.connect(context.destination, 0, 0, { gain: 0.5 });
+
+      // the source has a single output
+      assert.throws(() => src.disconnect(1), DOMException);
+      assert.throws(() => src.disconnect(context.destination, 1), DOMException);
+      src.start();
+
+      const output = (await context.startRendering()).getChannelData(0);
+      assert.deepEqual(output, new Float32Array(128).fill(0.5));
+
+      // and can still be removed
+      src.disconnect(0);
+      assert.throws(() => src.disconnect(context.destination), DOMException);
+    });
+  });
+});
@@ -1,5 +1,5 @@
 import { assert } from 'chai';
-import { AudioContext } from '../index.mjs';
+import { AudioContext, OfflineAudioContext } from '../index.mjs';
 
 describe('# AudioBuffer', () => {
   let audioContext;
@@ -25,3 +25,255 @@ describe('# AudioBuffer', () => {
     });
   });
 });
+
+describe('# AudioParam', () => {
+  function scheduleAutomation(param) {
+    param.setValueAtTime(0.2, 0);
+    param.linearRampToValueAtTime(1, 0.01);
+    param.exponentialRampToValueAtTime(0.1, 0.02);
+    param.setTargetAtTime(0.5, 0.02, 0.003);
+    param.setValueCurveAtTime([0, 0.3, -0.2, 0.8], 0.03, 0.005);
+    param.setValueAtTime(0, 0.04);
+  }
+
+  async function render(automate) {
+    const audioContext = new OfflineAudioContext(1, 0.05 * 48000, 48000);
+    const src = audioContext.createConstantSource();
+    src.connect(audioContext.destination);
+    automate(src.offset);
+    src.start();
+
+    return await audioContext.startRendering();
+  }
+
+  describe('## exportTimeline() / importTimeline()', () => {
+    it('should restore the automation timeline', async () => {
+      let timeline;
+
+      const expected = await render(param => {
+        scheduleAutomation(param);
+        timeline = param.exportTimeline();
+      });
+
+      assert.equal(timeline.length, 6);
+      assert.deepEqual(timeline[0], { type: 'setValueAtTime', value: 0.20000000298023224, startTime: 0 });
+      // should be serializable
+      timeline = JSON.parse(JSON.stringify(timeline));
+
+      const result = await render(param => {
+        scheduleAutomation(param);
+        param.cancelScheduledValues(0);
+        assert.deepEqual(param.exportTimeline(), []);
+        param.importTimeline(timeline);
+      });
+
+      assert.deepEqual(result.getChannelData(0), expected.getChannelData(0));
+    });
+
+    it('should remove cancelled events from the timeline', async () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const gain = audioContext.createGain();
+      scheduleAutomation(gain.gain);
+      gain.gain.cancelScheduledValues(0.02);
+
+      const timeline = gain.gain.exportTimeline();
+      assert.deepEqual(timeline.map(event => event.type), [
+        'setValueAtTime',
+        'linearRampToValueAtTime',
+      ]);
+    });
+
+    it('should throw TypeError on malformed events', async () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const gain = audioContext.createGain();
+      gain.gain.setValueAtTime(0.5, 0);
+
+      const malformed = [
+        null,
+        42,
+        [null],
+        [{ type: 'unknown', value: 1, startTime: 0 }],
+        [{ type: 'setValueAtTime', value: 1 }],
+        [{ type: 'setValueAtTime', value: '1', startTime: 0 }],
+        [{ type: 'setValueCurveAtTime', values: 1, startTime: 0, duration: 1 }],
+      ];
+
+      malformed.forEach(events => {
+        assert.throws(() => gain.gain.importTimeline(events), TypeError);
+      });
+
+      // timeline should be left untouched
+      assert.equal(gain.gain.exportTimeline().length, 1);
+    });
+
+    it('should record the value setter as setValueAtTime', async () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const param = audioContext.createGain().gain;
+
+      param.value = 0.5;
+
+      assert.deepEqual(param.exportTimeline(), [
+        { type: 'setValueAtTime', value: 0.5, startTime: 0 },
+      ]);
+    });
+
+    it('should drop the events ended before the current time', async () => {
+      const sampleRate = 48000;
+      const audioContext = new OfflineAudioContext(1, sampleRate, sampleRate);
+      const param = audioContext.createGain().gain;
+
+      param.setValueAtTime(0.25, 0);
+      param.linearRampToValueAtTime(0.5, 0.1);
+      param.setTargetAtTime(1, 0.15, 0.01);
+      param.setValueAtTime(0.75, 0.2);
+      param.setValueAtTime(0.25, 0.5);
+
+      let timeline = null;
+      let lengths = [];
+
+      audioContext.suspend(12800 / sampleRate).then(() => {
+        param.linearRampToValueAtTime(1, 0.6);
+        timeline = param.exportTimeline();
+        // keeps bounded when the value is set repeatedly
+        for (let i = 0; i < 10; i++) {
+          param.value = 0.5;
+          lengths.push(param.exportTimeline().length);
+        }
+
+        audioContext.resume();
+      });
+
+      await audioContext.startRendering();
+
+      assert.deepEqual(timeline, [
+        { type: 'setValueAtTime', value: 0.75, startTime: 0.2 },
+        { type: 'setValueAtTime', value: 0.25, startTime: 0.5 },
+        { type: 'linearRampToValueAtTime', value: 1, endTime: 0.6 },
+      ]);
+      assert.isTrue(lengths.every(length => length <= 4));
+    });
+  });
+
+  describe('## getScheduledEvents()', () => {
+    it('should return the scheduled events sorted by time', async () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const gain = audioContext.createGain();
+      const param = gain.gain;
+
+      param.setValueAtTime(0.5, 0.1);
+      param.linearRampToValueAtTime(1, 0.2);
+      param.setValueCurveAtTime([0, 0.25, 0.5], 0.3, 0.1);
+      param.exponentialRampToValueAtTime(0.25, 0.5);
+      param.setTargetAtTime(0, 0.6, 0.02);
+      // inserted before the other events
+      param.setValueAtTime(0, 0);
+
+      const events = param.getScheduledEvents();
+
+      assert.deepEqual(events.map(event => event.type), [
+        'setValue',
+        'setValue',
+        'linearRamp',
+        'setValueCurve',
+        'expRamp',
+        'setTarget',
+      ]);
+      assert.deepEqual(events.map(event => event.time), [0, 0.1, 0.2, 0.3, 0.5, 0.6]);
+      assert.deepEqual(events[3].values, [0, 0.25, 0.5]);
+      assert.equal(events[3].duration, 0.1);
+      assert.approximately(events[5].timeConstant, 0.02, 1e-6);
+
+      // read-only
+      events[0].time = 42;
+      events.pop();
+      assert.equal(param.getScheduledEvents()[0].time, 0);
+      assert.equal(param.getScheduledEvents().length, 6);
+    });
+
+    it('should remove canceled events', async () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const param = audioContext.createGain().gain;
+
+      param.setValueAtTime(0, 0);
+      param.linearRampToValueAtTime(1, 0.1);
+      param.linearRampToValueAtTime(0, 0.2);
+      param.cancelScheduledValues(0.2);
+
+      assert.deepEqual(param.getScheduledEvents().map(event => event.time), [0, 0.1]);
+
+      param.cancelAndHoldAtTime(0.05);
+
+      assert.deepEqual(param.getScheduledEvents().map(event => event.type), ['setValue', 'cancelAndHold']);
+    });
+  });
+
+  describe('## getValueAtTime(time) (non spec)', () => {
+    it('should compute the value of the scheduled automation', () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const param = audioContext.createGain().gain;
+
+      param.value = 0.5;
+      param.setValueAtTime(0, 0.1);
+      param.linearRampToValueAtTime(1, 0.2);
+      param.exponentialRampToValueAtTime(0.25, 0.4);
+      param.setValueCurveAtTime([1, 0, 1], 0.5, 0.2);
+
+      assert.equal(param.getValueAtTime(0.05), 0.5);
+      assert.approximately(param.getValueAtTime(0.15), 0.5, 1e-9);
+      assert.approximately(param.getValueAtTime(0.3), 0.5, 1e-9);
+      assert.approximately(param.getValueAtTime(0.55), 0.5, 1e-9);
+      assert.equal(param.getValueAtTime(1), 1);
+    });
+
+    it('should take the value set during the timeline into account', async () => {
+      const sampleRate = 48000;
+      const audioContext = new OfflineAudioContext(1, sampleRate, sampleRate);
+      const param = audioContext.createGain().gain;
+
+      param.setValueAtTime(0.25, 0);
+      param.setValueAtTime(0.5, 0.5);
+
+      let value = null;
+
+      audioContext.suspend(12800 / sampleRate).then(() => {
+        param.value = 0.75;
+        value = param.getValueAtTime(0.4);
+        audioContext.resume();
+      });
+
+      await audioContext.startRendering();
+
+      assert.equal(value, 0.75);
+      assert.equal(param.getValueAtTime(0.6), 0.5);
+    });
+
+    it('should report the held value after cancelAndHoldAtTime', async () => {
+      const sampleRate = 48000;
+      const audioContext = new OfflineAudioContext(1, sampleRate, sampleRate);
+      const src = audioContext.createConstantSource();
+      src.connect(audioContext.destination);
+      src.start();
+
+      const param = src.offset;
+      param.setValueAtTime(0, 0);
+      param.linearRampToValueAtTime(1, 1);
+      param.cancelAndHoldAtTime(0.1);
+
+      assert.approximately(param.getValueAtTime(0.2), 0.1, 1e-6);
+
+      let valueAtSuspend = null;
+      let scheduledValueAtSuspend = null;
+
+      audioContext.suspend(0.2).then(() => {
+        valueAtSuspend = param.value;
+        scheduledValueAtSuspend = param.getValueAtTime(audioContext.currentTime);
+        audioContext.resume();
+      });
+
+      await audioContext.startRendering();
+
+      assert.approximately(valueAtSuspend, 0.1, 1e-6);
+      assert.approximately(scheduledValueAtSuspend, 0.1, 1e-6);
+    });
+  });
+});
@@ -0,0 +1,71 @@
+import { assert } from 'chai';
+import {
+  AudioContext,
+  ConstantSourceNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# AudioScheduledSourceNode', () => {
+  describe('## getActualStartTime() (non spec)', () => {
+    it('should return the requested start time once started', async () => {
+      const context = new OfflineAudioContext(1, sampleRate * 0.1, sampleRate);
+      const src = new ConstantSourceNode(context);
+      src.connect(context.destination);
+
+      assert.isNull(src.getActualStartTime());
+
+      // in the middle of a render quantum
+      const when = 0.0123;
+      src.start(when);
+
+      assert.isNull(src.getActualStartTime());
+
+      const output = (await context.startRendering()).getChannelData(0);
+
+      assert.equal(src.getActualStartTime(), when);
+      // the first sample rendered is the first one at or after the start time
+      const firstFrame = output.findIndex(sample => sample !== 0);
+      assert.equal(firstFrame, Math.ceil(when * sampleRate));
+    });
+
+    it('should return the time of the next render quantum if start time is in the past', async () => {
+      const context = new OfflineAudioContext(1, sampleRate * 0.2, sampleRate);
+      const src = new ConstantSourceNode(context);
+      src.connect(context.destination);
+      let suspendTime;
+
+      context.suspend(0.1).then(() => {
+        suspendTime = context.currentTime;
+        src.start(0);
+        context.resume();
+      });
+
+      const output = (await context.startRendering()).getChannelData(0);
+
+      assert.equal(src.getActualStartTime(), suspendTime);
+      assert.equal(output.findIndex(sample => sample !== 0), Math.round(suspendTime * sampleRate));
+    });
+
+    it('should return the time at which the render thread started a tracked source', async () => {
+      const context = new AudioContext({ sinkId: { type: 'none' } });
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      const src = new ConstantSourceNode(context, { trackActualStartTime: true });
+      src.connect(context.destination);
+
+      const callTime = context.currentTime;
+      src.start(0);
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      const startTime = src.getActualStartTime();
+      // the render quantum in which the call to start has been handled
+      assert.isAtLeast(startTime, callTime);
+      assert.isBelow(startTime, context.currentTime);
+      assert.equal(Math.round(startTime * context.sampleRate) % 128, 0);
+
+      await context.close();
+    });
+  });
+});
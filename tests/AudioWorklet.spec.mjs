@@ -1,6 +1,11 @@
 import { Blob } from 'node:buffer';
 import { assert } from 'chai';
-import { AudioContext, OscillatorNode, AudioWorkletNode } from '../index.mjs';
+import {
+  AudioContext,
+  OfflineAudioContext,
+  OscillatorNode,
+  AudioWorkletNode,
+} from '../index.mjs';
 
 const scriptTexts = `
 class FirstProcessor extends AudioWorkletProcessor {
@@ -64,4 +69,474 @@ describe('AudioWorklet', () => {
     it.skip(`should support loading from caller relative path`, async () => {});
     it.skip(`should support loading from url`, async () => {});
   });
+
+  describe('# processor drop', () => {
+    it('should recycle the buffers of the params of dropped processors', async () => {
+      const paramsScriptTexts = `
+      class ParamsProcessor extends AudioWorkletProcessor {
+        static get parameterDescriptors() {
+          return [{ name: 'first' }, { name: 'second' }];
+        }
+
+        process(inputs, outputs, parameters) {
+          outputs[0].forEach(channel => channel.fill(parameters.first[0]));
+          return true;
+        }
+      }
+
+      registerProcessor('params-processor', ParamsProcessor);
+      `;
+
+      const context = new OfflineAudioContext(1, 128 * 4, 48000);
+      const blob = new Blob([paramsScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      const worklet = new AudioWorkletNode(context, 'params-processor', {
+        outputChannelCount: [1],
+      });
+      worklet.parameters.get('first').value = 0.5;
+      worklet.connect(context.destination);
+
+      // the processors are dropped at the end of the rendering, this used to
+      // crash the Worker as the cache is keyed by param name
+      const buffer = await context.startRendering();
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      assert.deepEqual(buffer.getChannelData(0), new Float32Array(128 * 4).fill(0.5));
+    });
+  });
+
+  describe('# processor not callable', () => {
+    it('should output silence once the processor is not callable', async () => {
+      const throwingScriptTexts = `
+      class ThrowingProcessor extends AudioWorkletProcessor {
+        process(inputs, outputs) {
+          if (currentFrame > 0) {
+            throw new Error('process error');
+          }
+
+          outputs[0].forEach(channel => channel.fill(0.5));
+          return true;
+        }
+      }
+
+      registerProcessor('throwing-processor', ThrowingProcessor);
+      `;
+
+      const context = new OfflineAudioContext(1, 128 * 4, 48000);
+      const blob = new Blob([throwingScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      const worklet = new AudioWorkletNode(context, 'throwing-processor', {
+        outputChannelCount: [1],
+      });
+      worklet.onprocessorerror = () => {};
+      worklet.connect(context.destination);
+
+      const buffer = await context.startRendering();
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      const data = buffer.getChannelData(0);
+      assert.deepEqual(data.subarray(0, 128), new Float32Array(128).fill(0.5));
+      // the last rendered buffer is not repeated
+      assert.deepEqual(data.subarray(128), new Float32Array(128 * 3));
+    });
+  });
+
+  describe('# getRegisteredProcessors() (non spec)', () => {
+    it('should list the processors of all added modules', async () => {
+      const context = new OfflineAudioContext(1, 128, 48000);
+      assert.deepEqual(context.audioWorklet.getRegisteredProcessors(), []);
+
+      const blob = new Blob([scriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      const other = new Blob([`
+        registerProcessor('third-processor', class extends AudioWorkletProcessor {
+          process() {
+            return true;
+          }
+        });
+      `], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(other));
+
+      assert.deepEqual(context.audioWorklet.getRegisteredProcessors(), [
+        'first-processor',
+        'second-processor',
+        'third-processor',
+      ]);
+
+      await context.startRendering();
+    });
+  });
+
+  describe('# workletProcessBudget context option', () => {
+    const budgetScriptTexts = `
+    class BusyProcessor extends AudioWorkletProcessor {
+      process(inputs, outputs) {
+        const start = Date.now();
+        // deliberately exceed the budget
+        while (Date.now() - start < 5) {}
+
+        outputs[0].forEach(channel => channel.fill(0.5));
+        return true;
+      }
+    }
+
+    registerProcessor('busy-processor', BusyProcessor);
+
+    class LightProcessor extends AudioWorkletProcessor {
+      process(inputs, outputs) {
+        outputs[0].forEach(channel => channel.fill(0.5));
+        return true;
+      }
+    }
+
+    registerProcessor('light-processor', LightProcessor);
+    `;
+
+    async function renderWithBudget(processorName) {
+      const sampleRate = 48000;
+      const context = new OfflineAudioContext({
+        numberOfChannels: 2,
+        length: 128 * 20,
+        sampleRate,
+        workletProcessBudget: { maxDuration: 0.001, maxConsecutiveOverruns: 3 },
+      });
+
+      const blob = new Blob([budgetScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      const merger = context.createChannelMerger(2);
+      merger.connect(context.destination);
+
+      const worklet = new AudioWorkletNode(context, processorName, {
+        outputChannelCount: [1],
+      });
+      worklet.connect(merger, 0, 0);
+
+      const errors = [];
+      worklet.onprocessorerror = event => errors.push(event);
+
+      // rest of the graph
+      const src = context.createConstantSource();
+      src.connect(merger, 0, 1);
+      src.start();
+
+      const buffer = await context.startRendering();
+      // let the processorerror event be dispatched
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      return { buffer, errors };
+    }
+
+    it('should disable processors exceeding their budget', async () => {
+      const { buffer, errors } = await renderWithBudget('busy-processor');
+
+      assert.equal(errors.length, 1);
+      assert.include(errors[0].message, 'CPU budget');
+
+      const workletChannel = buffer.getChannelData(0);
+      assert.equal(workletChannel[0], 0.5);
+      // processor has been disabled after 3 render quanta
+      assert.deepEqual(workletChannel.subarray(128 * 4), new Float32Array(128 * 16));
+      // the rest of the graph keeps rendering
+      assert.deepEqual(buffer.getChannelData(1), new Float32Array(128 * 20).fill(1));
+    });
+
+    it('should not disable processors within their budget', async () => {
+      const { buffer, errors } = await renderWithBudget('light-processor');
+
+      assert.equal(errors.length, 0);
+      assert.deepEqual(buffer.getChannelData(0), new Float32Array(128 * 20).fill(0.5));
+    });
+
+    it('should throw on invalid budget', () => {
+      assert.throws(() => new OfflineAudioContext({
+        length: 128,
+        sampleRate: 48000,
+        workletProcessBudget: { maxDuration: 0 },
+      }), RangeError);
+
+      assert.throws(() => new OfflineAudioContext({
+        length: 128,
+        sampleRate: 48000,
+        workletProcessBudget: { maxDuration: 0.001, maxConsecutiveOverruns: 0 },
+      }), RangeError);
+
+      assert.throws(() => new OfflineAudioContext({
+        length: 128,
+        sampleRate: 48000,
+        workletProcessBudget: 0.001,
+      }), TypeError);
+    });
+  });
+
+  describe('# ended event (non spec)', () => {
+    const endedScriptTexts = `
+    class SelfTerminatingProcessor extends AudioWorkletProcessor {
+      constructor(options) {
+        super();
+        this.remaining = options.processorOptions.numberOfQuanta;
+      }
+
+      process(inputs, outputs) {
+        outputs[0].forEach(channel => channel.fill(1));
+        this.remaining -= 1;
+        return this.remaining > 0;
+      }
+    }
+
+    registerProcessor('self-terminating-processor', SelfTerminatingProcessor);
+
+    class PassThroughProcessor extends AudioWorkletProcessor {
+      process(inputs, outputs) {
+        outputs[0].forEach((channel, i) => channel.set(inputs[0][i]));
+        return false;
+      }
+    }
+
+    registerProcessor('pass-through-processor', PassThroughProcessor);
+    `;
+
+    async function createContext(length) {
+      const context = new OfflineAudioContext(1, length, 48000);
+      const blob = new Blob([endedScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      return context;
+    }
+
+    it('should dispatch ended once when a processor without input returns false', async () => {
+      const numberOfQuanta = 4;
+      const context = await createContext(128 * 20);
+
+      const worklet = new AudioWorkletNode(context, 'self-terminating-processor', {
+        numberOfInputs: 0,
+        outputChannelCount: [1],
+        processorOptions: { numberOfQuanta },
+      });
+      worklet.connect(context.destination);
+
+      let endedCount = 0;
+      worklet.onended = () => endedCount += 1;
+
+      const buffer = await context.startRendering();
+      // let the ended event be dispatched
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      assert.equal(endedCount, 1);
+
+      const data = buffer.getChannelData(0);
+      assert.deepEqual(data.subarray(0, 128 * numberOfQuanta), new Float32Array(128 * numberOfQuanta).fill(1));
+      assert.deepEqual(data.subarray(128 * numberOfQuanta), new Float32Array(128 * (20 - numberOfQuanta)));
+    });
+
+    it('should not dispatch ended for processors with inputs, and keep them alive', async () => {
+      const context = await createContext(128 * 10);
+
+      const worklet = new AudioWorkletNode(context, 'pass-through-processor', {
+        outputChannelCount: [1],
+      });
+      worklet.connect(context.destination);
+
+      let ended = false;
+      worklet.onended = () => ended = true;
+
+      const src = context.createConstantSource();
+      src.connect(worklet);
+      src.start(128 * 2 / context.sampleRate);
+
+      const buffer = await context.startRendering();
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      assert.isFalse(ended);
+
+      const data = buffer.getChannelData(0);
+      assert.deepEqual(data.subarray(0, 128 * 2), new Float32Array(128 * 2));
+      assert.deepEqual(data.subarray(128 * 2), new Float32Array(128 * 8).fill(1));
+    });
+  });
+
+  describe('# connect to AudioParam', () => {
+    const modulatorScriptTexts = `
+    class ModulatorProcessor extends AudioWorkletProcessor {
+      constructor() {
+        super();
+        this.frame = 0;
+      }
+
+      process(inputs, outputs) {
+        const [sine, constant] = outputs;
+
+        for (let i = 0; i < sine[0].length; i++) {
+          sine[0][i] = 0.5 * Math.sin(2 * Math.PI * 2 * this.frame / sampleRate);
+          this.frame += 1;
+        }
+
+        constant[0].fill(0.25);
+
+        return true;
+      }
+    }
+
+    registerProcessor('modulator-processor', ModulatorProcessor);
+    `;
+
+    async function renderModulatedGain(output) {
+      const sampleRate = 48000;
+      const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+      const blob = new Blob([modulatorScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      const modulator = new AudioWorkletNode(context, 'modulator-processor', {
+        numberOfInputs: 0,
+        numberOfOutputs: 2,
+        outputChannelCount: [1, 1],
+      });
+
+      const gain = context.createGain();
+      gain.gain.value = 0;
+      modulator.connect(gain.gain, output);
+      gain.connect(context.destination);
+
+      const src = context.createConstantSource();
+      src.connect(gain);
+      src.start();
+
+      const buffer = await context.startRendering();
+      return buffer.getChannelData(0);
+    }
+
+    it('should modulate the param with the selected output', async () => {
+      const sine = await renderModulatedGain(0);
+
+      for (let i = 0; i < sine.length; i += 100) {
+        const expected = 0.5 * Math.sin(2 * Math.PI * 2 * i / 48000);
+        assert.approximately(sine[i], expected, 1e-4, `frame ${i}`);
+      }
+
+      const constant = await renderModulatedGain(1);
+      assert.deepEqual(constant, new Float32Array(constant.length).fill(0.25));
+    });
+  });
+
+  describe('# port messages', () => {
+    const echoScriptTexts = `
+    function describeView(view) {
+      return {
+        type: view.constructor.name,
+        byteOffset: view.byteOffset,
+        byteLength: view.byteLength,
+        length: view.length,
+        bufferByteLength: view.buffer.byteLength,
+        values: Array.from(view instanceof DataView ? new Uint8Array(view.buffer, view.byteOffset, view.byteLength) : view),
+      };
+    }
+
+    class EchoProcessor extends AudioWorkletProcessor {
+      constructor() {
+        super();
+
+        this.port.on('message', views => {
+          const descriptions = {};
+          for (const name in views) {
+            descriptions[name] = describeView(views[name]);
+          }
+          // send the views back
+          this.port.postMessage({ descriptions, views });
+        });
+      }
+
+      process() {
+        return true;
+      }
+    }
+
+    registerProcessor('echo-processor', EchoProcessor);
+    `;
+
+    async function createEchoNode() {
+      const context = new OfflineAudioContext(1, 128, 48000);
+      const blob = new Blob([echoScriptTexts], { type: 'application/javascript' });
+      await context.audioWorklet.addModule(URL.createObjectURL(blob));
+
+      return new AudioWorkletNode(context, 'echo-processor');
+    }
+
+    function roundTrip(node, message, transfer = []) {
+      return new Promise(resolve => {
+        node.port.once('message', resolve);
+        node.port.postMessage(message, transfer);
+      });
+    }
+
+    it('should preserve type, byteOffset and length of ArrayBuffer views', async () => {
+      const node = await createEchoNode();
+
+      const buffer = new ArrayBuffer(32);
+      new Uint8Array(buffer).forEach((_, i, arr) => arr[i] = i);
+
+      const float32 = new Float32Array([0, 1, 2, 3, 4, 5, 6, 7]).subarray(2, 6);
+      const int16 = new Int16Array(buffer, 4, 3);
+      const dataView = new DataView(buffer, 8, 4);
+
+      const { descriptions, views } = await roundTrip(node, { float32, int16, dataView });
+      node.port.close();
+      await node.context.startRendering();
+
+      assert.deepEqual(descriptions.float32, {
+        type: 'Float32Array',
+        byteOffset: 8,
+        byteLength: 16,
+        length: 4,
+        bufferByteLength: 32,
+        values: [2, 3, 4, 5],
+      });
+
+      assert.deepEqual(descriptions.int16, {
+        type: 'Int16Array',
+        byteOffset: 4,
+        byteLength: 6,
+        length: 3,
+        bufferByteLength: 32,
+        values: Array.from(int16),
+      });
+
+      assert.deepEqual(descriptions.dataView, {
+        type: 'DataView',
+        byteOffset: 8,
+        byteLength: 4,
+        length: undefined,
+        bufferByteLength: 32,
+        values: [8, 9, 10, 11],
+      });
+
+      // and back to the main thread
+      assert.isTrue(views.float32 instanceof Float32Array);
+      assert.deepEqual(views.float32, float32);
+      assert.isTrue(views.int16 instanceof Int16Array);
+      assert.deepEqual(views.int16, int16);
+      assert.isTrue(views.dataView instanceof DataView);
+      assert.equal(views.dataView.byteOffset, 8);
+      assert.equal(views.dataView.getUint8(0), 8);
+    });
+
+    it('should transfer the underlying buffer of views', async () => {
+      const node = await createEchoNode();
+
+      const float32 = new Float32Array([0, 1, 2, 3, 4, 5, 6, 7]).subarray(2, 6);
+      const { descriptions } = await roundTrip(node, { float32 }, [float32.buffer]);
+      node.port.close();
+      await node.context.startRendering();
+
+      // detached on the main thread
+      assert.equal(float32.buffer.byteLength, 0);
+      assert.equal(float32.length, 0);
+
+      assert.equal(descriptions.float32.byteOffset, 8);
+      assert.equal(descriptions.float32.length, 4);
+      assert.deepEqual(descriptions.float32.values, [2, 3, 4, 5]);
+    });
+  });
 });
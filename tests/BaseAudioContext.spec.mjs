@@ -0,0 +1,737 @@
+import fs from 'node:fs';
+import path from 'node:path';
+import { assert } from 'chai';
+import {
+  AudioBuffer,
+  AudioContext,
+  BaseAudioContext,
+  BiquadFilterNode,
+  ConstantSourceNode,
+  GainNode,
+  IIRFilterNode,
+  OfflineAudioContext,
+  OscillatorNode,
+  closeAllContexts,
+  probe,
+} from '../index.mjs';
+import { createWav } from './helpers.mjs';
+
+describe('# BaseAudioContext', () => {
+  describe('## panic()', () => {
+    it('should stop all sources and fade output to silence', async () => {
+      const sampleRate = 48000;
+      const panicFrame = 128 * 32;
+      const declickFrames = 0.005 * sampleRate;
+      const offline = new OfflineAudioContext(1, sampleRate / 4, sampleRate);
+
+      [200, 300, 400].forEach(frequency => {
+        const osc = offline.createOscillator();
+        osc.frequency.value = frequency;
+        osc.connect(offline.destination);
+        osc.start(0);
+      });
+
+      offline.suspend(panicFrame / sampleRate).then(() => {
+        offline.panic();
+        // should be safe to call several times
+        offline.panic();
+        offline.resume();
+      });
+
+      const buffer = await offline.startRendering();
+      const data = buffer.getChannelData(0);
+
+      let energyBefore = 0;
+      for (let i = 0; i < panicFrame; i++) {
+        energyBefore += Math.abs(data[i]);
+      }
+      assert.isAbove(energyBefore, 0);
+
+      // fade out should be monotonic in amplitude
+      const fadeStart = Math.max(...data.subarray(panicFrame - 200, panicFrame).map(Math.abs));
+      const fadeEnd = Math.max(...data.subarray(panicFrame + declickFrames - 20, panicFrame + declickFrames).map(Math.abs));
+      assert.isBelow(fadeEnd, fadeStart);
+
+      for (let i = panicFrame + declickFrames; i < data.length; i++) {
+        assert.equal(data[i], 0, `sample ${i} should be 0`);
+      }
+    });
+
+    it('should remove the fade out once the stopped sources have ended', async () => {
+      const sampleRate = 48000;
+      const offline = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+
+      // playing, and scheduled after the panic
+      [0, 0.2].forEach(when => {
+        const src = offline.createConstantSource();
+        src.connect(offline.destination);
+        src.start(when);
+      });
+
+      offline.suspend(128 * 8 / sampleRate).then(() => {
+        offline.panic();
+
+        const src = offline.createConstantSource();
+        src.connect(offline.destination);
+        src.start(0.1);
+
+        offline.resume();
+      });
+
+      // let the ended events be dispatched
+      offline.suspend(128 * 100 / sampleRate).then(async () => {
+        await new Promise(resolve => setTimeout(resolve, 100));
+        offline.resume();
+      });
+
+      const buffer = await offline.startRendering();
+      const data = buffer.getChannelData(0);
+
+      assert.equal(data[Math.round(0.1 * sampleRate) - 1], 0);
+      assert.equal(data[data.length - 1], 1);
+    });
+
+    it('should not prevent sources started afterward to be heard', async () => {
+      const sampleRate = 48000;
+      const offline = new OfflineAudioContext(1, sampleRate / 4, sampleRate);
+      offline.panic();
+
+      const src = offline.createConstantSource();
+      src.connect(offline.destination);
+      src.start(0.1);
+
+      const buffer = await offline.startRendering();
+      const data = buffer.getChannelData(0);
+
+      assert.equal(data[data.length - 1], 1);
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer, { trackIndex })', () => {
+    // two vorbis streams multiplexed in the same ogg container, i.e. sample.ogg (stereo)
+    // and major-scale.ogg (mono)
+    const pathname = path.join('examples', 'samples', 'two-tracks.ogg');
+
+    it('probe() should list the tracks of the container', async () => {
+      const tracks = await probe(fs.readFileSync(pathname).buffer);
+
+      assert.equal(tracks.length, 2);
+      tracks.forEach((track, index) => {
+        assert.equal(track.index, index);
+        assert.equal(track.codec, 'vorbis');
+        assert.isAbove(track.duration, 0);
+      });
+
+      assert.deepEqual(tracks.map(track => track.numberOfChannels).sort(), [1, 2]);
+    });
+
+    it('probe() should reject with EncodingError on invalid data', async () => {
+      let errored = false;
+
+      try {
+        await probe(new ArrayBuffer(32));
+      } catch (err) {
+        errored = true;
+        assert.equal(err.name, 'EncodingError');
+      }
+
+      assert.isTrue(errored);
+    });
+
+    it('should decode each track separately', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const tracks = await probe(fs.readFileSync(pathname).buffer);
+
+      for (let track of tracks) {
+        const buffer = await context.decodeAudioData(fs.readFileSync(pathname).buffer, {
+          trackIndex: track.index,
+        });
+
+        assert.equal(buffer.numberOfChannels, track.numberOfChannels);
+        assert.equal(buffer.sampleRate, 48000);
+        assert.approximately(buffer.duration, track.duration, 0.1);
+      }
+    });
+
+    it('should match the decoding of the single track files', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const tracks = await probe(fs.readFileSync(pathname).buffer);
+      const stereoTrack = tracks.find(track => track.numberOfChannels === 2);
+
+      const expected = await context.decodeAudioData(
+        fs.readFileSync(path.join('examples', 'samples', 'sample.ogg')).buffer,
+      );
+      // options can also be given as last argument
+      const result = await context.decodeAudioData(
+        fs.readFileSync(pathname).buffer,
+        undefined,
+        undefined,
+        { trackIndex: stereoTrack.index },
+      );
+
+      assert.equal(result.length, expected.length);
+      assert.deepEqual(result.getChannelData(1), expected.getChannelData(1));
+    });
+
+    it('should reject with IndexSizeError if trackIndex is out of range', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      let errored = false;
+
+      try {
+        await context.decodeAudioData(fs.readFileSync(pathname).buffer, { trackIndex: 2 });
+      } catch (err) {
+        errored = true;
+        assert.equal(err.name, 'IndexSizeError');
+      }
+
+      assert.isTrue(errored);
+    });
+
+    it('should call decodeErrorCallback with IndexSizeError', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      let error = null;
+
+      await context.decodeAudioData(
+        fs.readFileSync(pathname).buffer,
+        () => {},
+        err => error = err,
+        { trackIndex: 42 },
+      );
+
+      assert.equal(error.name, 'IndexSizeError');
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer, { signal })', () => {
+    const pathname = path.join('examples', 'samples', 'vocals-dry.wav');
+
+    it('should reject with AbortError if aborted', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const controller = new AbortController();
+      let error = null;
+
+      const promise = context.decodeAudioData(fs.readFileSync(pathname).buffer, {
+        signal: controller.signal,
+      });
+      controller.abort();
+
+      try {
+        await promise;
+      } catch (err) {
+        error = err;
+      }
+
+      assert.isTrue(error instanceof DOMException);
+      assert.equal(error.name, 'AbortError');
+    });
+
+    it('should reject with AbortError if already aborted', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      let error = null;
+
+      await context.decodeAudioData(
+        fs.readFileSync(pathname).buffer,
+        () => {},
+        err => error = err,
+        { signal: AbortSignal.abort() },
+      );
+
+      assert.equal(error.name, 'AbortError');
+    });
+
+    it('should decode normally if not aborted', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const controller = new AbortController();
+
+      const expected = await context.decodeAudioData(fs.readFileSync(pathname).buffer);
+      const result = await context.decodeAudioData(fs.readFileSync(pathname).buffer, {
+        signal: controller.signal,
+      });
+      // no-op once decoded
+      controller.abort();
+
+      assert.equal(result.numberOfChannels, expected.numberOfChannels);
+      assert.equal(result.length, expected.length);
+      assert.deepEqual(result.getChannelData(0), expected.getChannelData(0));
+    });
+
+    it('should throw if signal is not an AbortSignal', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      let errored = false;
+
+      try {
+        await context.decodeAudioData(fs.readFileSync(pathname).buffer, { signal: {} });
+      } catch (err) {
+        errored = true;
+        assert.isTrue(err instanceof TypeError);
+      }
+
+      assert.isTrue(errored);
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer, { into })', () => {
+    function createTone(frequency, length, sampleRate) {
+      return Float32Array.from({ length }, (_, i) => 0.5 * Math.sin(2 * Math.PI * frequency * i / sampleRate));
+    }
+
+    it('should decode into the provided buffer', async () => {
+      const sampleRate = 48000;
+      const length = 1000;
+      const context = new OfflineAudioContext(2, 1, sampleRate);
+
+      const first = createWav([createTone(440, length, sampleRate), createTone(660, length, sampleRate)], sampleRate);
+      const second = createWav([createTone(220, length, sampleRate), createTone(330, length, sampleRate)], sampleRate);
+      const expected = await context.decodeAudioData(second.slice(0));
+
+      const buffer = context.createBuffer(2, length, sampleRate);
+      // views on the channels are kept up to date
+      const left = buffer.getChannelData(0);
+
+      const firstResult = await context.decodeAudioData(first, { into: buffer });
+      assert.equal(firstResult, buffer);
+      assert.approximately(left[10], 0.5 * Math.sin(2 * Math.PI * 440 * 10 / sampleRate), 1e-4);
+
+      const secondResult = await context.decodeAudioData(second, { into: buffer });
+      assert.equal(secondResult, buffer);
+      assert.deepEqual(buffer.getChannelData(0), expected.getChannelData(0));
+      assert.deepEqual(buffer.getChannelData(1), expected.getChannelData(1));
+      assert.deepEqual(left, expected.getChannelData(0));
+    });
+
+    it('should reject with NotSupportedError if the shape does not match', async () => {
+      const sampleRate = 48000;
+      const context = new OfflineAudioContext(1, 1, sampleRate);
+      const wav = createWav([createTone(440, 1000, sampleRate)], sampleRate);
+
+      for (const buffer of [
+        context.createBuffer(2, 1000, sampleRate),
+        context.createBuffer(1, 999, sampleRate),
+        context.createBuffer(1, 1000, 44100),
+      ]) {
+        let error = null;
+
+        try {
+          await context.decodeAudioData(wav.slice(0), { into: buffer });
+        } catch (err) {
+          error = err;
+        }
+
+        assert.isTrue(error instanceof DOMException);
+        assert.equal(error.name, 'NotSupportedError');
+      }
+    });
+
+    it('should resample into the provided buffer', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const wav = createWav([createTone(440, 1000, 24000)], 24000);
+      const expected = await context.decodeAudioData(wav.slice(0));
+
+      const buffer = context.createBuffer(1, expected.length, 48000);
+      await context.decodeAudioData(wav, { into: buffer });
+
+      assert.deepEqual(buffer.getChannelData(0), expected.getChannelData(0));
+    });
+
+    it('should keep the buffer usable until the decoding is settled', async () => {
+      const sampleRate = 48000;
+      const context = new OfflineAudioContext(1, 1, sampleRate);
+      const wav = createWav([createTone(440, 1000, sampleRate)], sampleRate);
+      const buffer = context.createBuffer(1, 1000, sampleRate);
+
+      const decoding = context.decodeAudioData(wav.slice(0), { into: buffer });
+
+      buffer.getChannelData(0)[0] = 0.5;
+      assert.equal(buffer.getChannelData(0)[0], 0.5);
+
+      let error = null;
+
+      try {
+        await context.decodeAudioData(wav.slice(0), { into: buffer });
+      } catch (err) {
+        error = err;
+      }
+
+      assert.equal(error.name, 'InvalidStateError');
+      assert.equal(await decoding, buffer);
+      assert.equal(buffer.getChannelData(0).length, 1000);
+      assert.equal(buffer.getChannelData(0)[0], 0);
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer, { metadata }) (non spec)', () => {
+    it('should resolve with the buffer and the metadata of the file', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const data = fs.readFileSync(path.join('examples', 'samples', 'sample.mp3')).buffer;
+      const expected = await context.decodeAudioData(data.slice(0));
+
+      const { audioBuffer, metadata } = await context.decodeAudioData(data.slice(0), { metadata: true });
+
+      assert.isTrue(audioBuffer instanceof AudioBuffer);
+      assert.equal(audioBuffer.sampleRate, 48000);
+      assert.equal(audioBuffer.length, expected.length);
+      assert.equal(metadata.codec, 'mp3');
+      // the file is encoded at 44.1kHz
+      assert.equal(metadata.sampleRate, 44100);
+      assert.equal(metadata.numberOfChannels, 2);
+      assert.isNull(metadata.bitDepth);
+      assert.approximately(metadata.duration, expected.duration, 1e-3);
+
+      const wav = fs.readFileSync(path.join('examples', 'samples', 'sample.wav')).buffer;
+      const result = await context.decodeAudioData(wav, { metadata: true });
+      assert.equal(result.metadata.bitDepth, 16);
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer) unsupported formats (non spec)', () => {
+    async function getDecodeError(context, data, options) {
+      try {
+        await context.decodeAudioData(data, options);
+      } catch (err) {
+        return err;
+      }
+
+      assert.fail('decodeAudioData should have rejected');
+    }
+
+    it('should name the detected format and list the supported ones', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      // opus stream in a webm container, the opus codec is not compiled in
+      const data = fs.readFileSync(path.join('examples', 'samples', 'sample.webm')).buffer;
+
+      for (const options of [undefined, { metadata: true }]) {
+        const err = await getDecodeError(context, data.slice(0), options);
+
+        assert.instanceOf(err, DOMException);
+        assert.equal(err.name, 'EncodingError');
+        assert.include(err.message, `unsupported format 'opus'`);
+        assert.include(err.message, 'supported: wav, mp3, flac');
+      }
+
+      const aiff = fs.readFileSync(path.join('examples', 'samples', 'sample.aiff')).buffer;
+      const err = await getDecodeError(context, aiff);
+      assert.include(err.message, `unsupported format 'aiff'`);
+    });
+
+    it('should keep the error of malformed files of supported formats', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const data = fs.readFileSync(path.join('examples', 'samples', 'corrupt.wav')).buffer;
+
+      const err = await getDecodeError(context, data);
+      assert.equal(err.name, 'EncodingError');
+      assert.notInclude(err.message, 'unsupported format');
+    });
+  });
+
+  describe('## decodeAudioData(arrayBuffer, { normalize }) (non spec)', () => {
+    // mono sine with the given peak
+    function quietWav(peak, sampleRate, length) {
+      const samples = Float32Array.from({ length }, (_, i) => peak * Math.sin(2 * Math.PI * 441 * i / sampleRate));
+      return createWav([samples], sampleRate);
+    }
+
+    it('should scale the buffer to the target peak and report the gain', async () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const wav = quietWav(0.05, 48000, 4800);
+
+      const original = await context.decodeAudioData(wav.slice(0));
+      assert.approximately(Math.max(...original.getChannelData(0).map(Math.abs)), 0.05, 1e-3);
+      assert.equal((await context.decodeAudioData(wav.slice(0), { metadata: true })).metadata.normalizationGain, 1);
+
+      const { audioBuffer, metadata } = await context.decodeAudioData(wav.slice(0), {
+        metadata: true,
+        normalize: true,
+      });
+      const target = Math.pow(10, -1 / 20);
+      const peak = Math.max(...audioBuffer.getChannelData(0).map(Math.abs));
+
+      assert.approximately(peak, target, 1e-6);
+      assert.approximately(metadata.normalizationGain, target / 0.05, 0.1);
+
+      const louder = await context.decodeAudioData(wav.slice(0), { normalize: true, normalizeTarget: -6 });
+      assert.approximately(Math.max(...louder.getChannelData(0).map(Math.abs)), Math.pow(10, -6 / 20), 1e-6);
+    });
+  });
+
+  describe('## timeAtBeat(beat, bpm) / scheduleAtBeat(node, beat, bpm)', () => {
+    it('should compute time relative to currentTime', () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+
+      assert.equal(context.timeAtBeat(0, 120), 0);
+      assert.equal(context.timeAtBeat(1, 120), 0.5);
+      assert.equal(context.timeAtBeat(3, 90), 2);
+    });
+
+    it('should throw RangeError on negative beat or invalid bpm', () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+      const src = context.createConstantSource();
+
+      assert.throws(() => context.timeAtBeat(-1, 120), RangeError);
+      assert.throws(() => context.timeAtBeat(1, 0), RangeError);
+      assert.throws(() => context.scheduleAtBeat(src, -0.5, 120), RangeError);
+      assert.throws(() => context.scheduleAtBeat({}, 1, 120), TypeError);
+    });
+
+    it('should schedule sources one beat apart at 120 BPM', async () => {
+      const sampleRate = 48000;
+      const context = new OfflineAudioContext(2, sampleRate * 1.5, sampleRate);
+      const merger = context.createChannelMerger(2);
+      merger.connect(context.destination);
+
+      const startTimes = [1, 2].map((beat, index) => {
+        const src = context.createConstantSource();
+        src.connect(merger, 0, index);
+        return context.scheduleAtBeat(src, beat, 120);
+      });
+
+      assert.deepEqual(startTimes, [0.5, 1]);
+
+      const buffer = await context.startRendering();
+      const onsets = [0, 1].map(channel => buffer.getChannelData(channel).indexOf(1));
+
+      assert.equal(onsets[0], 0.5 * sampleRate);
+      assert.equal((onsets[1] - onsets[0]) / sampleRate, 0.5);
+    });
+  });
+
+  describe('## getCombinedFrequencyResponse(filters, frequencyHz, magResponse, phaseResponse) (non spec)', () => {
+    const sampleRate = 48000;
+
+    it('should combine the responses of the filters', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const lowpass = new BiquadFilterNode(context, { type: 'lowpass', frequency: 4000 });
+      const highpass = new BiquadFilterNode(context, { type: 'highpass', frequency: 500 });
+      // one pole lowpass
+      const iir = new IIRFilterNode(context, { feedforward: [0.5], feedback: [1, -0.5] });
+
+      const frequencyHz = new Float32Array([50, 1500, 20000]);
+      const magResponse = new Float32Array(3);
+      const phaseResponse = new Float32Array(3);
+
+      context.getCombinedFrequencyResponse([lowpass, highpass, iir], frequencyHz, magResponse, phaseResponse);
+
+      const lowpassMag = new Float32Array(3);
+      const highpassMag = new Float32Array(3);
+      const iirMag = new Float32Array(3);
+      lowpass.getFrequencyResponse(frequencyHz, lowpassMag, new Float32Array(3));
+      highpass.getFrequencyResponse(frequencyHz, highpassMag, new Float32Array(3));
+      iir.getFrequencyResponse(frequencyHz, iirMag, new Float32Array(3));
+
+      for (let i = 0; i < 3; i++) {
+        assert.approximately(magResponse[i], lowpassMag[i] * highpassMag[i] * iirMag[i], 1e-6);
+        assert.isAtLeast(phaseResponse[i], -Math.PI);
+        assert.isAtMost(phaseResponse[i], Math.PI);
+      }
+
+      // band pass shaped
+      assert.isAbove(magResponse[1], 0.5);
+      assert.isBelow(magResponse[0], 0.05);
+      assert.isBelow(magResponse[2], 0.05);
+    });
+
+    it('should throw InvalidAccessError if the lengths of the arrays mismatch', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const lowpass = new BiquadFilterNode(context);
+
+      assert.throws(() => {
+        context.getCombinedFrequencyResponse([lowpass], new Float32Array(3));
+      }, TypeError, /4 arguments required/);
+
+      try {
+        context.getCombinedFrequencyResponse([lowpass], new Float32Array(3), new Float32Array(2), new Float32Array(3));
+        assert.fail('should have thrown');
+      } catch (err) {
+        assert.instanceOf(err, DOMException);
+        assert.equal(err.name, 'InvalidAccessError');
+      }
+    });
+  });
+
+  describe('## exportGraph() / BaseAudioContext.importGraph(context, json) (non spec)', () => {
+    const sampleRate = 48000;
+
+    // square oscillator through a resonant lowpass modulated by an LFO
+    function createSynth(context) {
+      const osc = new OscillatorNode(context, { type: 'square', frequency: 220 });
+      const filter = new BiquadFilterNode(context, { type: 'lowpass', Q: 5 });
+      const output = new GainNode(context, { gain: 0.5 });
+      const lfo = new OscillatorNode(context, { frequency: 5 });
+      const depth = new GainNode(context, { gain: 400 });
+
+      filter.frequency.value = 1000;
+      osc.connect(filter).connect(output).connect(context.destination);
+      lfo.connect(depth).connect(filter.frequency);
+
+      return [osc, lfo];
+    }
+
+    async function render(context, sources) {
+      sources.forEach(src => src.start());
+      return (await context.startRendering()).getChannelData(0);
+    }
+
+    it('should reconstruct the graph in another context', async () => {
+      const context = new OfflineAudioContext(1, sampleRate / 10, sampleRate);
+      const sources = createSynth(context);
+      // serialize and parse to check the description is plain JSON
+      const json = JSON.parse(JSON.stringify(context.exportGraph()));
+
+      assert.equal(json.nodes.length, 6);
+      assert.equal(json.connections.length, 5);
+      assert.deepEqual(json.connections.find(c => 'param' in c), {
+        source: json.nodes.find(node => node.options.gain === 400).id,
+        output: 0,
+        destination: json.nodes.find(node => node.type === 'BiquadFilterNode').id,
+        param: 'frequency',
+      });
+
+      const other = new OfflineAudioContext(1, sampleRate / 10, sampleRate);
+      const nodes = BaseAudioContext.importGraph(other, json);
+
+      assert.equal(nodes.length, 6);
+      assert.equal(nodes[json.nodes.findIndex(node => node.type === 'AudioDestinationNode')], other.destination);
+
+      const otherSources = nodes.filter(node => node instanceof OscillatorNode);
+      assert.deepEqual(otherSources.map(osc => osc.frequency.value), sources.map(osc => osc.frequency.value));
+
+      const expected = await render(context, sources);
+      const output = await render(other, otherSources);
+
+      assert.isTrue(expected.some(value => value !== 0));
+      for (let i = 0; i < expected.length; i++) {
+        assert.approximately(output[i], expected[i], 1e-6);
+      }
+    });
+
+    it('should flag the nodes that are not reconstructible', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const wave = context.createPeriodicWave(new Float32Array([0, 1]), new Float32Array([0, 0]));
+      const custom = new OscillatorNode(context, { periodicWave: wave });
+      custom.connect(new GainNode(context)).connect(context.destination);
+
+      const json = context.exportGraph();
+      const desc = json.nodes.find(node => node.type === 'OscillatorNode');
+      assert.isFalse(desc.reconstructible);
+
+      const other = new OfflineAudioContext(1, 128, sampleRate);
+      const nodes = BaseAudioContext.importGraph(other, JSON.stringify(json));
+
+      assert.isNull(nodes[desc.id]);
+      assert.equal(nodes.filter(node => node instanceof GainNode).length, 1);
+
+      assert.throws(() => BaseAudioContext.importGraph(other, {}), TypeError);
+    });
+
+    it('should not export internal nodes nor disconnected nodes', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const filter = new BiquadFilterNode(context);
+      filter.bypass = true;
+      filter.connect(context.destination);
+
+      const src = new ConstantSourceNode(context);
+      src.connect(filter);
+      src.disconnect();
+
+      const json = context.exportGraph();
+      assert.deepEqual(json.nodes.map(node => node.type), ['BiquadFilterNode', 'AudioDestinationNode']);
+      assert.isTrue(json.nodes[0].options.bypass);
+    });
+
+    it('should only read the params of the nodes', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      let reads = 0;
+
+      class CountingGainNode extends GainNode {
+        get reads() {
+          reads += 1;
+          return reads;
+        }
+      }
+
+      const gain = new CountingGainNode(context, { gain: 0.25 });
+      gain.connect(context.destination);
+
+      const json = context.exportGraph();
+      assert.equal(reads, 0);
+      assert.equal(json.nodes[0].options.gain, 0.25);
+    });
+  });
+
+  describe('## waitForState(state) (non spec)', () => {
+    it('should resolve once the context is running', async () => {
+      const context = new AudioContext({ sinkId: { type: 'none' } });
+      // suspend is a no-op if the context is not running yet
+      await context.waitForState('running');
+      await context.suspend();
+      assert.equal(context.state, 'suspended');
+
+      let resolved = false;
+      const promise = context.waitForState('running').then(() => resolved = true);
+      // let the promise resolve if it would resolve immediately
+      await new Promise(resolve => setTimeout(resolve, 50));
+      assert.isFalse(resolved);
+
+      await context.resume();
+      await promise;
+      assert.isTrue(resolved);
+      assert.equal(context.state, 'running');
+
+      // already in the state
+      await context.waitForState('running');
+      await context.close();
+    });
+
+    it('should reject if the context is closed first', async () => {
+      const context = new AudioContext({ sinkId: { type: 'none' } });
+      await context.suspend();
+
+      let error = null;
+      const running = context.waitForState('running').catch(err => error = err);
+      const closed = context.waitForState('closed');
+      await context.close();
+
+      await running;
+      assert.equal(error.name, 'InvalidStateError');
+      await closed;
+
+      assert.throws(() => context.waitForState('resumed'), TypeError);
+    });
+  });
+
+  describe('## closeAllContexts() (non spec)', () => {
+    it('should close all contexts and release their resources', async () => {
+      const contexts = [
+        new AudioContext({ sinkId: { type: 'none' } }),
+        new AudioContext({ sinkId: { type: 'none' } }),
+        // never started
+        new OfflineAudioContext(1, 128, 48000),
+      ];
+
+      // suspended while rendering
+      const rendering = new OfflineAudioContext(1, 48000, 48000);
+      const suspended = rendering.suspendAtFrame(128);
+      const rendered = rendering.startRendering();
+      await suspended;
+      contexts.push(rendering);
+
+      await closeAllContexts();
+
+      for (const context of contexts) {
+        assert.equal(context.state, 'closed');
+      }
+
+      assert.equal((await rendered).length, 48000);
+      // the process does not hold any context anymore
+      const handles = Object.getOwnPropertySymbols(process)
+        .filter(symbol => symbol.description.startsWith('node-web-audio-api:audio-context'));
+      assert.equal(handles.length, 0);
+
+      // idempotent
+      await closeAllContexts();
+
+      let error = null;
+      await contexts[2].startRendering().catch(err => error = err);
+      assert.equal(error.name, 'InvalidStateError');
+    });
+  });
+});
@@ -0,0 +1,134 @@
+import { assert } from 'chai';
+import {
+  BiquadFilterNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# BiquadFilterNode', () => {
+  describe('## type', () => {
+    // magnitude at [100, 1000, 10000] Hz of a filter centered on 1000Hz,
+    // 12dB of gain for the types using it
+    function getMagnitudes(type) {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const node = new BiquadFilterNode(context, { frequency: 1000, gain: 12 });
+      node.type = type;
+      assert.equal(node.type, type);
+
+      const frequencyHz = new Float32Array([100, 1000, 10000]);
+      const magResponse = new Float32Array(3);
+      const phaseResponse = new Float32Array(3);
+      node.getFrequencyResponse(frequencyHz, magResponse, phaseResponse);
+
+      return Array.from(magResponse);
+    }
+
+    it('should support all the filter types', () => {
+      const boost = Math.pow(10, 12 / 20);
+      let low, center, high;
+
+      [low, center, high] = getMagnitudes('lowpass');
+      assert.approximately(low, 1, 0.05);
+      assert.isBelow(high, 0.05);
+
+      [low, center, high] = getMagnitudes('highpass');
+      assert.isBelow(low, 0.05);
+      assert.approximately(high, 1, 0.05);
+
+      [low, center, high] = getMagnitudes('bandpass');
+      assert.approximately(center, 1, 0.05);
+      assert.isBelow(low, 0.2);
+      assert.isBelow(high, 0.2);
+
+      [low, center, high] = getMagnitudes('lowshelf');
+      assert.approximately(low, boost, 0.1);
+      assert.approximately(high, 1, 0.05);
+
+      [low, center, high] = getMagnitudes('highshelf');
+      assert.approximately(low, 1, 0.05);
+      assert.approximately(high, boost, 0.1);
+
+      [low, center, high] = getMagnitudes('peaking');
+      assert.approximately(low, 1, 0.05);
+      assert.approximately(center, boost, 0.05);
+      assert.approximately(high, 1, 0.05);
+
+      [low, center, high] = getMagnitudes('notch');
+      assert.isBelow(center, 0.01);
+      assert.approximately(low, 1, 0.05);
+      assert.approximately(high, 1, 0.05);
+
+      [low, center, high] = getMagnitudes('allpass');
+      assert.approximately(low, 1, 1e-3);
+      assert.approximately(center, 1, 1e-3);
+      assert.approximately(high, 1, 1e-3);
+    });
+
+    it('should throw TypeError on unknown types and keep the current type', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const node = new BiquadFilterNode(context, { type: 'notch' });
+
+      assert.throws(() => node.type = 'bandstop', TypeError);
+      assert.throws(() => node.type = 'LOWPASS', TypeError);
+      assert.throws(() => node.type = null, TypeError);
+      assert.equal(node.type, 'notch');
+    });
+  });
+
+  describe('## Q and gain', () => {
+    // peak amplitude of the steady state output of a unit sine at `frequency`
+    async function renderSine(frequency, options, automate = () => {}) {
+      const length = sampleRate / 4;
+      const context = new OfflineAudioContext(1, length, sampleRate);
+      const osc = context.createOscillator();
+      osc.frequency.value = frequency;
+      const filter = new BiquadFilterNode(context, options);
+      automate(filter);
+
+      osc.connect(filter).connect(context.destination);
+      osc.start();
+
+      const buffer = await context.startRendering();
+      const data = buffer.getChannelData(0).subarray(length / 2);
+
+      return data.reduce((peak, value) => Math.max(peak, Math.abs(value)), 0);
+    }
+
+    it('should have the spec defaults and ranges', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const node = new BiquadFilterNode(context);
+      const mostPositiveFloat = 3.4028234663852886e38;
+
+      assert.equal(node.Q.defaultValue, 1);
+      assert.equal(node.Q.minValue, -mostPositiveFloat);
+      assert.equal(node.Q.maxValue, mostPositiveFloat);
+
+      assert.equal(node.gain.defaultValue, 0);
+      assert.equal(node.gain.minValue, -mostPositiveFloat);
+      assert.approximately(node.gain.maxValue, 40 * Math.log10(mostPositiveFloat), 1e-3);
+    });
+
+    it('should boost a peaking filter around its center frequency', async () => {
+      const options = { type: 'peaking', frequency: 1000, Q: 1 };
+
+      const flat = await renderSine(1000, options);
+      const boosted = await renderSine(1000, options, filter => filter.gain.value = 12);
+      const far = await renderSine(100, options, filter => filter.gain.value = 12);
+
+      assert.approximately(flat, 1, 0.01);
+      assert.approximately(boosted, Math.pow(10, 12 / 20), 0.05);
+      assert.approximately(far, 1, 0.1);
+    });
+
+    it('should have no effect on the types ignoring them', async () => {
+      const lowpass = await renderSine(500, { type: 'lowpass', frequency: 1000 });
+      const lowpassGain = await renderSine(500, { type: 'lowpass', frequency: 1000 }, filter => filter.gain.value = 12);
+      assert.equal(lowpassGain, lowpass);
+
+      const lowshelf = await renderSine(500, { type: 'lowshelf', frequency: 1000, gain: 6 });
+      const lowshelfQ = await renderSine(500, { type: 'lowshelf', frequency: 1000, gain: 6 }, filter => filter.Q.value = 10);
+      assert.equal(lowshelfQ, lowshelf);
+    });
+  });
+});
@@ -0,0 +1,74 @@
+import { assert } from 'chai';
+import {
+  ChannelMatrixNode,
+  ChannelMergerNode,
+  ConstantSourceNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# ChannelMatrixNode', () => {
+  it('should route a stereo input to 4 channels with the given matrix', async () => {
+    const context = new OfflineAudioContext(4, 256, sampleRate);
+    // one row per output channel: [left gain, right gain]
+    const matrix = new Float32Array([
+      1, 0,
+      0, 1,
+      0.5, 0.5,
+      0.25, -1,
+    ]);
+    const node = new ChannelMatrixNode(context, {
+      inputChannelCount: 2,
+      outputChannelCount: 4,
+      matrix,
+    });
+
+    assert.equal(node.channelCount, 2);
+    assert.equal(node.channelCountMode, 'explicit');
+    assert.equal(node.channelInterpretation, 'discrete');
+    assert.deepEqual(Array.from(node.matrix), Array.from(matrix));
+
+    const left = new ConstantSourceNode(context, { offset: 0.3 });
+    const right = new ConstantSourceNode(context, { offset: -0.7 });
+    const merger = new ChannelMergerNode(context, { numberOfInputs: 2 });
+    left.connect(merger, 0, 0);
+    right.connect(merger, 0, 1);
+    merger.connect(node).connect(context.destination);
+    left.start();
+    right.start();
+
+    const output = await context.startRendering();
+    const input = [0.3, -0.7];
+
+    for (let o = 0; o < 4; o++) {
+      const expected = matrix[o * 2] * input[0] + matrix[o * 2 + 1] * input[1];
+      const data = output.getChannelData(o);
+
+      for (let i = 0; i < data.length; i++) {
+        assert.approximately(data[i], expected, 1e-6, `channel ${o}, frame ${i}`);
+      }
+    }
+  });
+
+  it('should throw NotSupportedError if dimensions are invalid', () => {
+    const context = new OfflineAudioContext(1, 128, sampleRate);
+    const invalid = [
+      { inputChannelCount: 2, outputChannelCount: 4, matrix: new Float32Array(6) },
+      { inputChannelCount: 0, outputChannelCount: 1, matrix: new Float32Array(0) },
+      { inputChannelCount: 33, outputChannelCount: 1, matrix: new Float32Array(33) },
+    ];
+
+    for (const options of invalid) {
+      assert.throws(() => new ChannelMatrixNode(context, options), DOMException);
+
+      try {
+        new ChannelMatrixNode(context, options);
+      } catch (err) {
+        assert.equal(err.name, 'NotSupportedError');
+      }
+    }
+
+    assert.throws(() => new ChannelMatrixNode(context, { inputChannelCount: 1, outputChannelCount: 1 }), TypeError);
+  });
+});
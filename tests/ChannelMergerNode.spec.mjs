@@ -0,0 +1,153 @@
+import { assert } from 'chai';
+import {
+  ChannelMergerNode,
+  ChannelSplitterNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+function assertInvalidState(fn) {
+  assert.throws(fn, DOMException);
+
+  try {
+    fn();
+  } catch (err) {
+    assert.equal(err.name, 'InvalidStateError');
+  }
+}
+
+describe('# ChannelMergerNode', () => {
+  describe('## connect to the same input', () => {
+    it('should sum the sources connected to the same input', async () => {
+      const audioContext = new OfflineAudioContext(2, 128, 48000);
+      const merger = audioContext.createChannelMerger(2);
+      merger.connect(audioContext.destination);
+
+      const src1 = audioContext.createConstantSource();
+      src1.offset.value = 0.25;
+      src1.connect(merger, 0, 0);
+      src1.start();
+
+      const src2 = audioContext.createConstantSource();
+      src2.offset.value = 0.5;
+      src2.connect(merger, 0, 0);
+      src2.start();
+
+      const buffer = await audioContext.startRendering();
+      const left = buffer.getChannelData(0);
+      const right = buffer.getChannelData(1);
+
+      for (let i = 0; i < buffer.length; i++) {
+        assert.equal(left[i], 0.75);
+        assert.equal(right[i], 0);
+      }
+    });
+
+    it('should ignore duplicate connections from the same source', async () => {
+      const audioContext = new OfflineAudioContext(2, 128, 48000);
+      const merger = audioContext.createChannelMerger(2);
+      merger.connect(audioContext.destination);
+
+      const src = audioContext.createConstantSource();
+      src.offset.value = 0.25;
+      src.connect(merger, 0, 0);
+      src.connect(merger, 0, 0);
+      src.start();
+
+      const buffer = await audioContext.startRendering();
+      const left = buffer.getChannelData(0);
+
+      for (let i = 0; i < buffer.length; i++) {
+        assert.equal(left[i], 0.25);
+      }
+    });
+
+    it('should allow to connect again after disconnect', async () => {
+      const audioContext = new OfflineAudioContext(2, 128, 48000);
+      const merger = audioContext.createChannelMerger(2);
+      merger.connect(audioContext.destination);
+
+      const src = audioContext.createConstantSource();
+      src.offset.value = 0.25;
+      src.connect(merger, 0, 0);
+      src.disconnect(merger);
+      src.connect(merger, 0, 0);
+      src.start();
+
+      const buffer = await audioContext.startRendering();
+      const left = buffer.getChannelData(0);
+
+      for (let i = 0; i < buffer.length; i++) {
+        assert.equal(left[i], 0.25);
+      }
+    });
+  });
+
+  describe('## ChannelSplitterNode round trip', () => {
+    it('should reproduce a stereo input bit for bit', async () => {
+      const sampleRate = 48000;
+      const length = 1024;
+      const audioContext = new OfflineAudioContext(2, length, sampleRate);
+
+      const buffer = audioContext.createBuffer(2, length, sampleRate);
+      const left = buffer.getChannelData(0);
+      const right = buffer.getChannelData(1);
+      for (let i = 0; i < length; i++) {
+        left[i] = Math.sin(i / 10);
+        right[i] = Math.random() * 2 - 1;
+      }
+
+      const src = audioContext.createBufferSource();
+      src.buffer = buffer;
+
+      const splitter = audioContext.createChannelSplitter(2);
+      const merger = audioContext.createChannelMerger(2);
+      src.connect(splitter);
+      splitter.connect(merger, 0, 0);
+      splitter.connect(merger, 1, 1);
+      merger.connect(audioContext.destination);
+      src.start();
+
+      const output = await audioContext.startRendering();
+
+      assert.deepEqual(output.getChannelData(0), left);
+      assert.deepEqual(output.getChannelData(1), right);
+    });
+  });
+
+  describe('## fixed channel configuration', () => {
+    it('should throw InvalidStateError when changing the ChannelMergerNode config', () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const merger = new ChannelMergerNode(audioContext, { numberOfInputs: 2 });
+
+      assertInvalidState(() => merger.channelCount = 2);
+      assertInvalidState(() => merger.channelCountMode = 'max');
+      assertInvalidState(() => merger.channelCountMode = 'clamped-max');
+
+      // setting the current values is allowed
+      merger.channelCount = 1;
+      merger.channelCountMode = 'explicit';
+
+      assert.equal(merger.channelCount, 1);
+      assert.equal(merger.channelCountMode, 'explicit');
+    });
+
+    it('should throw InvalidStateError when changing the ChannelSplitterNode config', () => {
+      const audioContext = new OfflineAudioContext(1, 128, 48000);
+      const splitter = new ChannelSplitterNode(audioContext, { numberOfOutputs: 2 });
+
+      assertInvalidState(() => splitter.channelCount = 1);
+      assertInvalidState(() => splitter.channelCountMode = 'max');
+      assertInvalidState(() => splitter.channelCountMode = 'clamped-max');
+      assertInvalidState(() => splitter.channelInterpretation = 'speakers');
+
+      // setting the current values is allowed
+      splitter.channelCount = 2;
+      splitter.channelCountMode = 'explicit';
+      splitter.channelInterpretation = 'discrete';
+
+      assert.equal(splitter.channelCount, 2);
+      assert.equal(splitter.channelCountMode, 'explicit');
+      assert.equal(splitter.channelInterpretation, 'discrete');
+    });
+  });
+});
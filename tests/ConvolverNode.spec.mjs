@@ -0,0 +1,255 @@
+import { assert } from 'chai';
+import {
+  AudioBuffer,
+  ConvolverNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+import { rms } from './helpers.mjs';
+
+const sampleRate = 48000;
+
+// deterministic noise impulse response with the given energy
+function createImpulseResponse(length, energy) {
+  const buffer = new AudioBuffer({ length, sampleRate, numberOfChannels: 1 });
+  const data = buffer.getChannelData(0);
+  let seed = 1;
+  let sum = 0;
+
+  for (let i = 0; i < length; i++) {
+    seed = (seed * 16807) % 2147483647;
+    data[i] = seed / 2147483647 * 2 - 1;
+    sum += data[i] * data[i];
+  }
+
+  const gain = Math.sqrt(energy / sum);
+  for (let i = 0; i < length; i++) {
+    data[i] *= gain;
+  }
+
+  return buffer;
+}
+
+// response of the convolver to a unit impulse
+async function renderImpulseResponse(buffer, disableNormalization = false) {
+  const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+  const convolver = new ConvolverNode(context, { buffer, disableNormalization });
+
+  const impulse = new AudioBuffer({ length: 1, sampleRate, numberOfChannels: 1 });
+  impulse.getChannelData(0)[0] = 1;
+
+  const src = context.createBufferSource();
+  src.buffer = impulse;
+  src.connect(convolver).connect(context.destination);
+  src.start();
+
+  const output = await context.startRendering();
+  return output.getChannelData(0);
+}
+
+describe('# ConvolverNode', () => {
+  describe('## normalize', () => {
+    // https://webaudio.github.io/web-audio-api/#dom-convolvernode-normalize
+    it('should scale the impulse response following the spec formula', async () => {
+      const buffer = createImpulseResponse(1000, 1);
+      const data = buffer.getChannelData(0);
+      const output = await renderImpulseResponse(buffer);
+
+      const GainCalibration = 0.00125;
+      const GainCalibrationSampleRate = 44100;
+      const scale = 1 / rms(data) * GainCalibration * GainCalibrationSampleRate / sampleRate;
+
+      for (let i = 0; i < data.length; i++) {
+        assert.approximately(output[i], data[i] * scale, 1e-6);
+      }
+    });
+
+    it('should produce comparable levels for impulses of different lengths', async () => {
+      const short = createImpulseResponse(1000, 1);
+      const long = createImpulseResponse(4000, 1);
+
+      const shortOutput = await renderImpulseResponse(short);
+      const longOutput = await renderImpulseResponse(long);
+      // level of the responses over their own duration
+      const shortRms = rms(shortOutput.subarray(0, short.length));
+      const longRms = rms(longOutput.subarray(0, long.length));
+
+      assert.approximately(shortRms / longRms, 1, 0.01);
+    });
+
+    it('should not scale the impulse response if disabled', async () => {
+      const buffer = createImpulseResponse(1000, 1);
+      const data = buffer.getChannelData(0);
+      const output = await renderImpulseResponse(buffer, true);
+
+      for (let i = 0; i < data.length; i++) {
+        assert.approximately(output[i], data[i], 1e-6);
+      }
+    });
+  });
+
+  describe('## bufferLength (non spec)', () => {
+    it('should return the length of the buffer, 0 if not set', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const convolver = new ConvolverNode(context);
+      assert.equal(convolver.bufferLength, 0);
+
+      convolver.buffer = createImpulseResponse(1000, 1);
+      assert.equal(convolver.bufferLength, 1000);
+
+      const other = new ConvolverNode(context, { buffer: createImpulseResponse(10, 1) });
+      assert.equal(other.bufferLength, 10);
+    });
+  });
+
+  describe('## buffer', () => {
+    // https://webaudio.github.io/web-audio-api/#dom-convolvernode-buffer
+    it('should throw NotSupportedError if sample rates do not match', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const buffer = new AudioBuffer({ length: 100, sampleRate: 44100 });
+
+      assert.throws(() => new ConvolverNode(context, { buffer }), DOMException, /sample rate/);
+
+      const convolver = new ConvolverNode(context);
+      assert.throws(() => convolver.buffer = buffer, DOMException, /sample rate/);
+
+      try {
+        convolver.buffer = buffer;
+      } catch (err) {
+        assert.equal(err.name, 'NotSupportedError');
+      }
+
+      assert.equal(convolver.buffer, null);
+    });
+  });
+
+  describe('## swapBuffer(buffer, { crossfadeSeconds }) (non spec)', () => {
+    function scaledImpulse(value) {
+      const buffer = new AudioBuffer({ length: 128, sampleRate, numberOfChannels: 1 });
+      buffer.getChannelData(0)[0] = value;
+      return buffer;
+    }
+
+    // largest difference between two consecutive samples of the output when
+    // swapping from a unit impulse to a 0.2 impulse at 0.1s
+    async function renderSwap(crossfadeSeconds) {
+      const context = new OfflineAudioContext(1, sampleRate * 0.3, sampleRate);
+      const convolver = new ConvolverNode(context, {
+        buffer: scaledImpulse(1),
+        disableNormalization: true,
+      });
+      const src = context.createConstantSource();
+      src.connect(convolver).connect(context.destination);
+      src.start();
+
+      context.suspend(0.1).then(() => {
+        convolver.swapBuffer(scaledImpulse(0.2), { crossfadeSeconds });
+        context.resume();
+      });
+
+      const output = (await context.startRendering()).getChannelData(0);
+      let maxDiff = 0;
+
+      // skip the onset of the source
+      for (let i = 1024; i < output.length; i++) {
+        maxDiff = Math.max(maxDiff, Math.abs(output[i] - output[i - 1]));
+      }
+
+      return { output, maxDiff, buffer: convolver.buffer };
+    }
+
+    it('should crossfade between the impulse responses', async () => {
+      const { output, maxDiff, buffer } = await renderSwap(0.05);
+
+      // a 0.8 step spread over 50ms
+      assert.isBelow(maxDiff, 0.001);
+      assert.approximately(output[sampleRate * 0.05], 1, 1e-6);
+      assert.approximately(output[output.length - 1], 0.2, 1e-6);
+      assert.equal(buffer.getChannelData(0)[0], Math.fround(0.2));
+    });
+
+    it('should swap the impulse response immediately if crossfade is 0', async () => {
+      const { output, maxDiff } = await renderSwap(0);
+
+      assert.approximately(maxDiff, 0.8, 1e-6);
+      assert.approximately(output[output.length - 1], 0.2, 1e-6);
+    });
+
+    // render with swaps to the given impulses at the given times, the context
+    // is suspended for a while after each crossfade so that it is torn down
+    async function renderSwaps(swaps, crossfadeSeconds) {
+      const context = new OfflineAudioContext(1, sampleRate * 0.5, sampleRate);
+      const convolver = new ConvolverNode(context, {
+        buffer: scaledImpulse(1),
+        disableNormalization: true,
+      });
+      const src = context.createConstantSource();
+      src.connect(convolver).connect(context.destination);
+      src.start();
+
+      const suspendTimes = new Set();
+
+      swaps.forEach(({ time }) => {
+        suspendTimes.add(time);
+        // quantized to the render quanta, as context.suspend
+        suspendTimes.add(Math.ceil((time + crossfadeSeconds) * sampleRate / 128) * 128 / sampleRate + 0.02);
+      });
+
+      for (const time of suspendTimes) {
+        const swap = swaps.find(swap => swap.time === time);
+
+        context.suspend(time).then(async () => {
+          if (swap !== undefined) {
+            convolver.swapBuffer(scaledImpulse(swap.value), { crossfadeSeconds });
+          } else {
+            // let the end of the crossfade be dispatched
+            await new Promise(resolve => setTimeout(resolve, 50));
+          }
+
+          context.resume();
+        });
+      }
+
+      const output = (await context.startRendering()).getChannelData(0);
+      let maxDiff = 0;
+
+      for (let i = 1024; i < output.length; i++) {
+        maxDiff = Math.max(maxDiff, Math.abs(output[i] - output[i - 1]));
+      }
+
+      return { output, maxDiff };
+    }
+
+    it('should swap several times in a row', async () => {
+      const { output, maxDiff } = await renderSwaps([
+        { time: 0.1, value: 0.2 },
+        { time: 0.2, value: 0.6 },
+        { time: 0.3, value: 0.4 },
+      ], 0.05);
+
+      assert.isBelow(maxDiff, 0.001);
+      assert.approximately(output[Math.round(sampleRate * 0.19)], 0.2, 1e-6);
+      assert.approximately(output[Math.round(sampleRate * 0.29)], 0.6, 1e-6);
+      assert.approximately(output[output.length - 1], 0.4, 1e-6);
+    });
+
+    it('should restart the crossfade if swapped during a crossfade', async () => {
+      const { output, maxDiff } = await renderSwaps([
+        { time: 0.1, value: 0.2 },
+        { time: 0.12, value: 0.6 },
+      ], 0.05);
+
+      // the crossfade restarts from the current mix, without click
+      assert.isBelow(maxDiff, 0.001);
+      assert.approximately(output[Math.round(sampleRate * 0.17)], 0.6, 1e-3);
+      assert.approximately(output[output.length - 1], 0.6, 1e-6);
+    });
+
+    it('should throw RangeError if crossfade is negative', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const convolver = new ConvolverNode(context);
+
+      assert.throws(() => convolver.swapBuffer(scaledImpulse(1), { crossfadeSeconds: -1 }), RangeError);
+      assert.throws(() => convolver.swapBuffer({}), TypeError);
+    });
+  });
+});
@@ -0,0 +1,100 @@
+import { assert } from 'chai';
+import {
+  DelayNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+// feedback loop with a gain > 1, diverges to Infinity in ~1.5s
+async function renderFeedbackLoop(clampFeedback) {
+  const context = new OfflineAudioContext(1, 2 * sampleRate, sampleRate);
+
+  const delay = new DelayNode(context, { delayTime: 0.003, clampFeedback });
+  const feedback = context.createGain();
+  feedback.gain.value = 1.2;
+  delay.connect(feedback).connect(delay);
+  delay.connect(context.destination);
+
+  const impulse = context.createBuffer(1, 1, sampleRate);
+  impulse.getChannelData(0)[0] = 1;
+  const src = context.createBufferSource();
+  src.buffer = impulse;
+  src.connect(delay);
+  src.start();
+
+  const buffer = await context.startRendering();
+  return buffer.getChannelData(0);
+}
+
+describe('# DelayNode', () => {
+  describe('## clampFeedback (non spec)', () => {
+    it('should be disabled by default', async () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+
+      assert.isFalse(context.createDelay().clampFeedback);
+      assert.isTrue(new DelayNode(context, { clampFeedback: true }).clampFeedback);
+
+      const data = await renderFeedbackLoop(false);
+      assert.isTrue(data.some(value => !Number.isFinite(value)));
+    });
+
+    it('should keep the output of a feedback loop bounded', async () => {
+      const data = await renderFeedbackLoop(true);
+
+      let max = 0;
+      for (let i = 0; i < data.length; i++) {
+        assert.isTrue(Number.isFinite(data[i]));
+        max = Math.max(max, Math.abs(data[i]));
+      }
+
+      assert.isAbove(max, 1);
+      assert.isAtMost(max, 2);
+    });
+
+    it('should be transparent for signals below the threshold', async () => {
+      const context = new OfflineAudioContext(1, 1024, sampleRate);
+      const delay = new DelayNode(context, { delayTime: 128 / sampleRate, clampFeedback: true });
+      delay.connect(context.destination);
+
+      const buffer = context.createBuffer(1, 512, sampleRate);
+      const input = buffer.getChannelData(0);
+      for (let i = 0; i < input.length; i++) {
+        input[i] = 0.9 * Math.sin(2 * Math.PI * i / 64);
+      }
+
+      const src = context.createBufferSource();
+      src.buffer = buffer;
+      src.connect(delay);
+      src.start();
+
+      const output = (await context.startRendering()).getChannelData(0);
+
+      for (let i = 0; i < input.length; i++) {
+        assert.approximately(output[i + 128], input[i], 1e-5);
+      }
+    });
+  });
+
+  describe('## maxDelayTime / getAllocatedFrames() (non spec)', () => {
+    it('should return the size of the delay line', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const delay = new DelayNode(context, { maxDelayTime: 2 });
+
+      assert.equal(delay.maxDelayTime, 2);
+      // rounded up to whole render quanta
+      assert.approximately(delay.getAllocatedFrames(), 96000, 2 * 128);
+      assert.equal(delay.getAllocatedFrames() % 128, 0);
+
+      assert.equal(context.createDelay().maxDelayTime, 1);
+    });
+
+    it('should return the frames allocated at the rate of the render graph', () => {
+      const context = new OfflineAudioContext({ length: 128, sampleRate, renderOversample: 2 });
+      const delay = new DelayNode(context, { maxDelayTime: 1 });
+
+      // allocated by the native node at twice the rate of the context
+      assert.equal(delay.getAllocatedFrames(), (Math.ceil(2 * sampleRate / 128) + 1) * 128);
+    });
+  });
+});
@@ -0,0 +1,108 @@
+import { assert } from 'chai';
+import {
+  GainNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+// renders the gain applied to a constant signal, `schedule` is called at
+// time 0 and at `suspendTime` if given
+async function renderGain(length, schedule, suspendTime = null) {
+  const context = new OfflineAudioContext(1, length, sampleRate);
+  const gain = new GainNode(context, { gain: 0 });
+  gain.connect(context.destination);
+
+  const src = context.createConstantSource();
+  src.connect(gain);
+  src.start();
+
+  schedule(gain);
+
+  if (suspendTime !== null) {
+    context.suspend(suspendTime).then(() => {
+      schedule(gain);
+      context.resume();
+    });
+  }
+
+  const buffer = await context.startRendering();
+  return buffer.getChannelData(0);
+}
+
+describe('# GainNode', () => {
+  describe('## fade(targetValue, duration, options) (non spec)', () => {
+    const fadeLength = 0.1 * sampleRate;
+
+    it('should fade in linearly over the duration', async () => {
+      const data = await renderGain(2 * fadeLength, gain => gain.fade(1, 0.1));
+
+      for (let i = 0; i < data.length; i++) {
+        assert.isFalse(Number.isNaN(data[i]));
+        assert.approximately(data[i], Math.min(i / fadeLength, 1), 1e-4);
+      }
+    });
+
+    it('should fade in and out exponentially from and to zero', async () => {
+      const data = await renderGain(3 * fadeLength + 128, gain => {
+        if (gain.context.currentTime === 0) {
+          gain.fade(1, 0.1, { curve: 'exponential' });
+        } else {
+          gain.fade(0, 0.1, { curve: 'exponential' });
+        }
+      }, 0.2);
+
+      for (let i = 0; i < data.length; i++) {
+        assert.isFalse(Number.isNaN(data[i]));
+      }
+
+      // fade in spans the whole duration
+      assert.isBelow(data[0], 1e-5);
+      assert.isBelow(data[fadeLength / 2], 0.01);
+      assert.isAbove(data[fadeLength - 1], 0.99);
+      for (let i = 1; i < fadeLength; i++) {
+        assert.isAtLeast(data[i], data[i - 1]);
+      }
+
+      assert.approximately(data[fadeLength + 1], 1, 1e-6);
+      // fade out reaches zero
+      assert.isBelow(data[2 * fadeLength + fadeLength / 2], 0.01);
+      for (let i = 3 * fadeLength; i < data.length; i++) {
+        assert.equal(data[i], 0);
+      }
+    });
+
+    it('should fade exponentially from the value held at the current time', async () => {
+      const context = new OfflineAudioContext(1, 2 * fadeLength, sampleRate);
+      const gain = new GainNode(context, { gain: 1 });
+      gain.connect(context.destination);
+
+      const src = context.createConstantSource();
+      src.connect(gain);
+      src.start();
+
+      // `gain.value` is still 1, while 0 is held at the start of the fade
+      gain.gain.setValueAtTime(0, 0);
+      gain.fade(1, 0.1, { curve: 'exponential' });
+
+      const buffer = await context.startRendering();
+      const data = buffer.getChannelData(0);
+
+      assert.isBelow(data[0], 1e-5);
+      assert.isAbove(data[fadeLength - 1], 0.99);
+      for (let i = 1; i < fadeLength; i++) {
+        assert.isFalse(Number.isNaN(data[i]));
+        assert.isAtLeast(data[i], data[i - 1]);
+      }
+    });
+
+    it('should throw on invalid arguments', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+      const gain = context.createGain();
+
+      assert.throws(() => gain.fade(1), TypeError);
+      assert.throws(() => gain.fade(1, -1), RangeError);
+      assert.throws(() => gain.fade(1, 0.1, { curve: 'cubic' }), TypeError);
+    });
+  });
+});
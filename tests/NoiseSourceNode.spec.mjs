@@ -0,0 +1,125 @@
+import { assert } from 'chai';
+import {
+  AudioScheduledSourceNode,
+  NoiseSourceNode,
+  OfflineAudioContext,
+} from '../index.mjs';
+import { rms } from './helpers.mjs';
+
+const sampleRate = 48000;
+
+async function renderNoise(type, duration = 0.5) {
+  const context = new OfflineAudioContext(1, duration * sampleRate, sampleRate);
+  const noise = new NoiseSourceNode(context, { type });
+  noise.connect(context.destination);
+  noise.start();
+
+  const buffer = await context.startRendering();
+  return buffer.getChannelData(0);
+}
+
+// averaged power spectrum (Welch method without overlap) in `numBands` bands
+function bandPowers(data, numBands, windowSize = 512) {
+  const numBins = windowSize / 2;
+  const bins = new Float64Array(numBins);
+
+  for (let offset = 0; offset + windowSize <= data.length; offset += windowSize) {
+    for (let k = 0; k < numBins; k++) {
+      let real = 0;
+      let imag = 0;
+
+      for (let n = 0; n < windowSize; n++) {
+        const phase = 2 * Math.PI * k * n / windowSize;
+        real += data[offset + n] * Math.cos(phase);
+        imag -= data[offset + n] * Math.sin(phase);
+      }
+
+      bins[k] += real * real + imag * imag;
+    }
+  }
+
+  const bandSize = numBins / numBands;
+  const bands = [];
+
+  for (let band = 0; band < numBands; band++) {
+    let sum = 0;
+    // skip DC
+    for (let k = Math.max(1, band * bandSize); k < (band + 1) * bandSize; k++) {
+      sum += bins[k];
+    }
+    bands.push(sum);
+  }
+
+  return bands;
+}
+
+describe('# NoiseSourceNode (non spec)', () => {
+  it('should be an AudioScheduledSourceNode with type white by default', () => {
+    const context = new OfflineAudioContext(1, 128, sampleRate);
+    const noise = context.createNoiseSource();
+
+    assert.isTrue(noise instanceof AudioScheduledSourceNode);
+    assert.equal(noise.type, 'white');
+    assert.equal(noise.numberOfInputs, 0);
+    assert.equal(noise.numberOfOutputs, 1);
+
+    noise.type = 'pink';
+    assert.equal(noise.type, 'pink');
+
+    assert.throws(() => new NoiseSourceNode(context, { type: 'blue' }), TypeError);
+  });
+
+  it('should generate white noise with a flat spectrum and a stable RMS', async () => {
+    const data = await renderNoise('white');
+    const bands = bandPowers(data, 8);
+    const mean = bands.reduce((acc, value) => acc + value, 0) / bands.length;
+
+    bands.forEach(power => {
+      assert.approximately(power / mean, 1, 0.25);
+    });
+
+    // uniform distribution in [-1, 1[
+    const expected = 1 / Math.sqrt(3);
+    const segmentSize = sampleRate / 10;
+
+    for (let offset = 0; offset < data.length; offset += segmentSize) {
+      const value = rms(data.subarray(offset, offset + segmentSize));
+      assert.approximately(value, expected, expected * 0.05);
+    }
+  });
+
+  it('should generate pink and brown noise with decreasing spectra', async () => {
+    for (const type of ['pink', 'brown']) {
+      const data = await renderNoise(type);
+      const bands = bandPowers(data, 8);
+
+      assert.isAbove(bands[0], bands[7] * 4, type);
+    }
+  });
+
+  it('should support start, stop and onended', async () => {
+    const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+    const noise = new NoiseSourceNode(context);
+    noise.connect(context.destination);
+
+    let ended = false;
+    noise.onended = () => ended = true;
+
+    const startFrame = 1000;
+    const stopFrame = 5000;
+    noise.start(startFrame / sampleRate);
+    noise.stop(stopFrame / sampleRate);
+
+    assert.throws(() => noise.start(), DOMException);
+
+    const buffer = await context.startRendering();
+    const data = buffer.getChannelData(0);
+    // let the ended event be dispatched
+    await new Promise(resolve => setTimeout(resolve, 100));
+
+    assert.isTrue(ended);
+    assert.deepEqual(data.subarray(0, startFrame), new Float32Array(startFrame));
+    assert.isAbove(rms(data.subarray(startFrame, stopFrame)), 0.5);
+    assert.deepEqual(data.subarray(stopFrame), new Float32Array(data.length - stopFrame));
+  });
+});
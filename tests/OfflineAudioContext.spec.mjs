@@ -1,10 +1,24 @@
+import fs from 'node:fs';
+import path from 'node:path';
 import { assert } from 'chai';
 import {
   AudioContext,
   AudioBuffer,
+  ConvolverNode,
   OfflineAudioContext,
+  setOfflineRenderConcurrency,
 } from '../index.mjs';
 
+// number of threads of the process, null if not available on the platform
+function getThreadCount() {
+  try {
+    const status = fs.readFileSync('/proc/self/status', 'utf8');
+    return parseInt(status.match(/^Threads:\s+(\d+)$/m)[1]);
+  } catch (_err) {
+    return null;
+  }
+}
+
 describe('# OfflineAudioContext', () => {
   describe('## await startRendering()', () => {
     it('buffer returned by startRendering and buffer from `oncomplete` event should be same instance', async () => {
@@ -36,6 +50,566 @@ describe('# OfflineAudioContext', () => {
       assert.deepEqual(aResult, bResult);
     });
   });
-});
 
+  describe('## masterLimiter option (non spec)', () => {
+    async function renderOverUnity(masterLimiter) {
+      const offline = new OfflineAudioContext({
+        numberOfChannels: 2,
+        length: 48000,
+        sampleRate: 48000,
+        masterLimiter,
+      });
+
+      const osc = offline.createOscillator();
+      osc.frequency.value = 220;
+      const gain = offline.createGain();
+      gain.gain.value = 4;
+
+      osc.connect(gain).connect(offline.destination);
+      osc.start();
+
+      const buffer = await offline.startRendering();
+
+      let peak = 0;
+
+      for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+        for (let value of buffer.getChannelData(channel)) {
+          peak = Math.max(peak, Math.abs(value));
+        }
+      }
+
+      return peak;
+    }
+
+    it('should not limit output by default', async () => {
+      const peak = await renderOverUnity(undefined);
+      assert.isAbove(peak, 1);
+    });
+
+    it('should prevent output from exceeding 1 when enabled', async () => {
+      const peak = await renderOverUnity(true);
+      assert.isAtMost(peak, 1);
+      // signal should be limited, not muted
+      assert.isAbove(peak, 0.9);
+    });
+
+    it('should limit the peaks between samples', async () => {
+      const sampleRate = 48000;
+      const offline = new OfflineAudioContext({
+        numberOfChannels: 1,
+        length: sampleRate / 10,
+        sampleRate,
+        masterLimiter: true,
+      });
+
+      // sine at a quarter of the sample rate sampled at +/-45 degrees, every
+      // sample is below 1 while the signal peaks at 1.2
+      const buffer = offline.createBuffer(1, 4, sampleRate);
+      buffer.copyToChannel(new Float32Array([1, 1, -1, -1].map(v => v * 1.2 * Math.SQRT1_2)), 0);
+
+      const src = offline.createBufferSource();
+      src.buffer = buffer;
+      src.loop = true;
+      src.connect(offline.destination);
+      src.start();
+
+      const output = await offline.startRendering();
+      const data = output.getChannelData(0);
+
+      let peak = 0;
+
+      for (let value of data.subarray(sampleRate / 100)) {
+        peak = Math.max(peak, Math.abs(value));
+      }
+
+      // every sample is scaled by 1 / 1.2
+      assert.approximately(peak, Math.SQRT1_2, 0.02);
+    });
+  });
+
+  describe('## suspendAtFrame(frame) (non spec)', () => {
+    it('should suspend before the given frame is rendered', async () => {
+      const sampleRate = 48000;
+      const frame = 64; // mid render quantum
+      const context = new OfflineAudioContext(1, 512, sampleRate);
+      let suspendedAt = null;
+
+      context.suspendAtFrame(frame).then(() => {
+        suspendedAt = context.currentTime;
+        // rendering is halted, schedule a step exactly at frame
+        const src = context.createConstantSource();
+        src.connect(context.destination);
+        src.start(frame / sampleRate);
+
+        context.resume();
+      });
+
+      const buffer = await context.startRendering();
+      const data = buffer.getChannelData(0);
+
+      assert.isNotNull(suspendedAt);
+      assert.isAtMost(suspendedAt * sampleRate, frame);
+      assert.deepEqual(data.subarray(0, frame), new Float32Array(frame));
+      assert.deepEqual(data.subarray(frame), new Float32Array(512 - frame).fill(1));
+    });
+
+    it('should support frames after the first render quantum', async () => {
+      const sampleRate = 44100;
+      const frame = 128 * 3 + 17;
+      const context = new OfflineAudioContext(1, 1024, sampleRate);
+      let suspendedAt = null;
+
+      context.suspendAtFrame(frame).then(() => {
+        suspendedAt = context.currentTime;
+
+        const src = context.createConstantSource();
+        src.connect(context.destination);
+        src.start(frame / sampleRate);
+
+        context.resume();
+      });
+
+      const buffer = await context.startRendering();
+
+      assert.approximately(suspendedAt * sampleRate, 128 * 3, 1e-6);
+      assert.equal(buffer.getChannelData(0).indexOf(1), frame);
+    });
+
+    it('should reject with RangeError if frame is beyond length', async () => {
+      const context = new OfflineAudioContext(1, 512, 48000);
+      let errored = false;
+
+      try {
+        await context.suspendAtFrame(512);
+      } catch (err) {
+        errored = true;
+        assert.isTrue(err instanceof RangeError);
+      }
+
+      assert.isTrue(errored);
+    });
+  });
+
+  describe('# renderOversample', () => {
+    const sampleRate = 44100;
+    const windowSize = 4096;
+    // odd bin, so that aliased harmonics never fall on a true harmonic bin
+    const fundamentalBin = 93;
+
+    async function renderDistortedTone(renderOversample) {
+      const context = new OfflineAudioContext({
+        length: windowSize * 2,
+        sampleRate,
+        renderOversample,
+      });
+
+      const curve = new Float32Array(1025);
+      for (let i = 0; i < curve.length; i++) {
+        curve[i] = Math.tanh(8 * (i / (curve.length - 1) * 2 - 1));
+      }
+
+      const shaper = context.createWaveShaper();
+      shaper.curve = curve;
+      shaper.connect(context.destination);
+
+      const osc = context.createOscillator();
+      osc.frequency.value = fundamentalBin * sampleRate / windowSize;
+      osc.connect(shaper);
+      osc.start();
+
+      return await context.startRendering();
+    }
+
+    // energy of the bins that are not harmonics of the fundamental
+    function aliasingEnergy(buffer) {
+      const data = buffer.getChannelData(0).subarray(windowSize / 2, windowSize / 2 + windowSize);
+      let energy = 0;
+
+      for (let k = 1; k < windowSize / 2; k++) {
+        if (k % fundamentalBin === 0) {
+          continue;
+        }
+
+        let real = 0;
+        let imag = 0;
+
+        for (let n = 0; n < windowSize; n++) {
+          const phase = 2 * Math.PI * k * n / windowSize;
+          real += data[n] * Math.cos(phase);
+          imag -= data[n] * Math.sin(phase);
+        }
+
+        energy += real * real + imag * imag;
+      }
+
+      return energy;
+    }
+
+    it('should return a buffer at the context rate', async () => {
+      const context = new OfflineAudioContext({
+        numberOfChannels: 2,
+        length: 1000,
+        sampleRate,
+        renderOversample: 4,
+      });
+
+      assert.equal(context.renderOversample, 4);
+      assert.equal(context.sampleRate, sampleRate);
+      assert.equal(context.length, 1000);
+
+      const buffer = await context.startRendering();
+
+      assert.equal(buffer.numberOfChannels, 2);
+      assert.equal(buffer.length, 1000);
+      assert.equal(buffer.sampleRate, sampleRate);
+    });
+
+    it('should reduce aliasing of nonlinear processing', async () => {
+      const reference = await renderDistortedTone(1);
+      const oversampled = await renderDistortedTone(4);
+
+      assert.isBelow(aliasingEnergy(oversampled), aliasingEnergy(reference) / 10);
+    });
+
+    it('should throw NotSupportedError if factor is not supported', () => {
+      assert.throws(() => {
+        new OfflineAudioContext({ length: 128, sampleRate, renderOversample: 3 });
+      }, DOMException, /renderOversample/);
+    });
+
+    it('should report the Nyquist frequency of the context in param ranges', () => {
+      const context = new OfflineAudioContext({ length: 128, sampleRate, renderOversample: 4 });
+      const biquad = context.createBiquadFilter();
+      const osc = context.createOscillator();
+
+      assert.equal(biquad.frequency.minValue, 0);
+      assert.equal(biquad.frequency.maxValue, sampleRate / 2);
+      assert.equal(osc.frequency.minValue, -sampleRate / 2);
+      assert.equal(osc.frequency.maxValue, sampleRate / 2);
+    });
+
+    it('should decode audio data at the context rate', async () => {
+      const pathname = path.join('examples', 'samples', 'sample-48000.wav');
+      const arrayBuffer = fs.readFileSync(pathname).buffer;
+      const reference = new OfflineAudioContext({ length: 128, sampleRate });
+      const context = new OfflineAudioContext({ length: 128, sampleRate, renderOversample: 4 });
+
+      const expected = await reference.decodeAudioData(arrayBuffer.slice(0));
+      const buffer = await context.decodeAudioData(arrayBuffer.slice(0));
+
+      assert.equal(buffer.sampleRate, sampleRate);
+      assert.equal(buffer.length, expected.length);
+    });
+
+    it('should keep the level of ConvolverNode', async () => {
+      // smooth impulse response with a DC gain of 1
+      const ir = new AudioBuffer({ length: 64, sampleRate });
+      const data = ir.getChannelData(0);
+      let sum = 0;
+
+      for (let i = 0; i < data.length; i++) {
+        data[i] = Math.sin(Math.PI * (i + 1) / (data.length + 1)) ** 2;
+        sum += data[i];
+      }
+
+      data.forEach((value, i) => data[i] = value / sum);
+
+      async function renderConstant(renderOversample, disableNormalization) {
+        const context = new OfflineAudioContext({ length: 1000, sampleRate, renderOversample });
+        const convolver = new ConvolverNode(context, { buffer: ir, disableNormalization });
+        convolver.connect(context.destination);
+
+        const src = context.createConstantSource();
+        src.connect(convolver);
+        src.start();
 
+        const buffer = await context.startRendering();
+        return buffer.getChannelData(0)[500];
+      }
+
+      for (let disableNormalization of [true, false]) {
+        const reference = await renderConstant(1, disableNormalization);
+        const oversampled = await renderConstant(4, disableNormalization);
+
+        assert.approximately(oversampled, reference, Math.abs(reference) * 0.01);
+      }
+    });
+
+    it('should check the rate of ConvolverNode buffer against the context rate', () => {
+      const context = new OfflineAudioContext({ length: 128, sampleRate, renderOversample: 4 });
+      const convolver = context.createConvolver();
+
+      convolver.buffer = new AudioBuffer({ length: 64, sampleRate });
+
+      assert.throws(() => {
+        convolver.buffer = new AudioBuffer({ length: 64, sampleRate: sampleRate * 4 });
+      }, DOMException, /NotSupportedError|sample rate/i);
+    });
+  });
+
+  describe('## OfflineAudioContext.forBuffer(buffer, options) (non spec)', () => {
+    it('should size the context to the buffer plus the tail', async () => {
+      const sampleRate = 44100;
+      // stereo click
+      const click = new AudioBuffer({ numberOfChannels: 2, length: sampleRate / 2, sampleRate });
+      click.getChannelData(0)[0] = 1;
+      click.getChannelData(1)[0] = 1;
+
+      const context = OfflineAudioContext.forBuffer(click, { tailSeconds: 1.5 });
+      assert.equal(context.sampleRate, sampleRate);
+      assert.equal(context.length, click.length + 1.5 * sampleRate);
+
+      // 1 second of exponentially decaying noise
+      const impulse = new AudioBuffer({ length: sampleRate, sampleRate });
+      const data = impulse.getChannelData(0);
+      let seed = 1;
+
+      for (let i = 0; i < data.length; i++) {
+        seed = (seed * 16807) % 2147483647;
+        data[i] = (seed / 2147483647 * 2 - 1) * Math.exp(-3 * i / sampleRate);
+      }
+
+      const reverb = context.createConvolver();
+      reverb.buffer = impulse;
+      reverb.connect(context.destination);
+
+      const src = context.createBufferSource();
+      src.buffer = click;
+      src.connect(reverb);
+      src.start();
+
+      const output = await context.startRendering();
+
+      assert.equal(output.numberOfChannels, 2);
+      assert.equal(output.length, click.length + 1.5 * sampleRate);
+      // the tail of the reverb is rendered after the end of the click buffer
+      const tail = output.getChannelData(0).subarray(click.length, click.length + 0.4 * sampleRate);
+      assert.isTrue(tail.some(value => value !== 0));
+    });
+
+    it('should throw RangeError if tailSeconds is negative', () => {
+      const buffer = new AudioBuffer({ length: 128, sampleRate: 48000 });
+      assert.throws(() => OfflineAudioContext.forBuffer(buffer, { tailSeconds: -1 }), RangeError);
+      assert.throws(() => OfflineAudioContext.forBuffer({}), TypeError);
+    });
+  });
+
+  describe('## strictAutomation option (non spec)', () => {
+    const sampleRate = 48000;
+
+    it('should throw RangeError if an automation ends after the rendering', () => {
+      const context = new OfflineAudioContext({ length: 4800, sampleRate, strictAutomation: true });
+      assert.isTrue(context.strictAutomation);
+
+      const gain = context.createGain();
+      // ends exactly at the end of the rendering
+      gain.gain.setValueCurveAtTime([0, 1], 0, 0.1);
+
+      assert.throws(() => gain.gain.setValueCurveAtTime([1, 0], 0.05, 0.1), RangeError);
+      assert.throws(() => gain.gain.linearRampToValueAtTime(0, 0.2), RangeError);
+      // the timeline is not modified
+      assert.equal(gain.gain.getScheduledEvents().length, 1);
+    });
+
+    it('should be lenient by default', () => {
+      const context = new OfflineAudioContext({ length: 4800, sampleRate });
+      assert.isFalse(context.strictAutomation);
+
+      const gain = context.createGain();
+      gain.gain.setValueCurveAtTime([1, 0], 0.05, 0.1);
+    });
+  });
+
+  describe('## setOfflineRenderConcurrency(n) (non spec)', () => {
+    it('should bound the number of renderings running in parallel', async () => {
+      const sampleRate = 48000;
+      const numberOfRenderings = 50;
+      const concurrency = 4;
+
+      // make sure the shared runtime is started before counting threads
+      await new OfflineAudioContext(1, 128, sampleRate).startRendering();
+      const initialThreadCount = getThreadCount();
+
+      setOfflineRenderConcurrency(concurrency);
+
+      const contexts = [];
+      let maxRunning = 0;
+      let maxThreadCount = initialThreadCount;
+
+      const monitor = () => {
+        const running = contexts.filter(context => context.state === 'running').length;
+        maxRunning = Math.max(maxRunning, running);
+
+        if (initialThreadCount !== null) {
+          maxThreadCount = Math.max(maxThreadCount, getThreadCount());
+        }
+      };
+
+      const renderings = [];
+
+      for (let i = 0; i < numberOfRenderings; i++) {
+        const context = new OfflineAudioContext(1, sampleRate / 4, sampleRate);
+        context.onstatechange = monitor;
+        contexts.push(context);
+
+        const src = context.createConstantSource();
+        src.offset.value = i;
+        src.connect(context.destination);
+        src.start();
+
+        renderings.push(context.startRendering());
+      }
+
+      const interval = setInterval(monitor, 1);
+      let buffers;
+
+      try {
+        buffers = await Promise.all(renderings);
+      } finally {
+        clearInterval(interval);
+        setOfflineRenderConcurrency(Infinity);
+      }
+
+      buffers.forEach((buffer, i) => {
+        assert.deepEqual(buffer.getChannelData(0), new Float32Array(sampleRate / 4).fill(i));
+      });
+
+      assert.isAtLeast(maxRunning, 1);
+      assert.isAtMost(maxRunning, concurrency);
+
+      if (initialThreadCount !== null) {
+        assert.isAtMost(maxThreadCount, initialThreadCount + concurrency);
+      }
+    });
+
+    it('should throw on invalid values', () => {
+      assert.throws(() => setOfflineRenderConcurrency(), TypeError);
+      assert.throws(() => setOfflineRenderConcurrency(-1), TypeError);
+      assert.throws(() => setOfflineRenderConcurrency(0), RangeError);
+      // reset to default
+      setOfflineRenderConcurrency(Infinity);
+    });
+  });
+
+  describe('## renderProgress(options) (non spec)', () => {
+    // fails the test if a promise left behind by renderProgress rejects
+    async function withoutUnhandledRejection(callback) {
+      const rejections = [];
+      const onRejection = reason => rejections.push(reason);
+      process.on('unhandledRejection', onRejection);
+
+      try {
+        await callback();
+        await new Promise(resolve => setTimeout(resolve, 50));
+      } finally {
+        process.off('unhandledRejection', onRejection);
+      }
+
+      assert.deepEqual(rejections, []);
+    }
+
+    it('should throw InvalidStateError if the rendering is started', async () => {
+      await withoutUnhandledRejection(async () => {
+        const context = new OfflineAudioContext(1, 128 * 40, 48000);
+        const rendering = context.startRendering();
+
+        try {
+          for await (const _ of context.renderProgress()) {
+            assert.fail('should not yield');
+          }
+          assert.fail('should throw');
+        } catch (err) {
+          assert.instanceOf(err, DOMException);
+          assert.equal(err.name, 'InvalidStateError');
+        }
+
+        await rendering;
+      });
+    });
+
+    it('should throw InvalidStateError if a step is already suspended', async () => {
+      const context = new OfflineAudioContext(1, 128 * 40, 48000);
+      // already scheduled, thus the rendering is not started
+      context.suspendAtFrame(128 * 10).then(() => context.resume());
+
+      let steps = 0;
+
+      try {
+        for await (const _ of context.renderProgress({ step: 128 * 10 })) {
+          steps += 1;
+        }
+        assert.fail('should throw');
+      } catch (err) {
+        assert.instanceOf(err, DOMException);
+        assert.equal(err.name, 'InvalidStateError');
+      }
+
+      assert.equal(steps, 0);
+      // the suspension is still reached
+      await context.startRendering();
+    });
+
+    it('should yield the progress and end with the rendered buffer', async () => {
+      const sampleRate = 48000;
+      const length = 128 * 40;
+      const context = new OfflineAudioContext(1, length, sampleRate);
+
+      const src = context.createConstantSource();
+      src.connect(context.destination);
+      src.start();
+
+      const progress = [];
+      let renderedBuffer = null;
+
+      for await (const step of context.renderProgress({ step: 128 * 10 })) {
+        assert.equal(step.total, length);
+        progress.push(step.framesRendered);
+        renderedBuffer = step.renderedBuffer;
+      }
+
+      assert.deepEqual(progress, [1280, 2560, 3840, length]);
+      assert.instanceOf(renderedBuffer, AudioBuffer);
+      assert.deepEqual(renderedBuffer.getChannelData(0), new Float32Array(length).fill(1));
+      assert.equal(context.state, 'closed');
+    });
+
+    it('should cancel the rendering when breaking out of the loop', async () => {
+      const sampleRate = 48000;
+      const length = sampleRate * 10;
+      const context = new OfflineAudioContext(1, length, sampleRate);
+
+      const src = context.createConstantSource();
+      src.connect(context.destination);
+      src.start();
+
+      let framesRendered;
+
+      for await (const step of context.renderProgress()) {
+        framesRendered = step.framesRendered;
+
+        if (framesRendered >= step.total / 2) {
+          break;
+        }
+      }
+
+      assert.isAtLeast(framesRendered, length / 2);
+      assert.isBelow(framesRendered, length);
+
+      const currentTime = context.currentTime;
+      await new Promise(resolve => setTimeout(resolve, 50));
+      assert.equal(context.currentTime, currentTime);
+      assert.equal(context.state, 'suspended');
+
+      // the render slot is released, another rendering can run
+      setOfflineRenderConcurrency(1);
+
+      try {
+        const other = new OfflineAudioContext(1, 128, sampleRate);
+        const buffer = await other.startRendering();
+        assert.equal(buffer.length, 128);
+      } finally {
+        setOfflineRenderConcurrency(Infinity);
+      }
+    });
+  });
+});
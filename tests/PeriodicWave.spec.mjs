@@ -21,5 +21,64 @@ describe('# PeriodicWave', () => {
       assert.isTrue(periodicWave instanceof PeriodicWave);
     });
   });
-});
 
+  describe('fromWaveform(context, type, { partials }) (non spec)', () => {
+    // magnitude of the harmonics of a periodic signal, `data` must contain an
+    // integer number of periods of `period` samples
+    function harmonics(data, period, numHarmonics) {
+      const numPeriods = data.length / period;
+      const magnitudes = [];
+
+      for (let k = 1; k <= numHarmonics; k++) {
+        let real = 0;
+        let imag = 0;
+
+        for (let n = 0; n < data.length; n++) {
+          const phase = 2 * Math.PI * k * numPeriods * n / data.length;
+          real += data[n] * Math.cos(phase);
+          imag -= data[n] * Math.sin(phase);
+        }
+
+        magnitudes[k] = 2 * Math.sqrt(real * real + imag * imag) / data.length;
+      }
+
+      return magnitudes;
+    }
+
+    it('should build a band-limited square with odd harmonics only', async () => {
+      const sampleRate = 48000;
+      const period = 256;
+      const partials = 9;
+      const context = new OfflineAudioContext(1, 16 * period, sampleRate);
+
+      const wave = PeriodicWave.fromWaveform(context, 'square', { partials });
+      assert.isTrue(wave instanceof PeriodicWave);
+
+      const osc = context.createOscillator();
+      osc.frequency.value = sampleRate / period;
+      osc.setPeriodicWave(wave);
+      osc.connect(context.destination);
+      osc.start();
+
+      const data = (await context.startRendering()).getChannelData(0);
+      const magnitudes = harmonics(data, period, 2 * partials);
+
+      for (let k = 1; k <= 2 * partials; k++) {
+        if (k <= partials && k % 2 === 1) {
+          // 1 / k decay of the square wave harmonics
+          assert.approximately(magnitudes[k] / magnitudes[1], 1 / k, 1e-2, `harmonic ${k}`);
+        } else {
+          assert.isBelow(magnitudes[k], 1e-3, `harmonic ${k}`);
+        }
+      }
+    });
+
+    it('should throw TypeError on unknown waveform', () => {
+      const context = new OfflineAudioContext(1, 1, 48000);
+
+      assert.throws(() => PeriodicWave.fromWaveform(context, 'custom'), TypeError);
+      assert.throws(() => PeriodicWave.fromWaveform(context, 'toString'), TypeError);
+      assert.throws(() => PeriodicWave.fromWaveform(context, 'sawtooth', { partials: 0 }), RangeError);
+    });
+  });
+});
@@ -0,0 +1,181 @@
+import { assert } from 'chai';
+import {
+  AudioBuffer,
+  OfflineAudioContext,
+  Sampler,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+// amplitude of the `frequency` component of `data`, hann windowed
+function amplitudeAt(data, frequency) {
+  let re = 0;
+  let im = 0;
+
+  for (let i = 0; i < data.length; i++) {
+    const w = 0.5 - 0.5 * Math.cos(2 * Math.PI * i / data.length);
+    const phase = 2 * Math.PI * frequency * i / sampleRate;
+    re += w * data[i] * Math.cos(phase);
+    im -= w * data[i] * Math.sin(phase);
+  }
+
+  return 4 * Math.hypot(re, im) / data.length;
+}
+
+function sineBuffer(frequency, duration) {
+  const buffer = new AudioBuffer({ length: duration * sampleRate, sampleRate });
+  buffer.getChannelData(0).forEach((_, i, data) => {
+    data[i] = Math.sin(2 * Math.PI * frequency * i / sampleRate);
+  });
+
+  return buffer;
+}
+
+describe('# Sampler', () => {
+  it('should play overlapping playbacks on each trigger', async () => {
+    const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+    // 0.2 second of constant signal
+    const buffer = new AudioBuffer({ length: 0.2 * sampleRate, sampleRate });
+    buffer.getChannelData(0).fill(0.25);
+
+    const sampler = new Sampler(context, { buffer });
+    sampler.connect(context.destination);
+
+    sampler.trigger(0);
+    sampler.trigger(0.1);
+    assert.equal(sampler.activeVoices, 2);
+
+    const output = (await context.startRendering()).getChannelData(0);
+    // let the ended events be dispatched
+    await new Promise(resolve => setTimeout(resolve, 100));
+
+    const at = time => output[Math.round(time * sampleRate)];
+    assert.equal(at(0.05), 0.25);
+    // both playbacks overlap between 0.1 and 0.2
+    assert.equal(at(0.15), 0.5);
+    assert.equal(at(0.25), 0.25);
+    assert.equal(at(0.35), 0);
+
+    assert.equal(sampler.activeVoices, 0);
+  });
+
+  it('should play the buffer from offset', async () => {
+    const context = new OfflineAudioContext(1, 256, sampleRate);
+    const buffer = new AudioBuffer({ length: 256, sampleRate });
+    buffer.getChannelData(0).forEach((_, i, data) => data[i] = i / 256);
+
+    const sampler = new Sampler(context, { buffer });
+    sampler.connect(context.destination);
+    sampler.trigger(0, 128 / sampleRate);
+
+    const output = (await context.startRendering()).getChannelData(0);
+
+    for (let i = 0; i < 128; i++) {
+      assert.equal(output[i], (i + 128) / 256);
+    }
+    assert.equal(output[128], 0);
+  });
+
+  it('should stop all playbacks', async () => {
+    const context = new OfflineAudioContext(1, 1024, sampleRate);
+    const buffer = new AudioBuffer({ length: 1024, sampleRate });
+    buffer.getChannelData(0).fill(1);
+
+    const sampler = new Sampler(context, { buffer });
+    sampler.connect(context.destination);
+    sampler.trigger(0);
+    sampler.trigger(0);
+    sampler.stopAll(512 / sampleRate);
+
+    const output = (await context.startRendering()).getChannelData(0);
+    assert.equal(output[511], 2);
+    assert.equal(output[512], 0);
+  });
+
+  it('should pitch each note from rootPitch', async () => {
+    const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+    // A4
+    const sampler = new Sampler(context, { buffer: sineBuffer(440, 1), rootPitch: 69 });
+    sampler.connect(context.destination);
+
+    // A4, C#5, E5
+    const pitches = [69, 73, 76];
+    pitches.forEach((pitch, i) => sampler.noteOn(pitch, 127, i * 0.01));
+    assert.equal(sampler.activeVoices, 3);
+
+    const output = (await context.startRendering()).getChannelData(0);
+    const data = output.subarray(0.1 * sampleRate);
+
+    for (const pitch of pitches) {
+      const frequency = 440 * Math.pow(2, (pitch - 69) / 12);
+      assert.isAbove(amplitudeAt(data, frequency), 0.5, `pitch ${pitch}`);
+    }
+
+    // not played
+    assert.isBelow(amplitudeAt(data, 440 * Math.pow(2, 2 / 12)), 0.05);
+  });
+
+  it('should stop notes on noteOff and steal the oldest voice', async () => {
+    const context = new OfflineAudioContext(1, sampleRate / 2, sampleRate);
+    const sampler = new Sampler(context, {
+      buffer: sineBuffer(440, 1),
+      rootPitch: 69,
+      maxVoices: 2,
+    });
+    sampler.connect(context.destination);
+    assert.equal(sampler.maxVoices, 2);
+
+    sampler.noteOn(69);
+    sampler.noteOn(73);
+    // steals the voice of 69
+    sampler.noteOn(76, 64);
+    assert.equal(sampler.activeVoices, 2);
+    sampler.noteOff(73, 0.25);
+
+    const output = (await context.startRendering()).getChannelData(0);
+    const head = output.subarray(0, 0.25 * sampleRate);
+    const tail = output.subarray(0.25 * sampleRate);
+
+    assert.isBelow(amplitudeAt(head, 440), 0.05);
+    assert.isAbove(amplitudeAt(head, 440 * Math.pow(2, 4 / 12)), 0.5);
+    assert.isBelow(amplitudeAt(tail, 440 * Math.pow(2, 4 / 12)), 0.05);
+    // half velocity
+    assert.approximately(amplitudeAt(tail, 440 * Math.pow(2, 7 / 12)), 64 / 127 * Math.SQRT1_2, 0.05);
+  });
+
+  it('should fade out a stolen voice and not stop it again', async () => {
+    const context = new OfflineAudioContext(1, sampleRate / 4, sampleRate);
+    const buffer = new AudioBuffer({ length: sampleRate, sampleRate });
+    buffer.getChannelData(0).fill(1);
+
+    const sampler = new Sampler(context, { buffer, maxVoices: 1 });
+    sampler.connect(context.destination);
+
+    sampler.noteOn(69);
+    // steals the voice of 69 with a silent voice
+    sampler.noteOn(72, 0, 0.1);
+    // the stolen voice is not part of the notes anymore
+    sampler.noteOff(69, 0.15);
+    sampler.noteOff(72, 0.2);
+    sampler.stopAll(0.2);
+
+    const output = (await context.startRendering()).getChannelData(0);
+    let maxStep = 0;
+
+    for (let i = 1; i < output.length; i++) {
+      maxStep = Math.max(maxStep, Math.abs(output[i] - output[i - 1]));
+    }
+
+    // equal power panning of the centered voice
+    assert.approximately(output[0.05 * sampleRate], Math.SQRT1_2, 1e-6);
+    assert.equal(output[0.15 * sampleRate], 0);
+    assert.isBelow(maxStep, 0.01);
+  });
+
+  it('should throw InvalidStateError if no buffer is set', () => {
+    const context = new OfflineAudioContext(1, 128, sampleRate);
+    const sampler = new Sampler(context);
+
+    assert.throws(() => sampler.trigger(0), DOMException, /buffer/);
+  });
+});
@@ -0,0 +1,60 @@
+import { assert } from 'chai';
+import {
+  ConstantSourceNode,
+  OfflineAudioContext,
+  ScriptProcessorNode,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+
+describe('# ScriptProcessorNode', () => {
+  describe('## eventQueueSize (non spec)', () => {
+    it('should default to an unbounded queue', async () => {
+      const context = new OfflineAudioContext(1, 128 * 64, sampleRate);
+      const node = new ScriptProcessorNode(context, { bufferSize: 256 });
+      node.connect(context.destination);
+
+      assert.equal(node.droppedEvents, 0);
+
+      await context.startRendering();
+
+      assert.equal(node.droppedEvents, 0);
+    });
+
+    it('should count the events dropped when the queue is full', async () => {
+      const context = new OfflineAudioContext(1, sampleRate * 10, sampleRate);
+      const src = new ConstantSourceNode(context);
+      const node = new ScriptProcessorNode(context, {
+        bufferSize: 256,
+        numberOfInputChannels: 1,
+        numberOfOutputChannels: 1,
+        eventQueueSize: 1,
+      });
+      let received = 0;
+
+      node.onaudioprocess = e => {
+        received += 1;
+        e.outputBuffer.copyToChannel(e.inputBuffer.getChannelData(0), 0);
+      };
+
+      src.connect(node).connect(context.destination);
+      src.start();
+
+      await context.startRendering();
+      // let the queued events be dispatched
+      await new Promise(resolve => setTimeout(resolve, 100));
+
+      const dispatched = sampleRate * 10 / 256;
+
+      assert.isAbove(node.droppedEvents, 0);
+      assert.isAbove(received, 0);
+      assert.isAtMost(received + node.droppedEvents, dispatched + 1);
+    });
+
+    it('should throw a TypeError if the queue size is out of range', () => {
+      const context = new OfflineAudioContext(1, 128, sampleRate);
+
+      assert.throws(() => new ScriptProcessorNode(context, { eventQueueSize: -1 }), TypeError);
+    });
+  });
+});
@@ -0,0 +1,28 @@
+import { assert } from 'chai';
+import {
+  OfflineAudioContext,
+  WaveShaperNode,
+} from '../index.mjs';
+
+describe('# WaveShaperNode', () => {
+  describe('## curveLength (non spec)', () => {
+    it('should return the length of the curve without reading it', () => {
+      const context = new OfflineAudioContext(1, 128, 48000);
+      const node = new WaveShaperNode(context);
+      // make sure the curve data is never read back
+      const { set } = Object.getOwnPropertyDescriptor(WaveShaperNode.prototype, 'curve');
+      Object.defineProperty(node, 'curve', {
+        get() { throw new Error('curve getter should not be called'); },
+        set,
+      });
+
+      assert.equal(node.curveLength, 0);
+
+      node.curve = new Float32Array(1024);
+      assert.equal(node.curveLength, 1024);
+
+      const other = new WaveShaperNode(context, { curve: new Float32Array(3) });
+      assert.equal(other.curveLength, 3);
+    });
+  });
+});
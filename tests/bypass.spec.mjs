@@ -0,0 +1,107 @@
+import { assert } from 'chai';
+import {
+  OfflineAudioContext,
+  BiquadFilterNode,
+  ConvolverNode,
+  DynamicsCompressorNode,
+  WaveShaperNode,
+} from '../index.mjs';
+
+const sampleRate = 48000;
+// duration of the crossfade between the dry and wet signals
+const crossfadeFrames = 0.005 * sampleRate;
+
+async function render(createEffect, bypass) {
+  const audioContext = new OfflineAudioContext(1, 4096, sampleRate);
+  const src = audioContext.createOscillator();
+  const effect = createEffect(audioContext);
+  src.connect(effect).connect(audioContext.destination);
+  src.start();
+
+  effect.bypass = bypass;
+
+  return await audioContext.startRendering();
+}
+
+describe('# bypass (non spec)', () => {
+  [
+    ['BiquadFilterNode', ctx => new BiquadFilterNode(ctx, { frequency: 100 })],
+    ['ConvolverNode', ctx => {
+      const buffer = ctx.createBuffer(1, 10, sampleRate);
+      buffer.getChannelData(0)[9] = 0.5;
+      return new ConvolverNode(ctx, { buffer, disableNormalization: true });
+    }],
+    ['DynamicsCompressorNode', ctx => new DynamicsCompressorNode(ctx, { threshold: -50 })],
+    ['WaveShaperNode', ctx => new WaveShaperNode(ctx, { curve: new Float32Array([0.5, 0, 0.5]) })],
+  ].forEach(([name, createEffect]) => {
+    it(`${name} - should pass the input unprocessed when bypassed`, async () => {
+      const input = await render(ctx => ctx.createGain(), false);
+      const processed = await render(createEffect, false);
+      const bypassed = await render(createEffect, true);
+
+      const inputData = input.getChannelData(0);
+      const processedData = processed.getChannelData(0);
+      const bypassedData = bypassed.getChannelData(0);
+
+      assert.notDeepEqual(processedData, inputData);
+      // ignore the crossfade
+      assert.deepEqual(bypassedData.subarray(crossfadeFrames), inputData.subarray(crossfadeFrames));
+    });
+  });
+
+  it('should keep the connections when toggled', async () => {
+    const audioContext = new OfflineAudioContext(1, 4096, sampleRate);
+    const src = audioContext.createConstantSource();
+    const shaper = new WaveShaperNode(audioContext, {
+      curve: new Float32Array([0.5, 0.5, 0.5]),
+    });
+
+    src.connect(shaper).connect(audioContext.destination);
+    src.start();
+
+    assert.isFalse(shaper.bypass);
+    shaper.bypass = true;
+    assert.isTrue(shaper.bypass);
+
+    audioContext.suspend(2048 / sampleRate).then(() => {
+      shaper.bypass = false;
+      audioContext.resume();
+    });
+
+    const buffer = await audioContext.startRendering();
+    const data = buffer.getChannelData(0);
+
+    assert.equal(data[2047], 1);
+    assert.equal(data[4095], 0.5);
+    // the wet / dry crossfade should be smooth
+    for (let i = 1; i < data.length; i++) {
+      assert.isBelow(Math.abs(data[i] - data[i - 1]), 0.01);
+    }
+  });
+
+  it('should move the existing connections when first bypassed', async () => {
+    const audioContext = new OfflineAudioContext(1, 4096, sampleRate);
+    const src = audioContext.createConstantSource();
+    const shaper = new WaveShaperNode(audioContext, {
+      curve: new Float32Array([0.25, 0.25, 0.25]),
+    });
+
+    src.connect(shaper, 0, 0, { gain: 0.5 });
+    shaper.connect(audioContext.destination);
+    src.start();
+
+    shaper.bypass = true;
+
+    audioContext.suspend(2048 / sampleRate).then(() => {
+      src.disconnect(shaper);
+      audioContext.resume();
+    });
+
+    const buffer = await audioContext.startRendering();
+    const data = buffer.getChannelData(0);
+
+    assert.equal(data[2047], 0.5);
+    // the connection is removed from the routing input
+    assert.equal(data[2048 + 128], 0);
+  });
+});
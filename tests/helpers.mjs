@@ -0,0 +1,46 @@
+// Shared fixtures of the test suites
+
+// 16-bit PCM WAV file containing the given channels
+export function createWav(channels, sampleRate) {
+  const numberOfChannels = channels.length;
+  const length = channels[0].length;
+  const dataSize = length * numberOfChannels * 2;
+  const view = new DataView(new ArrayBuffer(44 + dataSize));
+  const writeString = (offset, str) => {
+    for (let i = 0; i < str.length; i++) {
+      view.setUint8(offset + i, str.charCodeAt(i));
+    }
+  };
+
+  writeString(0, 'RIFF');
+  view.setUint32(4, 36 + dataSize, true);
+  writeString(8, 'WAVE');
+  writeString(12, 'fmt ');
+  view.setUint32(16, 16, true);
+  view.setUint16(20, 1, true);
+  view.setUint16(22, numberOfChannels, true);
+  view.setUint32(24, sampleRate, true);
+  view.setUint32(28, sampleRate * numberOfChannels * 2, true);
+  view.setUint16(32, numberOfChannels * 2, true);
+  view.setUint16(34, 16, true);
+  writeString(36, 'data');
+  view.setUint32(40, dataSize, true);
+
+  for (let i = 0; i < length; i++) {
+    for (let channel = 0; channel < numberOfChannels; channel++) {
+      const offset = 44 + (i * numberOfChannels + channel) * 2;
+      view.setInt16(offset, Math.round(channels[channel][i] * 32767), true);
+    }
+  }
+
+  return view.buffer;
+}
+
+// root mean square of the samples of `data`
+export function rms(data) {
+  let sum = 0;
+  for (let i = 0; i < data.length; i++) {
+    sum += data[i] * data[i];
+  }
+  return Math.sqrt(sum / data.length);
+}